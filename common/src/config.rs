@@ -12,7 +12,14 @@ use serde::Deserialize;
 /// - `RUST_LOG` - Log level (default: "info")
 /// - `MAX_CONNECTIONS` - Maximum connections per pool (default: 10)
 /// - `CONNECT_TIMEOUT` - Connection timeout in seconds (default: 30)
+/// - `POOL_EVICT_AFTER` - Seconds without use after which a pool is shut down, 0 never (default: 300)
 /// - `DATA_DIR` - Data directory for persistence (default: "./data")
+/// - `SCHEMA_CACHE_TTL` - Schema cache lifetime in seconds (default: 300)
+/// - `SEARCH_INDEX_INTERVAL` - Metadata search index refresh interval in seconds (default: 600)
+/// - `HEALTH_CHECK_INTERVAL` - Seconds between health probes of open pools, 0 disables them (default: 30)
+/// - `ADMIN_TOKEN` - Token required for admin operations; admin operations are disabled when unset
+/// - `MASTER_KEY` - Comma-separated base64 keys that encrypt stored secrets, active key first
+/// - `MASTER_KEY_FILE` - File holding the master keys when `MASTER_KEY` is unset (default: `<DATA_DIR>/master.key`)
 #[derive(Debug, Clone, Deserialize)]
 pub struct AppConfig {
     /// Server host address.
@@ -35,10 +42,38 @@ pub struct AppConfig {
     #[serde(default = "default_connect_timeout")]
     pub connect_timeout_secs: u64,
 
+    /// Seconds without use after which a connection pool is shut down; 0 keeps pools open.
+    #[serde(default = "default_pool_evict_after")]
+    pub pool_evict_after_secs: u64,
+
     /// Data directory for persistence.
     #[serde(default = "default_data_dir")]
     pub data_dir: String,
 
+    /// Lifetime of cached schema metadata in seconds.
+    #[serde(default = "default_schema_cache_ttl")]
+    pub schema_cache_ttl_secs: u64,
+
+    /// Interval between metadata search index refreshes in seconds.
+    #[serde(default = "default_search_index_interval")]
+    pub search_index_interval_secs: u64,
+
+    /// Interval between health probes of open pools in seconds; 0 disables the health monitor.
+    #[serde(default = "default_health_check_interval")]
+    pub health_check_interval_secs: u64,
+
+    /// Token that authorizes admin operations, sent in the `X-Admin-Token` header.
+    #[serde(default)]
+    pub admin_token: Option<String>,
+
+    /// Base64 master keys that encrypt stored secrets, active key first.
+    #[serde(default)]
+    pub master_key: Option<String>,
+
+    /// File holding the master keys, used when `master_key` is unset.
+    #[serde(default)]
+    pub master_key_file: Option<String>,
+
     /// Service name for identification.
     #[serde(default = "default_service_name")]
     pub service_name: String,
@@ -64,7 +99,26 @@ impl AppConfig {
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or_else(default_connect_timeout),
+            pool_evict_after_secs: std::env::var("POOL_EVICT_AFTER")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or_else(default_pool_evict_after),
             data_dir: std::env::var("DATA_DIR").unwrap_or_else(|_| default_data_dir()),
+            schema_cache_ttl_secs: std::env::var("SCHEMA_CACHE_TTL")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or_else(default_schema_cache_ttl),
+            search_index_interval_secs: std::env::var("SEARCH_INDEX_INTERVAL")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or_else(default_search_index_interval),
+            health_check_interval_secs: std::env::var("HEALTH_CHECK_INTERVAL")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or_else(default_health_check_interval),
+            admin_token: std::env::var("ADMIN_TOKEN").ok().filter(|v| !v.is_empty()),
+            master_key: std::env::var("MASTER_KEY").ok().filter(|v| !v.is_empty()),
+            master_key_file: std::env::var("MASTER_KEY_FILE").ok().filter(|v| !v.is_empty()),
             service_name: std::env::var("SERVICE_NAME").unwrap_or_else(|_| default_service_name()),
         }
     }
@@ -107,11 +161,31 @@ fn default_connect_timeout() -> u64 {
     30
 }
 
+/// Default idle period before a pool is shut down.
+fn default_pool_evict_after() -> u64 {
+    300
+}
+
 /// Default data directory.
 fn default_data_dir() -> String {
     "./data".to_string()
 }
 
+/// Default schema cache lifetime.
+fn default_schema_cache_ttl() -> u64 {
+    300
+}
+
+/// Default search index refresh interval.
+fn default_search_index_interval() -> u64 {
+    600
+}
+
+/// Default health probe interval.
+fn default_health_check_interval() -> u64 {
+    30
+}
+
 /// Default service name.
 fn default_service_name() -> String {
     "unknown".to_string()
@@ -61,6 +61,10 @@ pub enum AppError {
     #[error("database query failed: {0}")]
     DatabaseQuery(String),
 
+    /// No pooled connection became free within the acquire timeout.
+    #[error("connection pool exhausted: {0}")]
+    PoolExhausted(String),
+
     /// Redis connection error.
     #[error("redis connection failed: {0}")]
     RedisConnection(String),
@@ -110,6 +114,7 @@ impl AppError {
             // Server errors
             AppError::DatabaseConnection(_) => "DATABASE_CONNECTION_ERROR",
             AppError::DatabaseQuery(_) => "DATABASE_QUERY_ERROR",
+            AppError::PoolExhausted(_) => "DB_POOL_EXHAUSTED",
             AppError::RedisConnection(_) => "REDIS_CONNECTION_ERROR",
             AppError::RedisOperation(_) => "REDIS_OPERATION_ERROR",
             AppError::Internal(_) => "INTERNAL_ERROR",
@@ -137,6 +142,7 @@ impl AppError {
             // Server errors (5xx)
             AppError::DatabaseConnection(_) => StatusCode::BAD_GATEWAY,
             AppError::DatabaseQuery(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            AppError::PoolExhausted(_) => StatusCode::SERVICE_UNAVAILABLE,
             AppError::RedisConnection(_) => StatusCode::BAD_GATEWAY,
             AppError::RedisOperation(_) => StatusCode::INTERNAL_SERVER_ERROR,
             AppError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
@@ -167,6 +173,7 @@ impl AppError {
             AppError::UnsafeSql(_) => code::DB_UNSAFE_SQL,
             AppError::DatabaseConnection(_) => code::DB_CONNECTION_ERROR,
             AppError::DatabaseQuery(_) => code::DB_QUERY_ERROR,
+            AppError::PoolExhausted(_) => code::DB_POOL_EXHAUSTED,
             AppError::RedisConnection(_) => code::REDIS_CONNECTION_ERROR,
             AppError::RedisOperation(_) => code::REDIS_OPERATION_ERROR,
             
@@ -227,7 +234,7 @@ impl From<sqlx::Error> for AppError {
         match err {
             sqlx::Error::RowNotFound => AppError::NotFound("Database record not found".into()),
             sqlx::Error::PoolTimedOut => {
-                AppError::Timeout("Database connection pool timeout".into())
+                AppError::PoolExhausted("timed out waiting for a free connection".into())
             }
             sqlx::Error::Configuration(e) => AppError::Configuration(e.to_string()),
             _ => AppError::DatabaseQuery(err.to_string()),
@@ -259,23 +266,39 @@ impl From<std::io::Error> for AppError {
 
 impl From<validator::ValidationErrors> for AppError {
     fn from(err: validator::ValidationErrors) -> Self {
-        let errors: Vec<String> = err
-            .field_errors()
-            .iter()
-            .flat_map(|(field, errors)| {
-                errors.iter().map(move |e| {
-                    format!(
-                        "{}: {}",
-                        field,
-                        e.message.as_ref().map(|m| m.to_string()).unwrap_or_default()
-                    )
-                })
-            })
-            .collect();
+        let mut errors = Vec::new();
+        collect_validation_errors(&err, "", &mut errors);
         AppError::Validation(errors.join("; "))
     }
 }
 
+/// Flattens validation errors into `path: message` strings.
+///
+/// Nested fields are joined with `.`; struct-level errors are reported under
+/// the path of the struct itself.
+fn collect_validation_errors(err: &validator::ValidationErrors, prefix: &str, out: &mut Vec<String>) {
+    use validator::ValidationErrorsKind;
+
+    for (field, kind) in err.errors() {
+        let path = match (prefix.is_empty(), field.as_ref()) {
+            (_, "__all__") => prefix.to_string(),
+            (true, field) => field.to_string(),
+            (false, field) => format!("{}.{}", prefix, field),
+        };
+        match kind {
+            ValidationErrorsKind::Field(errors) => out.extend(errors.iter().map(|e| {
+                format!("{}: {}", path, e.message.as_ref().map(|m| m.to_string()).unwrap_or_default())
+            })),
+            ValidationErrorsKind::Struct(nested) => collect_validation_errors(nested, &path, out),
+            ValidationErrorsKind::List(items) => {
+                for (index, nested) in items {
+                    collect_validation_errors(nested, &format!("{}[{}]", path, index), out);
+                }
+            }
+        }
+    }
+}
+
 impl From<reqwest::Error> for AppError {
     fn from(err: reqwest::Error) -> Self {
         if err.is_timeout() {
@@ -4,11 +4,16 @@
 
 use axum::{
     body::Body,
-    http::{Request, StatusCode},
+    http::{header::HeaderName, HeaderMap, Request, StatusCode},
     middleware::Next,
     response::Response,
 };
 
+use crate::errors::{AppError, AppResult};
+
+/// Header carrying the admin token.
+pub static ADMIN_TOKEN_HEADER: HeaderName = HeaderName::from_static("x-admin-token");
+
 /// Authentication middleware handler.
 ///
 /// Validates authentication tokens and authorizes requests.
@@ -38,3 +43,48 @@ pub fn extract_bearer_token(req: &Request<Body>) -> Option<&str> {
         .and_then(|v| v.to_str().ok())
         .and_then(|v| v.strip_prefix("Bearer "))
 }
+
+/// Checks that a request carries the configured admin token.
+///
+/// Admin operations are disabled when no token is configured. A missing
+/// header yields `Unauthorized`, a wrong token `Forbidden`.
+pub fn authorize_admin(headers: &HeaderMap, expected: Option<&str>) -> AppResult<()> {
+    let Some(expected) = expected else {
+        return Err(AppError::Forbidden(
+            "admin operations are disabled; set ADMIN_TOKEN to enable them".into(),
+        ));
+    };
+    let provided = headers
+        .get(&ADMIN_TOKEN_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .ok_or(AppError::Unauthorized)?;
+    if constant_time_eq(provided.as_bytes(), expected.as_bytes()) {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden("invalid admin token".into()))
+    }
+}
+
+/// Compares two byte strings without short-circuiting on the first difference.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn test_authorize_admin() {
+        let mut headers = HeaderMap::new();
+        assert!(matches!(authorize_admin(&headers, None), Err(AppError::Forbidden(_))));
+        assert!(matches!(authorize_admin(&headers, Some("secret")), Err(AppError::Unauthorized)));
+
+        headers.insert(ADMIN_TOKEN_HEADER.clone(), HeaderValue::from_static("wrong"));
+        assert!(matches!(authorize_admin(&headers, Some("secret")), Err(AppError::Forbidden(_))));
+
+        headers.insert(ADMIN_TOKEN_HEADER.clone(), HeaderValue::from_static("secret"));
+        assert!(authorize_admin(&headers, Some("secret")).is_ok());
+    }
+}
@@ -4,5 +4,5 @@ pub mod auth;
 pub mod request_id;
 
 // Re-export commonly used types
-pub use auth::auth_middleware;
+pub use auth::{auth_middleware, authorize_admin, ADMIN_TOKEN_HEADER};
 pub use request_id::{request_id_middleware, RequestId, REQUEST_ID_HEADER};
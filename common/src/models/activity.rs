@@ -0,0 +1,144 @@
+//! Server activity models.
+//!
+//! Contains models for listing the sessions and running queries of a
+//! database server and for terminating them.
+
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+/// Query parameters for listing server activity.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ActivityQuery {
+    /// Include idle sessions (default false).
+    pub include_idle: Option<bool>,
+}
+
+/// One server session and its current query.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SessionActivity {
+    /// Server process or thread ID.
+    pub pid: i64,
+    /// Database user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    /// Current database.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub database: Option<String>,
+    /// Client address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client: Option<String>,
+    /// Client application name (PostgreSQL).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub application: Option<String>,
+    /// Normalized state: `active`, `idle`, `idle in transaction`, ... on
+    /// PostgreSQL; the lowercased command with `Sleep` as `idle` on MySQL.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    /// Current or last SQL text.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+    /// When the session entered its current state.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<String>,
+    /// Time spent in the current state in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<i64>,
+    /// What the session is waiting on, if anything.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wait_event: Option<String>,
+    /// Whether this is the session serving this request.
+    pub current: bool,
+}
+
+/// How a session is stopped.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum KillMode {
+    /// Cancel the running query and keep the session.
+    Cancel,
+    /// Close the session.
+    #[default]
+    Terminate,
+}
+
+/// Request body for terminating a session.
+#[derive(Debug, Clone, Default, Deserialize, ToSchema)]
+pub struct KillSessionRequest {
+    /// Cancel the query or close the session (default terminate).
+    #[serde(default)]
+    pub mode: KillMode,
+    /// Reason recorded in the audit trail.
+    pub reason: Option<String>,
+}
+
+/// Result of terminating a session.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct KillSessionResult {
+    /// Server process or thread ID.
+    pub pid: i64,
+    /// Applied mode.
+    pub mode: KillMode,
+    /// ID of the audit trail entry.
+    pub audit_id: String,
+}
+
+/// One lock held or requested by a session.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct LockItem {
+    /// Session holding or requesting the lock.
+    pub pid: i64,
+    /// Lockable object kind, e.g. `relation`, `tuple`, `transactionid` (PostgreSQL) or `TABLE`, `RECORD` (MySQL).
+    pub lock_type: String,
+    /// Lock mode.
+    pub mode: String,
+    /// Whether the lock is held (`false` while waiting for it).
+    pub granted: bool,
+    /// Locked table as `schema.table`, if the lock is on a table.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relation: Option<String>,
+}
+
+/// A session in the blocker → waiter tree.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BlockingNode {
+    /// Server process or thread ID.
+    pub pid: i64,
+    /// Database user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    /// Session state.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    /// Current or last SQL text.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+    /// Time spent in the current state in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<i64>,
+    /// Time spent waiting for a lock in milliseconds; absent for sessions that are not waiting.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wait_ms: Option<i64>,
+    /// The lock being waited for, e.g. `RowExclusiveLock on public.orders`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub waiting_for: Option<String>,
+    /// Whether the session is part of a blocking cycle (deadlock).
+    pub deadlocked: bool,
+    /// Sessions waiting on this one. Sessions of a cycle are listed once.
+    #[schema(no_recursion)]
+    pub blocked: Vec<BlockingNode>,
+}
+
+/// Current locks and blocking relationships of a server.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct LockReport {
+    /// Root blockers (sessions that block others but are not waiting themselves)
+    /// with their waiters as children. Cycles are rooted at their lowest pid.
+    pub blocking: Vec<BlockingNode>,
+    /// Number of sessions waiting for a lock.
+    pub waiting_sessions: usize,
+    /// Sessions in blocking cycles.
+    pub deadlocked: Vec<i64>,
+    /// Locks of client sessions other than this request's.
+    pub locks: Vec<LockItem>,
+}
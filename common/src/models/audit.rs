@@ -0,0 +1,56 @@
+//! Audit trail models.
+//!
+//! Contains models for the append-only record of privileged operations such
+//! as terminating database sessions.
+
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+/// Result of an audited operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOutcome {
+    /// The operation succeeded.
+    Success,
+    /// The operation failed; `detail` holds the error.
+    Failure,
+}
+
+/// One audit trail entry.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AuditEntry {
+    /// Entry identifier.
+    pub id: String,
+    /// Timestamp of the operation.
+    pub timestamp: String,
+    /// Operation name, e.g. `session.terminate`.
+    pub action: String,
+    /// Connection the operation applied to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connection_id: Option<String>,
+    /// Target of the operation, e.g. a session ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    /// ID of the request that performed the operation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    /// Result of the operation.
+    pub outcome: AuditOutcome,
+    /// Reason given by the caller or error message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+/// Query parameters for reading the audit trail.
+#[derive(Debug, Default, Deserialize, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AuditQuery {
+    /// Only entries for this connection.
+    pub connection_id: Option<String>,
+    /// Only entries with this action.
+    pub action: Option<String>,
+    /// Maximum number of entries, newest first (default 100, at most 1000).
+    #[validate(range(min = 1, max = 1000, message = "limit must be 1-1000"))]
+    pub limit: Option<u32>,
+}
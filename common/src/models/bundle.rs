@@ -0,0 +1,152 @@
+//! Connection bundle models.
+//!
+//! Contains models for exporting saved connections as a versioned bundle
+//! and importing them into another installation.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::ToSchema;
+use validator::Validate;
+
+/// Current bundle format version.
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// Request body for exporting connections.
+#[derive(Debug, Clone, Default, Deserialize, Validate, ToSchema)]
+pub struct ExportBundleRequest {
+    /// IDs of the connections to export; all connections when empty.
+    #[serde(default)]
+    pub ids: Vec<String>,
+    /// Only connections in this folder or its subfolders.
+    pub folder: Option<String>,
+    /// Passphrase that encrypts the secrets in the bundle. Secrets are
+    /// omitted when unset.
+    #[validate(length(min = 8, max = 1024, message = "passphrase must be 8-1024 characters"))]
+    pub passphrase: Option<String>,
+}
+
+/// Key derivation settings of a bundle whose secrets are encrypted.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BundleEncryption {
+    /// Key derivation function; always `pbkdf2-sha256`.
+    pub kdf: String,
+    /// PBKDF2 iteration count.
+    pub iterations: u32,
+    /// Base64 salt.
+    pub salt: String,
+    /// Known value encrypted with the derived key, used to check the passphrase.
+    pub check: String,
+}
+
+/// A versioned set of exported connections.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ConnectionBundle {
+    /// Format version; see [`BUNDLE_VERSION`].
+    pub version: u32,
+    /// Export timestamp.
+    pub exported_at: String,
+    /// Key derivation settings, present when secrets are included.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encryption: Option<BundleEncryption>,
+    /// Exported connections: the connection settings plus `secrets`, a map
+    /// from secret field (`password`, `tls.client_key`, `ssh_tunnel.password`,
+    /// `options.<key>`, ...) to its encrypted value, or `omitted_secrets`,
+    /// the secret fields left out.
+    #[schema(value_type = Vec<Object>)]
+    pub connections: Vec<Value>,
+}
+
+/// Format of the data to import.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportFormat {
+    /// A bundle produced by the export endpoint.
+    #[default]
+    Bundle,
+    /// A DBeaver `data-sources.json` file. Passwords kept in DBeaver's
+    /// encrypted credentials file are not imported.
+    Dbeaver,
+}
+
+/// How an imported connection whose ID already exists is handled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictMode {
+    /// Keep the existing connection and skip the imported one.
+    #[default]
+    Skip,
+    /// Replace the existing connection.
+    Overwrite,
+    /// Import under a new ID; a name already in use gets a numbered suffix.
+    Rename,
+}
+
+/// Request body for importing connections.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct ImportBundleRequest {
+    /// Format of `data`.
+    #[serde(default)]
+    pub format: ImportFormat,
+    /// The bundle or DBeaver file contents.
+    #[schema(value_type = Object)]
+    pub data: Value,
+    /// Handling of connections whose ID already exists.
+    #[serde(default)]
+    pub on_conflict: ConflictMode,
+    /// Passphrase of a bundle with encrypted secrets; without it the secrets
+    /// are not imported.
+    pub passphrase: Option<String>,
+}
+
+/// Outcome of importing one connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportOutcome {
+    /// Added as a new connection.
+    Created,
+    /// Replaced an existing connection with the same ID.
+    Overwritten,
+    /// Added under a new ID because the ID was taken.
+    Renamed,
+    /// Skipped because the ID was taken.
+    Skipped,
+    /// Not imported because the entry is invalid.
+    Failed,
+}
+
+/// Result of importing one connection.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ImportEntryResult {
+    /// Position of the entry in the imported data.
+    pub index: usize,
+    /// ID of the entry in the imported data.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_id: Option<String>,
+    /// Connection name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// ID of the connection after the import.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// What happened to the entry.
+    pub outcome: ImportOutcome,
+    /// Secret fields the connection had in the source but that were not imported.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub missing_secrets: Vec<String>,
+    /// Why the entry failed or was skipped.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Report of an import.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ImportReport {
+    /// Connections added or replaced.
+    pub imported: usize,
+    /// Entries skipped because of an ID conflict.
+    pub skipped: usize,
+    /// Entries that failed validation.
+    pub failed: usize,
+    /// Per-entry results in input order.
+    pub results: Vec<ImportEntryResult>,
+}
@@ -0,0 +1,97 @@
+//! SQL completion models.
+//!
+//! Contains models for context-aware SQL autocompletion in editors, served
+//! from a per-connection schema cache.
+
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+/// Request body for SQL completion.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct CompletionRequest {
+    /// Connection to complete against.
+    #[validate(length(min = 1, message = "connection_id is required"))]
+    pub connection_id: String,
+    /// SQL text in the editor.
+    pub sql: String,
+    /// Cursor position as a character offset into `sql`.
+    pub cursor: usize,
+    /// Default schema for unqualified names. Defaults to the connection's current schema.
+    pub schema: Option<String>,
+    /// Maximum number of suggestions (default 50, at most 500).
+    #[validate(range(min = 1, max = 500, message = "limit must be 1-500"))]
+    pub limit: Option<u32>,
+}
+
+/// Kind of completion suggestion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CompletionKind {
+    /// SQL keyword of the dialect.
+    Keyword,
+    /// Built-in function.
+    Function,
+    /// Schema (or database).
+    Schema,
+    /// Table.
+    Table,
+    /// View.
+    View,
+    /// Column of a table.
+    Column,
+    /// Alias of a table in the statement.
+    Alias,
+}
+
+/// One completion suggestion.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CompletionItem {
+    /// Text to insert.
+    pub label: String,
+    /// Kind of suggestion.
+    pub kind: CompletionKind,
+    /// Extra information, e.g. the owning table and type of a column.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// Relevance; higher is better. Items are sorted by it.
+    pub score: u32,
+}
+
+/// Completion suggestions for a cursor position.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CompletionResult {
+    /// Partial word before the cursor that suggestions replace.
+    pub prefix: String,
+    /// Ranked suggestions.
+    pub items: Vec<CompletionItem>,
+    /// When the schema model used for completion was loaded.
+    pub schema_loaded_at: String,
+}
+
+/// Query parameters for refreshing the schema cache.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SchemaCacheQuery {
+    /// Schema to reload. Defaults to the connection's current schema.
+    pub schema: Option<String>,
+}
+
+/// State of a cached schema model.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SchemaCacheInfo {
+    /// Connection ID.
+    pub connection_id: String,
+    /// Cached schema.
+    pub schema: String,
+    /// Number of schemas on the server.
+    pub schemas: usize,
+    /// Number of tables and views in the schema.
+    pub tables: usize,
+    /// Number of columns in the schema.
+    pub columns: usize,
+    /// Load timestamp.
+    pub loaded_at: String,
+    /// Seconds until the entry expires.
+    pub expires_in_secs: u64,
+}
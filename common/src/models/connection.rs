@@ -2,10 +2,15 @@
 //!
 //! Contains models for database connection management.
 
-use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Deserializer, Serialize};
+use utoipa::{IntoParams, ToSchema};
 use validator::Validate;
 
+use crate::errors::{AppError, AppResult};
+use crate::utils::dsn;
+
 /// Database type enumeration.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
 #[serde(rename_all = "lowercase")]
@@ -126,8 +131,312 @@ pub struct ConnectionConfig {
     /// SQLite file path.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub file_path: Option<String>,
+    /// Driver options passed in the connection URL, e.g. `sslmode`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub options: BTreeMap<String, String>,
+    /// Pool sizing and lifetime settings; unset values use the service defaults.
+    #[serde(default, skip_serializing_if = "PoolOptions::is_default")]
+    pub pool: PoolOptions,
+    /// TLS settings; `None` leaves TLS to the driver default and URL options.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls: Option<TlsOptions>,
+    /// SSH tunnel the database is reached through.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ssh_tunnel: Option<SshTunnelOptions>,
+    /// Whether data changes through this connection are refused.
+    #[serde(default)]
+    pub read_only: bool,
+    /// Folder path such as `shop/backend`; segments are separated by `/`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub folder: Option<String>,
+    /// Free-form tags.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Environment the database belongs to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<Environment>,
+    /// Display color as `#rgb` or `#rrggbb`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
     /// Creation timestamp.
     pub created_at: String,
+    /// Last update timestamp.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+}
+
+impl ConnectionConfig {
+    /// Whether write statements need explicit confirmation, which is the
+    /// case for connections labelled [`Environment::Prod`].
+    pub fn requires_write_confirmation(&self) -> bool {
+        self.environment == Some(Environment::Prod)
+    }
+
+    /// Whether the connection is in `folder` or one of its subfolders;
+    /// `folder` is compared segment by segment after normalization.
+    pub fn in_folder(&self, folder: &str) -> bool {
+        let Some(folder) = normalize_folder(folder) else { return true };
+        self.folder.as_deref().is_some_and(|f| {
+            f == folder || f.strip_prefix(&folder).is_some_and(|rest| rest.starts_with('/'))
+        })
+    }
+}
+
+/// Environment label of a connection.
+///
+/// Variants are declared from least to most critical, which is also the
+/// order used when sorting by environment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Environment {
+    /// Development.
+    Dev,
+    /// Testing.
+    Test,
+    /// Staging.
+    Staging,
+    /// Production; write statements require explicit confirmation.
+    Prod,
+}
+
+impl std::fmt::Display for Environment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Environment::Dev => write!(f, "dev"),
+            Environment::Test => write!(f, "test"),
+            Environment::Staging => write!(f, "staging"),
+            Environment::Prod => write!(f, "prod"),
+        }
+    }
+}
+
+/// Normalizes a folder path: segments are trimmed, empty segments dropped and
+/// the rest joined with `/`. Returns `None` for a path without segments.
+pub fn normalize_folder(folder: &str) -> Option<String> {
+    let path = folder
+        .split('/')
+        .map(str::trim)
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>()
+        .join("/");
+    (!path.is_empty()).then_some(path)
+}
+
+/// Trims tags and drops empty and duplicate ones, comparing case-insensitively
+/// and keeping the first spelling.
+pub fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags {
+        let tag = tag.trim();
+        if !tag.is_empty() && !normalized.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            normalized.push(tag.to_string());
+        }
+    }
+    normalized
+}
+
+fn validate_folder(folder: &str) -> Result<(), validator::ValidationError> {
+    if folder.chars().count() > 255 {
+        return Err(validator::ValidationError::new("folder").with_message("folder must be at most 255 characters".into()));
+    }
+    Ok(())
+}
+
+fn validate_tags(tags: &[String]) -> Result<(), validator::ValidationError> {
+    if tags.len() > 20 {
+        return Err(validator::ValidationError::new("tags").with_message("at most 20 tags are allowed".into()));
+    }
+    if tags.iter().any(|tag| tag.chars().count() > 50) {
+        return Err(validator::ValidationError::new("tags").with_message("tags must be at most 50 characters".into()));
+    }
+    Ok(())
+}
+
+fn validate_color(color: &str) -> Result<(), validator::ValidationError> {
+    let valid = color
+        .strip_prefix('#')
+        .is_some_and(|hex| matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit()));
+    if !valid {
+        return Err(validator::ValidationError::new("color").with_message("color must be #rgb or #rrggbb".into()));
+    }
+    Ok(())
+}
+
+/// Per-connection pool settings.
+///
+/// Unset values fall back to the service-wide defaults. Pools are opened on
+/// first use and shut down after `evict_after_secs` without use.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Validate, ToSchema)]
+#[validate(schema(function = "validate_pool_size"))]
+pub struct PoolOptions {
+    /// Connections kept open while the pool is open, even when idle.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_connections: Option<u32>,
+    /// Upper bound on open connections.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1, max = 1000, message = "max_connections must be 1-1000"))]
+    pub max_connections: Option<u32>,
+    /// Seconds to wait for a free connection before failing.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1, max = 3600, message = "acquire_timeout_secs must be 1-3600"))]
+    pub acquire_timeout_secs: Option<u64>,
+    /// Seconds after which an idle connection above `min_connections` is closed; 0 never.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub idle_timeout_secs: Option<u64>,
+    /// Seconds after which a connection is replaced, however busy; 0 never.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_lifetime_secs: Option<u64>,
+    /// Seconds without use after which the whole pool is shut down; 0 never.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub evict_after_secs: Option<u64>,
+}
+
+impl PoolOptions {
+    /// Whether no setting is overridden.
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+fn validate_pool_size(options: &PoolOptions) -> Result<(), validator::ValidationError> {
+    match (options.min_connections, options.max_connections) {
+        (Some(min), Some(max)) if min > max => Err(validator::ValidationError::new("pool_size")
+            .with_message("min_connections must not exceed max_connections".into())),
+        _ => Ok(()),
+    }
+}
+
+/// How strictly a connection uses TLS.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum TlsMode {
+    /// Never use TLS.
+    Disable,
+    /// Use TLS if the server supports it, without verifying the certificate.
+    #[default]
+    Prefer,
+    /// Require TLS without verifying the certificate.
+    Require,
+    /// Require TLS and a certificate signed by a trusted CA.
+    VerifyCa,
+    /// Require TLS, a trusted certificate and a matching host name.
+    VerifyFull,
+}
+
+impl TlsMode {
+    /// Whether the server certificate is verified.
+    pub fn verifies(self) -> bool {
+        matches!(self, TlsMode::VerifyCa | TlsMode::VerifyFull)
+    }
+}
+
+/// TLS settings of a connection.
+///
+/// Certificates and keys are given either as PEM content or as a path to a
+/// PEM file readable by the service.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Validate, ToSchema)]
+#[validate(schema(function = "validate_client_identity"))]
+pub struct TlsOptions {
+    /// TLS mode.
+    #[serde(default)]
+    pub mode: TlsMode,
+    /// CA certificates that sign the server certificate; the public web CAs are trusted when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ca_cert: Option<String>,
+    /// Client certificate for mutual TLS.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_cert: Option<String>,
+    /// Private key of the client certificate; stored encrypted and redacted in responses.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_key: Option<String>,
+}
+
+fn validate_client_identity(tls: &TlsOptions) -> Result<(), validator::ValidationError> {
+    if tls.client_cert.is_some() != tls.client_key.is_some() {
+        return Err(validator::ValidationError::new("client_identity")
+            .with_message("client_cert and client_key must be set together".into()));
+    }
+    Ok(())
+}
+
+/// SSH tunnel through a bastion host.
+///
+/// The database host and port are resolved on the bastion. The private key
+/// is given either as key content or as a path to a key file readable by the
+/// service. With a tunnel, MySQL and PostgreSQL connect to a local port, so
+/// their `verify-full` TLS mode cannot match the host name; use `verify-ca`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Validate, ToSchema)]
+#[validate(schema(function = "validate_ssh_auth"))]
+pub struct SshTunnelOptions {
+    /// Bastion host name or address.
+    #[validate(length(min = 1, max = 255, message = "host must be 1-255 characters"))]
+    pub host: String,
+    /// Bastion SSH port.
+    #[serde(default = "default_ssh_port")]
+    pub port: u16,
+    /// User name on the bastion.
+    #[validate(length(min = 1, max = 128, message = "user must be 1-128 characters"))]
+    pub user: String,
+    /// Password; stored encrypted and redacted in responses.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    /// Private key content or path; key content is stored encrypted and redacted in responses.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub private_key: Option<String>,
+    /// Passphrase of the private key; stored encrypted and redacted in responses.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub passphrase: Option<String>,
+}
+
+fn default_ssh_port() -> u16 {
+    22
+}
+
+impl SshTunnelOptions {
+    /// `user@host:port` of the bastion.
+    pub fn bastion(&self) -> String {
+        format!("{}@{}:{}", self.user, self.host, self.port)
+    }
+
+    /// Whether the private key is given as content rather than as a path.
+    pub fn has_inline_key(&self) -> bool {
+        self.private_key.as_deref().is_some_and(|key| key.trim_start().starts_with("-----BEGIN"))
+    }
+
+    /// Replaces secrets given as [`dsn::REDACTED`] with those of `previous`.
+    fn keep_secrets(mut self, previous: Option<&SshTunnelOptions>) -> Self {
+        let keep = |value: &mut Option<String>, old: Option<&String>| {
+            if value.as_deref() == Some(dsn::REDACTED) {
+                *value = old.cloned();
+            }
+        };
+        keep(&mut self.password, previous.and_then(|p| p.password.as_ref()));
+        keep(&mut self.private_key, previous.and_then(|p| p.private_key.as_ref()));
+        keep(&mut self.passphrase, previous.and_then(|p| p.passphrase.as_ref()));
+        self
+    }
+
+    /// Copy with secrets replaced by [`dsn::REDACTED`]; a key path is kept.
+    fn redacted(self) -> Self {
+        let redact = |value: Option<String>| value.map(|_| dsn::REDACTED.to_string());
+        Self {
+            private_key: if self.has_inline_key() { redact(self.private_key.clone()) } else { self.private_key.clone() },
+            password: redact(self.password),
+            passphrase: redact(self.passphrase),
+            ..self
+        }
+    }
+}
+
+fn validate_ssh_auth(ssh: &SshTunnelOptions) -> Result<(), validator::ValidationError> {
+    let error = |message: &'static str| Err(validator::ValidationError::new("ssh_auth").with_message(message.into()));
+    if ssh.password.is_none() && ssh.private_key.is_none() {
+        return error("password or private_key is required");
+    }
+    if ssh.passphrase.is_some() && ssh.private_key.is_none() {
+        return error("passphrase requires private_key");
+    }
+    Ok(())
 }
 
 /// Request body for creating a new connection.
@@ -136,8 +445,13 @@ pub struct CreateConnectionRequest {
     /// Connection display name.
     #[validate(length(min = 1, max = 100, message = "Name must be 1-100 characters"))]
     pub name: String,
-    /// Database type.
-    pub db_type: DbType,
+    /// Database type; derived from `url` when omitted.
+    #[serde(default)]
+    pub db_type: Option<DbType>,
+    /// Connection URL, e.g. `postgres://user@host:5432/db?sslmode=require`,
+    /// as an alternative to the discrete fields. Discrete fields that are set
+    /// take precedence over the URL.
+    pub url: Option<String>,
     /// Database host (required for mysql/postgres/redis/mongodb/clickhouse/elasticsearch/oracle/sqlserver/mariadb/cassandra/influxdb/db2/couchdb/neo4j/memcached/hbase/milvus).
     pub host: Option<String>,
     /// Database port (uses default if not specified).
@@ -150,26 +464,268 @@ pub struct CreateConnectionRequest {
     pub database: Option<String>,
     /// SQLite file path (required for sqlite).
     pub file_path: Option<String>,
+    /// Driver options, e.g. `sslmode`; merged over the URL's query parameters.
+    #[serde(default)]
+    pub options: BTreeMap<String, String>,
+    /// Pool settings.
+    #[serde(default)]
+    #[validate(nested)]
+    pub pool: PoolOptions,
+    /// TLS settings.
+    #[validate(nested)]
+    pub tls: Option<TlsOptions>,
+    /// SSH tunnel through a bastion host.
+    #[validate(nested)]
+    pub ssh_tunnel: Option<SshTunnelOptions>,
+    /// Refuse data changes through this connection.
+    #[serde(default)]
+    pub read_only: bool,
+    /// Folder path such as `shop/backend`.
+    #[validate(custom(function = "validate_folder"))]
+    pub folder: Option<String>,
+    /// Free-form tags.
+    #[serde(default)]
+    #[validate(custom(function = "validate_tags"))]
+    pub tags: Vec<String>,
+    /// Environment label; `prod` requires confirmation for write statements.
+    pub environment: Option<Environment>,
+    /// Display color as `#rgb` or `#rrggbb`.
+    #[validate(custom(function = "validate_color"))]
+    pub color: Option<String>,
 }
 
 impl CreateConnectionRequest {
+    /// Resolves `url` into the discrete fields and returns the database type.
+    ///
+    /// Fails when the URL is malformed, when neither `db_type` nor `url` is
+    /// given, or when they name different database types.
+    fn resolve(&mut self) -> AppResult<DbType> {
+        if let Some(url) = self.url.take() {
+            let parsed = dsn::parse(&url)?;
+            let scheme_type = parsed.db_type.expect("parsed URLs have a database type");
+            match &self.db_type {
+                Some(db_type) if *db_type != scheme_type => {
+                    return Err(AppError::Validation(format!(
+                        "url: scheme is for {} but db_type is {}",
+                        scheme_type, db_type
+                    )));
+                }
+                _ => self.db_type = Some(scheme_type),
+            }
+            self.host = self.host.take().or(parsed.host);
+            self.port = self.port.or(parsed.port);
+            self.username = self.username.take().or(parsed.username);
+            self.password = self.password.take().or(parsed.password);
+            self.database = self.database.take().or(parsed.database);
+            self.file_path = self.file_path.take().or(parsed.file_path);
+            let explicit = std::mem::take(&mut self.options);
+            self.options = parsed.options;
+            self.options.extend(explicit);
+        }
+        self.db_type
+            .clone()
+            .ok_or_else(|| AppError::Validation("either db_type or url is required".into()))
+    }
+
     /// Converts the request into a ConnectionConfig.
-    pub fn into_config(self, id: String, created_at: String) -> ConnectionConfig {
-        ConnectionConfig {
+    pub fn into_config(mut self, id: String, created_at: String) -> AppResult<ConnectionConfig> {
+        let db_type = self.resolve()?;
+        Ok(ConnectionConfig {
             id,
             name: self.name,
-            db_type: self.db_type.clone(),
+            port: self.port.or_else(|| db_type.default_port()),
+            db_type,
             host: self.host,
-            port: self.port.or_else(|| self.db_type.default_port()),
             username: self.username,
             password: self.password,
             database: self.database,
             file_path: self.file_path,
+            options: self.options,
+            pool: self.pool,
+            tls: self.tls,
+            ssh_tunnel: self.ssh_tunnel,
+            read_only: self.read_only,
+            folder: self.folder.as_deref().and_then(normalize_folder),
+            tags: normalize_tags(self.tags),
+            environment: self.environment,
+            color: self.color,
             created_at,
+            updated_at: None,
+        })
+    }
+
+    /// Converts the request into a full replacement: every field is set and
+    /// missing ones are cleared.
+    pub fn into_update(mut self) -> AppResult<UpdateConnectionRequest> {
+        let db_type = self.resolve()?;
+        Ok(UpdateConnectionRequest {
+            name: Some(self.name),
+            port: Some(self.port.or_else(|| db_type.default_port())),
+            db_type: Some(db_type),
+            host: Some(self.host),
+            username: Some(self.username),
+            password: Some(self.password),
+            database: Some(self.database),
+            file_path: Some(self.file_path),
+            options: Some(self.options),
+            pool: Some(self.pool),
+            tls: Some(self.tls),
+            ssh_tunnel: Some(self.ssh_tunnel),
+            read_only: Some(self.read_only),
+            folder: Some(self.folder),
+            tags: Some(self.tags),
+            environment: Some(self.environment),
+            color: Some(self.color),
+        })
+    }
+}
+
+/// Request body for updating a connection.
+///
+/// Absent fields are left unchanged and `null` clears an optional field.
+#[derive(Debug, Default, Deserialize, Validate, ToSchema)]
+pub struct UpdateConnectionRequest {
+    /// Connection display name.
+    #[validate(length(min = 1, max = 100, message = "Name must be 1-100 characters"))]
+    pub name: Option<String>,
+    /// Database type.
+    pub db_type: Option<DbType>,
+    /// Database host.
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<String>)]
+    pub host: Option<Option<String>>,
+    /// Database port.
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<u16>)]
+    pub port: Option<Option<u16>>,
+    /// Database username.
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<String>)]
+    pub username: Option<Option<String>>,
+    /// Database password.
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<String>)]
+    pub password: Option<Option<String>>,
+    /// Default database name.
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<String>)]
+    pub database: Option<Option<String>>,
+    /// SQLite file path.
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<String>)]
+    pub file_path: Option<Option<String>>,
+    /// Driver options; replaces all existing options.
+    pub options: Option<BTreeMap<String, String>>,
+    /// Pool settings; replaces all existing pool settings.
+    #[validate(nested)]
+    pub pool: Option<PoolOptions>,
+    /// TLS settings; replaces all existing TLS settings. A `client_key` of
+    /// `***`, as returned by the API, keeps the stored key.
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<TlsOptions>)]
+    pub tls: Option<Option<TlsOptions>>,
+    /// SSH tunnel; replaces the existing tunnel settings. Secrets of `***`,
+    /// as returned by the API, keep the stored values.
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<SshTunnelOptions>)]
+    pub ssh_tunnel: Option<Option<SshTunnelOptions>>,
+    /// Refuse data changes through this connection.
+    pub read_only: Option<bool>,
+    /// Folder path.
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<String>)]
+    #[validate(custom(function = "validate_folder"))]
+    pub folder: Option<Option<String>>,
+    /// Tags; replaces all existing tags.
+    #[validate(custom(function = "validate_tags"))]
+    pub tags: Option<Vec<String>>,
+    /// Environment label.
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<Environment>)]
+    pub environment: Option<Option<Environment>>,
+    /// Display color.
+    #[serde(default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<String>)]
+    #[validate(custom(function = "validate_color"))]
+    pub color: Option<Option<String>>,
+}
+
+impl UpdateConnectionRequest {
+    /// Applies the changes to `config`.
+    pub fn apply(self, config: ConnectionConfig) -> ConnectionConfig {
+        ConnectionConfig {
+            name: self.name.unwrap_or(config.name),
+            db_type: self.db_type.unwrap_or(config.db_type),
+            host: self.host.unwrap_or(config.host),
+            port: self.port.unwrap_or(config.port),
+            username: self.username.unwrap_or(config.username),
+            password: self.password.unwrap_or(config.password),
+            database: self.database.unwrap_or(config.database),
+            file_path: self.file_path.unwrap_or(config.file_path),
+            options: self.options.unwrap_or(config.options),
+            pool: self.pool.unwrap_or(config.pool),
+            tls: match self.tls {
+                Some(Some(mut tls)) => {
+                    if tls.client_key.as_deref() == Some(dsn::REDACTED) {
+                        tls.client_key = config.tls.as_ref().and_then(|t| t.client_key.clone());
+                    }
+                    Some(tls)
+                }
+                Some(None) => None,
+                None => config.tls,
+            },
+            ssh_tunnel: match self.ssh_tunnel {
+                Some(ssh) => ssh.map(|ssh| ssh.keep_secrets(config.ssh_tunnel.as_ref())),
+                None => config.ssh_tunnel,
+            },
+            read_only: self.read_only.unwrap_or(config.read_only),
+            folder: match self.folder {
+                Some(folder) => folder.as_deref().and_then(normalize_folder),
+                None => config.folder,
+            },
+            tags: self.tags.map(normalize_tags).unwrap_or(config.tags),
+            environment: self.environment.unwrap_or(config.environment),
+            color: self.color.unwrap_or(config.color),
+            ..config
         }
     }
 }
 
+/// Deserializes a present value, including `null`, as `Some`, so that an
+/// absent field (`None`) can be told apart from an explicit `null`.
+fn nullable<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// A saved connection rendered as a connection URL.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ConnectionDsn {
+    /// Connection identifier.
+    pub id: String,
+    /// Connection URL with the password redacted.
+    pub dsn: String,
+}
+
+/// Runtime status of a connection's pool.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ConnectionStatus {
+    /// The pool is open.
+    Connected,
+    /// The pool is open but its last probe or acquisition failed.
+    Degraded,
+    /// No pool is open, either not used yet or shut down after being idle;
+    /// it is opened on next use.
+    Idle,
+    /// The database could not be reached; the configuration is kept and
+    /// the pool is opened again on next use.
+    Disconnected,
+}
+
 /// Connection item for API responses (excludes sensitive data).
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ConnectionItem {
@@ -194,10 +750,46 @@ pub struct ConnectionItem {
     /// SQLite file path.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub file_path: Option<String>,
+    /// Driver options; secret values are redacted.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub options: BTreeMap<String, String>,
+    /// Pool settings overridden for this connection.
+    #[serde(default, skip_serializing_if = "PoolOptions::is_default")]
+    pub pool: PoolOptions,
+    /// TLS settings; the client key is redacted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls: Option<TlsOptions>,
+    /// SSH tunnel settings; secrets are redacted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ssh_tunnel: Option<SshTunnelOptions>,
+    /// Whether data changes through this connection are refused.
+    pub read_only: bool,
+    /// Folder path.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub folder: Option<String>,
+    /// Free-form tags.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Environment label.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<Environment>,
+    /// Display color.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
     /// Creation timestamp.
     pub created_at: String,
+    /// Last update timestamp.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+    /// Runtime status of the connection's pool.
+    pub status: ConnectionStatus,
+    /// Error of the last failed connect attempt, if disconnected.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
 }
 
+/// The pool status is not part of the configuration: it starts as
+/// [`ConnectionStatus::Idle`] and is filled in by the caller.
 impl From<ConnectionConfig> for ConnectionItem {
     fn from(config: ConnectionConfig) -> Self {
         Self {
@@ -209,7 +801,53 @@ impl From<ConnectionConfig> for ConnectionItem {
             username: config.username,
             database: config.database,
             file_path: config.file_path,
+            options: config
+                .options
+                .into_iter()
+                .map(|(key, value)| {
+                    let value = if dsn::is_secret_option(&key) { dsn::REDACTED.to_string() } else { value };
+                    (key, value)
+                })
+                .collect(),
+            pool: config.pool,
+            tls: config.tls.map(|tls| TlsOptions {
+                client_key: tls.client_key.map(|_| dsn::REDACTED.to_string()),
+                ..tls
+            }),
+            ssh_tunnel: config.ssh_tunnel.map(SshTunnelOptions::redacted),
+            read_only: config.read_only,
+            folder: config.folder,
+            tags: config.tags,
+            environment: config.environment,
+            color: config.color,
             created_at: config.created_at,
+            updated_at: config.updated_at,
+            status: ConnectionStatus::Idle,
+            last_error: None,
         }
     }
 }
+
+/// Query parameters for listing connections.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ConnectionListQuery {
+    /// Only connections in this folder or its subfolders.
+    pub folder: Option<String>,
+    /// Comma-separated tags; only connections having all of them, compared case-insensitively.
+    pub tags: Option<String>,
+    /// Only connections with this environment label.
+    pub environment: Option<Environment>,
+    /// Only connections of this database type.
+    pub db_type: Option<DbType>,
+    /// Case-insensitive substring of the connection name.
+    pub search: Option<String>,
+    /// Sort field, prefixed with `-` for descending (default `name`). One of
+    /// `name`, `folder`, `environment`, `db_type`, `created_at`, `updated_at`.
+    /// Ties are ordered by name and then by ID.
+    pub sort: Option<String>,
+    /// Page number, starting at 1.
+    pub page: Option<u32>,
+    /// Connections per page (default 50, at most 1000).
+    pub page_size: Option<u32>,
+}
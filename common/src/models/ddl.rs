@@ -0,0 +1,57 @@
+//! DDL generation models.
+//!
+//! Contains models for CREATE statement export and dialect translation.
+
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use super::connection::DbType;
+use super::metadata::TableKind;
+
+/// Query parameters for DDL export.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DdlQuery {
+    /// Schema of the table. Defaults to the connection's current schema.
+    pub schema: Option<String>,
+    /// Dialect to translate the DDL into (mysql, postgres, sqlite).
+    /// The native DDL is returned when omitted.
+    pub target: Option<DbType>,
+}
+
+/// CREATE statements for one table or view.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TableDdl {
+    /// Schema of the object.
+    pub schema: String,
+    /// Table or view name.
+    pub table: String,
+    /// Object kind.
+    pub kind: TableKind,
+    /// Dialect the DDL is written in.
+    pub dialect: DbType,
+    /// CREATE statements (table, indexes, comments), separated by blank lines.
+    pub ddl: String,
+    /// Column type conversions (only present for translated DDL).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub type_mappings: Vec<TypeMapping>,
+    /// Parts of the definition that could not be carried over.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// Column type conversion between dialects.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct TypeMapping {
+    /// Column name.
+    pub column: String,
+    /// Type in the source dialect.
+    pub source_type: String,
+    /// Type in the target dialect.
+    pub target_type: String,
+    /// Whether the conversion loses range, precision or constraints.
+    pub lossy: bool,
+    /// Explanation of the conversion.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
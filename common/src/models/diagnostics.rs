@@ -0,0 +1,78 @@
+//! Connection diagnostics models.
+//!
+//! Contains models for a dry-run connection test, which probes a connection
+//! step by step without saving it.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::connection::DbType;
+
+/// A stage of a connection test, in the order the stages run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticStage {
+    /// Resolving the host name to addresses.
+    Dns,
+    /// Opening a TCP connection to the first reachable address.
+    Tcp,
+    /// Opening the SSH tunnel through the bastion host.
+    Tunnel,
+    /// Negotiating TLS with the server.
+    Tls,
+    /// Logging in with the given credentials.
+    Auth,
+    /// Reading the server version.
+    Version,
+    /// Running a trivial probe query.
+    Latency,
+}
+
+/// Outcome of a diagnostic step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum StepStatus {
+    /// The step succeeded.
+    Ok,
+    /// The step failed; later steps are skipped.
+    Failed,
+    /// The step did not run or does not apply to the database type.
+    Skipped,
+}
+
+/// Result of one diagnostic step.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DiagnosticStep {
+    /// Stage this step covers.
+    pub stage: DiagnosticStage,
+    /// Outcome of the step.
+    pub status: StepStatus,
+    /// Time the step took in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    /// Resolved addresses, negotiated cipher, error message or reason for skipping.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+/// Result of a dry-run connection test.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ConnectionDiagnostics {
+    /// Whether every step succeeded.
+    pub success: bool,
+    /// Database type that was tested.
+    pub db_type: DbType,
+    /// `host:port` or file path that was tested, with the bastion when tunnelled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    /// Steps in the order they ran.
+    pub steps: Vec<DiagnosticStep>,
+    /// Server version reported by the database.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server_version: Option<String>,
+    /// Round-trip time of the probe query in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    /// Total time of the test in milliseconds.
+    pub total_ms: u64,
+}
@@ -0,0 +1,100 @@
+//! ER diagram models.
+//!
+//! Contains models for rendering the foreign-key relationship graph of a
+//! schema as Mermaid, Graphviz DOT or a JSON nodes/edges graph.
+
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use super::metadata::TableKind;
+
+/// Output format of an ER diagram.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ErFormat {
+    /// Mermaid `erDiagram` text.
+    #[default]
+    Mermaid,
+    /// Graphviz DOT text.
+    Dot,
+    /// Nodes and edges only.
+    Json,
+}
+
+/// Query parameters for ER diagram export.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ErDiagramQuery {
+    /// Schema to render. Defaults to the connection's current schema.
+    pub schema: Option<String>,
+    /// Output format (mermaid, dot, json). Defaults to mermaid.
+    pub format: Option<ErFormat>,
+    /// Only include tables whose name starts with this prefix.
+    pub prefix: Option<String>,
+    /// Comma-separated list of tables to include.
+    pub tables: Option<String>,
+    /// Table to center the graph on.
+    pub focus: Option<String>,
+    /// Maximum number of foreign-key hops from the focus table (default 1).
+    pub hops: Option<u32>,
+}
+
+/// Column shown in an ER diagram node.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ErColumn {
+    /// Column name.
+    pub name: String,
+    /// Native data type.
+    pub data_type: String,
+    /// Whether the column accepts NULL.
+    pub nullable: bool,
+    /// Whether the column is part of the primary key.
+    pub primary_key: bool,
+    /// Whether the column is part of a foreign key.
+    pub foreign_key: bool,
+}
+
+/// Table or view in an ER diagram.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ErNode {
+    /// Table name, used as node identifier.
+    pub id: String,
+    /// Object kind.
+    pub kind: TableKind,
+    /// Columns in ordinal order.
+    pub columns: Vec<ErColumn>,
+}
+
+/// Foreign-key relationship in an ER diagram, pointing from the referencing
+/// table to the referenced table.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ErEdge {
+    /// Foreign key name.
+    pub name: String,
+    /// Referencing table.
+    pub from: String,
+    /// Referencing columns.
+    pub from_columns: Vec<String>,
+    /// Referenced table.
+    pub to: String,
+    /// Referenced columns.
+    pub to_columns: Vec<String>,
+    /// Whether the referencing columns are unique (one-to-one instead of many-to-one).
+    pub one_to_one: bool,
+    /// Whether the referencing columns are nullable (the relationship is optional).
+    pub optional: bool,
+}
+
+/// Rendered ER diagram.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ErDiagram {
+    /// Output format.
+    pub format: ErFormat,
+    /// Rendered diagram text (absent for the JSON format).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    /// Tables in the diagram.
+    pub nodes: Vec<ErNode>,
+    /// Relationships between the tables.
+    pub edges: Vec<ErEdge>,
+}
@@ -0,0 +1,160 @@
+//! Catalog metadata models.
+//!
+//! Engine-neutral description of databases, schemas, tables, columns,
+//! indexes and keys, shared by every metadata-driven feature.
+
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+/// Query parameters selecting a schema.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SchemaQuery {
+    /// Schema (MySQL database, PostgreSQL schema, SQLite attached database).
+    /// Defaults to the connection's current schema.
+    pub schema: Option<String>,
+}
+
+/// Database or schema entry.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SchemaItem {
+    /// Database or schema name.
+    pub name: String,
+    /// Whether this is the connection's current database or schema.
+    pub is_current: bool,
+}
+
+/// Kind of a table-like catalog object.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TableKind {
+    /// Base table.
+    Table,
+    /// View.
+    View,
+    /// Materialized view (PostgreSQL).
+    MaterializedView,
+}
+
+impl std::fmt::Display for TableKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TableKind::Table => write!(f, "table"),
+            TableKind::View => write!(f, "view"),
+            TableKind::MaterializedView => write!(f, "materialized_view"),
+        }
+    }
+}
+
+/// Table or view entry.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct TableItem {
+    /// Owning schema.
+    pub schema: String,
+    /// Table name.
+    pub name: String,
+    /// Object kind.
+    pub kind: TableKind,
+    /// Estimated row count from catalog statistics.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub row_estimate: Option<i64>,
+    /// Table comment.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+/// Column definition.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct ColumnItem {
+    /// Column name.
+    pub name: String,
+    /// 1-based ordinal position.
+    pub ordinal: u32,
+    /// Full native type, including length/precision (e.g. `varchar(255)`).
+    pub data_type: String,
+    /// Whether the column accepts NULL.
+    pub nullable: bool,
+    /// Default value expression.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_value: Option<String>,
+    /// Whether the column is part of the primary key.
+    pub is_primary_key: bool,
+    /// Whether the column is auto-generated (auto_increment, serial, identity).
+    pub auto_increment: bool,
+    /// Column comment.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+/// Primary key definition.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct PrimaryKeyItem {
+    /// Constraint name, if the engine names primary keys.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Key columns in key order.
+    pub columns: Vec<String>,
+}
+
+/// Index definition.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct IndexItem {
+    /// Index name.
+    pub name: String,
+    /// Indexed columns or expressions in index order.
+    pub columns: Vec<String>,
+    /// Whether the index enforces uniqueness.
+    pub unique: bool,
+    /// Whether the index backs the primary key.
+    pub primary: bool,
+    /// Access method (btree, hash, ...), if reported.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index_type: Option<String>,
+}
+
+/// Foreign key definition.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct ForeignKeyItem {
+    /// Constraint name.
+    pub name: String,
+    /// Referencing columns.
+    pub columns: Vec<String>,
+    /// Schema of the referenced table.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub referenced_schema: Option<String>,
+    /// Referenced table.
+    pub referenced_table: String,
+    /// Referenced columns, in the same order as `columns`. Empty when they
+    /// cannot be determined, such as a SQLite key that omits them and refers
+    /// to a table without a matching primary key.
+    pub referenced_columns: Vec<String>,
+    /// ON UPDATE rule (e.g. `CASCADE`, `NO ACTION`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_update: Option<String>,
+    /// ON DELETE rule.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_delete: Option<String>,
+}
+
+/// Full description of one table.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct TableDetail {
+    /// Table entry.
+    pub table: TableItem,
+    /// Columns in ordinal order.
+    pub columns: Vec<ColumnItem>,
+    /// Primary key, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub primary_key: Option<PrimaryKeyItem>,
+    /// Indexes, including the one backing the primary key.
+    pub indexes: Vec<IndexItem>,
+    /// Outgoing foreign keys.
+    pub foreign_keys: Vec<ForeignKeyItem>,
+}
+
+impl TableDetail {
+    /// Looks up a column by name.
+    pub fn column(&self, name: &str) -> Option<&ColumnItem> {
+        self.columns.iter().find(|c| c.name == name)
+    }
+}
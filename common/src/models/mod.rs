@@ -1,10 +1,73 @@
 //! Shared data models for all microservices.
 
+pub mod activity;
+pub mod audit;
+pub mod bundle;
+pub mod completion;
 pub mod connection;
-pub mod database;
+pub mod diagnostics;
+pub mod ddl;
+pub mod er_diagram;
+pub mod metadata;
+pub mod pool;
+pub mod profile;
 pub mod query;
+pub mod schema_diff;
+pub mod search;
+pub mod secrets;
+pub mod snapshot;
+pub mod storage;
+pub mod table_data;
 
 // Re-export commonly used types
-pub use connection::{ConnectionConfig, ConnectionItem, CreateConnectionRequest, DbType};
-pub use database::{DatabaseItem, ListDatabasesRequest};
+pub use activity::{
+    ActivityQuery, BlockingNode, KillMode, KillSessionRequest, KillSessionResult, LockItem,
+    LockReport, SessionActivity,
+};
+pub use audit::{AuditEntry, AuditOutcome, AuditQuery};
+pub use bundle::{
+    BundleEncryption, ConflictMode, ConnectionBundle, ExportBundleRequest, ImportBundleRequest, ImportEntryResult,
+    ImportFormat, ImportOutcome, ImportReport,
+};
+pub use completion::{
+    CompletionItem, CompletionKind, CompletionRequest, CompletionResult, SchemaCacheInfo,
+    SchemaCacheQuery,
+};
+pub use connection::{
+    ConnectionConfig, ConnectionDsn, ConnectionItem, ConnectionListQuery, ConnectionStatus,
+    CreateConnectionRequest, DbType, Environment, PoolOptions, SshTunnelOptions, TlsMode, TlsOptions,
+    UpdateConnectionRequest,
+};
+pub use diagnostics::{ConnectionDiagnostics, DiagnosticStage, DiagnosticStep, StepStatus};
+pub use ddl::{DdlQuery, TableDdl, TypeMapping};
+pub use er_diagram::{ErColumn, ErDiagram, ErDiagramQuery, ErEdge, ErFormat, ErNode};
+pub use metadata::{
+    ColumnItem, ForeignKeyItem, IndexItem, PrimaryKeyItem, SchemaItem, SchemaQuery, TableDetail,
+    TableItem, TableKind,
+};
+pub use pool::{
+    AcquireStats, ConnectionHealth, HealthOverview, HealthState, HealthTransition, LatencyStats, PoolStats,
+    PoolStatsQuery, TunnelState, TunnelStatus,
+};
+pub use profile::{
+    ColumnProfile, DistinctMode, HistogramBucket, ProfileColumnKind, ProfileJob, ProfileJobStatus,
+    ProfileRequest, TableProfile, ValueFrequency,
+};
 pub use query::{ColumnInfo, QueryRequest, QueryResult};
+pub use schema_diff::{
+    ChangeKind, ColumnDiff, ForeignKeyDiff, IndexDiff, MigrationStep, PrimaryKeyDiff, SchemaDiff,
+    SchemaDiffRequest, TableDiff,
+};
+pub use search::{
+    IndexedConnection, SearchHit, SearchIndexStatus, SearchObjectType, SearchQuery, SearchResult,
+};
+pub use secrets::{KeyRotationResult, RotateKeyRequest};
+pub use snapshot::{
+    SchemaSnapshot, SnapshotChange, SnapshotResult, SnapshotSchedule, SnapshotScheduleRequest,
+    SnapshotSummary, SnapshotTrigger, TakeSnapshotRequest,
+};
+pub use storage::{IndexStorage, StorageQuery, TableStorage};
+pub use table_data::{
+    CountMode, FilterOperator, RowChange, RowChangeKind, RowChangeResult, RowChangesRequest,
+    RowChangesResult, RowFilter, RowsQuery,
+};
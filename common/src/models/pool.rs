@@ -0,0 +1,197 @@
+//! Connection pool statistics models.
+//!
+//! Contains models for the size, usage and probe history of a connection's
+//! pool, the state of the SSH tunnel it connects through, and the status
+//! history recorded by the health monitor.
+
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use super::connection::{ConnectionStatus, DbType};
+
+/// Query parameters for pool statistics.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PoolStatsQuery {
+    /// Acquire a connection and run `SELECT 1` / `PING` before reporting,
+    /// opening the pool if it is idle.
+    #[serde(default)]
+    pub probe: bool,
+}
+
+/// Wait times for connections acquired by probes.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct AcquireStats {
+    /// Number of acquisitions measured.
+    pub count: u64,
+    /// Number of acquisitions that timed out because every connection was in use.
+    pub timeouts: u64,
+    /// Wait of the most recent acquisition in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_wait_ms: Option<u64>,
+    /// Average wait in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avg_wait_ms: Option<u64>,
+    /// Longest wait in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_wait_ms: Option<u64>,
+}
+
+/// Statistics of one connection's pool.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PoolStats {
+    /// Connection identifier.
+    pub id: String,
+    /// Connection display name.
+    pub name: String,
+    /// Database type.
+    pub db_type: DbType,
+    /// `connected`, `degraded` when the last probe or acquisition failed,
+    /// `disconnected` when the pool could not be opened, or `idle` when no
+    /// pool is open.
+    pub status: ConnectionStatus,
+    /// Open connections; Redis uses a single multiplexed connection.
+    pub size: u32,
+    /// Open connections not checked out.
+    pub idle: u32,
+    /// Connections checked out.
+    pub in_use: u32,
+    /// Connections kept open even when idle.
+    pub min_connections: u32,
+    /// Upper bound on open connections.
+    pub max_connections: u32,
+    /// Acquisition wait times.
+    pub acquire: AcquireStats,
+    /// Time of the last successful connect or probe.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_success_at: Option<String>,
+    /// Time of the last probe, successful or not.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_probe_at: Option<String>,
+    /// Round-trip time of the last successful probe in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_probe_ms: Option<u64>,
+    /// Last connect or probe error.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    /// Time of the last error.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error_at: Option<String>,
+    /// SSH tunnel of the open pool, if the connection uses one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tunnel: Option<TunnelStatus>,
+}
+
+/// State of an SSH tunnel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TunnelState {
+    /// The SSH session is being established.
+    Connecting,
+    /// The port forward is up.
+    Up,
+    /// The SSH session was lost; it is re-established after a backoff.
+    Down,
+}
+
+/// Status of the SSH tunnel a pool connects through.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TunnelStatus {
+    /// Current state.
+    pub state: TunnelState,
+    /// `user@host:port` of the bastion.
+    pub bastion: String,
+    /// Local port forwarded to the database.
+    pub local_port: u16,
+    /// Time the current SSH session was established.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connected_since: Option<String>,
+    /// Number of times the SSH session was re-established.
+    pub reconnects: u32,
+    /// Last SSH error.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    /// Time of the last SSH error.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error_at: Option<String>,
+}
+
+/// Whether a connection's database was reachable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthState {
+    /// The last connect or probe succeeded.
+    Up,
+    /// The last connect or probe failed. A pool that is merely exhausted
+    /// stays up.
+    Down,
+}
+
+/// A change of a connection's health state.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct HealthTransition {
+    /// State entered.
+    pub state: HealthState,
+    /// Time of the change.
+    pub at: String,
+    /// Error that took the connection down.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Probe round-trip times over the most recent probes.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct LatencyStats {
+    /// Number of probes in the window.
+    pub samples: u32,
+    /// Median round-trip time in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub p50_ms: Option<u64>,
+    /// 95th percentile round-trip time in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub p95_ms: Option<u64>,
+    /// 99th percentile round-trip time in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub p99_ms: Option<u64>,
+    /// Longest round-trip time in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_ms: Option<u64>,
+}
+
+/// Health history of one connection.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ConnectionHealth {
+    /// Connection identifier.
+    pub id: String,
+    /// Connection display name.
+    pub name: String,
+    /// Pool status, as in [`PoolStats`].
+    pub status: ConnectionStatus,
+    /// Current health state; unset until the connection was first used or probed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<HealthState>,
+    /// Time the current state was entered.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<String>,
+    /// Failed connects and probes since the last success.
+    pub consecutive_failures: u32,
+    /// Number of times the health monitor rebuilt the pool.
+    pub rebuilds: u32,
+    /// Probe round-trip times.
+    pub latency: LatencyStats,
+    /// Recent state changes, newest first.
+    pub transitions: Vec<HealthTransition>,
+}
+
+/// Health of all connections.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct HealthOverview {
+    /// Connections that are up.
+    pub up: usize,
+    /// Connections that are down.
+    pub down: usize,
+    /// Connections not used or probed yet.
+    pub unknown: usize,
+    /// Per-connection health, ordered by name.
+    pub connections: Vec<ConnectionHealth>,
+}
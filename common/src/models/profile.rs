@@ -0,0 +1,182 @@
+//! Column profiling models.
+//!
+//! Contains models for computing per-column data statistics of a table
+//! (null ratio, distinct count, min/max, lengths, frequent values and
+//! histograms), optionally on a sample and as a background job.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::ToSchema;
+use validator::Validate;
+
+/// How distinct values are counted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DistinctMode {
+    /// `COUNT(DISTINCT column)` over the profiled rows.
+    Exact,
+    /// Use planner statistics where available, falling back to an exact count.
+    Approximate,
+}
+
+/// Request body for profiling a table.
+#[derive(Debug, Clone, Default, Deserialize, Validate, ToSchema)]
+pub struct ProfileRequest {
+    /// Schema of the table. Defaults to the connection's current schema.
+    pub schema: Option<String>,
+    /// Columns to profile. Defaults to all columns.
+    pub columns: Option<Vec<String>>,
+    /// Profile at most this many sampled rows instead of the whole table.
+    #[validate(range(min = 1, message = "sample_rows must be positive"))]
+    pub sample_rows: Option<u64>,
+    /// Number of most frequent values per column (default 10, at most 100).
+    #[validate(range(min = 1, max = 100, message = "top_n must be 1-100"))]
+    pub top_n: Option<u32>,
+    /// Number of histogram buckets for numeric and date columns (default 10, at most 100).
+    #[validate(range(min = 1, max = 100, message = "histogram_buckets must be 1-100"))]
+    pub histogram_buckets: Option<u32>,
+    /// How distinct values are counted. Defaults to approximate for large
+    /// unsampled tables and exact otherwise.
+    pub distinct: Option<DistinctMode>,
+    /// Run as a background job. Large unsampled tables always run in the background.
+    pub background: Option<bool>,
+}
+
+/// Statistics category of a column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProfileColumnKind {
+    /// Integer, decimal and floating-point columns.
+    Numeric,
+    /// Character columns.
+    Text,
+    /// Date and time columns.
+    Temporal,
+    /// Boolean columns.
+    Boolean,
+    /// JSON, binary and other columns.
+    Other,
+}
+
+/// A value and how often it occurs.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ValueFrequency {
+    /// Column value.
+    pub value: Value,
+    /// Number of rows with this value.
+    pub count: u64,
+}
+
+/// One equal-width histogram bucket. Bounds are numbers for numeric columns
+/// and RFC 3339 timestamps for date columns; the last bucket includes its upper bound.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct HistogramBucket {
+    /// Inclusive lower bound.
+    pub lower: Value,
+    /// Exclusive upper bound.
+    pub upper: Value,
+    /// Number of rows in the bucket.
+    pub count: u64,
+}
+
+/// Statistics of one column.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ColumnProfile {
+    /// Column name.
+    pub name: String,
+    /// Native data type.
+    pub data_type: String,
+    /// Statistics category.
+    pub kind: ProfileColumnKind,
+    /// Number of NULL values.
+    pub null_count: u64,
+    /// Share of NULL values (0 to 1).
+    pub null_ratio: f64,
+    /// Number of distinct non-NULL values.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distinct_count: Option<u64>,
+    /// Whether `distinct_count` comes from planner statistics.
+    pub distinct_approximate: bool,
+    /// Smallest value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<Value>,
+    /// Largest value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<Value>,
+    /// Average of numeric columns.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avg: Option<f64>,
+    /// Shortest length of text columns.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<u64>,
+    /// Longest length of text columns.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<u64>,
+    /// Average length of text columns.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avg_length: Option<f64>,
+    /// Most frequent non-NULL values, most frequent first.
+    pub top_values: Vec<ValueFrequency>,
+    /// Value distribution of numeric and date columns.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub histogram: Option<Vec<HistogramBucket>>,
+}
+
+/// Profile of a table.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TableProfile {
+    /// Schema of the table.
+    pub schema: String,
+    /// Table name.
+    pub table: String,
+    /// Number of rows profiled.
+    pub row_count: u64,
+    /// Whether only a sample of the table was profiled.
+    pub sampled: bool,
+    /// Per-column statistics in ordinal order.
+    pub columns: Vec<ColumnProfile>,
+    /// Completion timestamp.
+    pub profiled_at: String,
+    /// Time spent profiling in milliseconds.
+    pub elapsed_ms: u64,
+}
+
+/// State of a profiling job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProfileJobStatus {
+    /// Still profiling.
+    Running,
+    /// Finished; `result` is set.
+    Completed,
+    /// Failed; `error` is set.
+    Failed,
+}
+
+/// A profiling run, either finished inline or running in the background.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ProfileJob {
+    /// Job identifier.
+    pub id: String,
+    /// Connection ID.
+    pub connection_id: String,
+    /// Schema of the table.
+    pub schema: String,
+    /// Table name.
+    pub table: String,
+    /// Job state.
+    pub status: ProfileJobStatus,
+    /// Whether the job runs in the background.
+    pub background: bool,
+    /// Creation timestamp.
+    pub created_at: String,
+    /// Completion timestamp.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<String>,
+    /// Failure message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Profile, once completed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<TableProfile>,
+}
@@ -20,6 +20,10 @@ pub struct QueryRequest {
     /// Maximum number of rows to return (default: 1000).
     #[serde(default = "default_limit")]
     pub limit: Option<u32>,
+    /// Confirms a write statement on a connection labelled `prod`, where it
+    /// is refused without it.
+    #[serde(default)]
+    pub confirm_prod: bool,
 }
 
 fn default_limit() -> Option<u32> {
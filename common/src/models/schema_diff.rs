@@ -0,0 +1,144 @@
+//! Schema comparison models.
+//!
+//! Contains models for diffing two schemas and the migration script that
+//! makes the target schema match the source.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::connection::DbType;
+use super::metadata::{ColumnItem, ForeignKeyItem, IndexItem};
+
+/// Request body for comparing two schemas.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct SchemaDiffRequest {
+    /// Connection holding the desired schema.
+    pub source_id: String,
+    /// Schema on the source connection. Defaults to its current schema.
+    pub source_schema: Option<String>,
+    /// Connection to be migrated.
+    pub target_id: String,
+    /// Schema on the target connection. Defaults to its current schema.
+    pub target_schema: Option<String>,
+}
+
+/// Kind of change between source and target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    /// Present in source only; will be created on target.
+    Added,
+    /// Present in target only; will be dropped from target.
+    Removed,
+    /// Present in both but different.
+    Modified,
+}
+
+/// Difference for one table.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TableDiff {
+    /// Table name.
+    pub table: String,
+    /// Kind of change.
+    pub change: ChangeKind,
+    /// Column differences (only for modified tables).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub columns: Vec<ColumnDiff>,
+    /// Primary key columns in source and target, when they differ.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub primary_key: Option<PrimaryKeyDiff>,
+    /// Index differences (only for modified tables).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub indexes: Vec<IndexDiff>,
+    /// Foreign key differences (only for modified tables).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub foreign_keys: Vec<ForeignKeyDiff>,
+}
+
+/// Difference for one column.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ColumnDiff {
+    /// Column name.
+    pub name: String,
+    /// Kind of change.
+    pub change: ChangeKind,
+    /// Changed attributes (data_type, nullable, default_value).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attributes: Vec<String>,
+    /// Column in the source schema.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<ColumnItem>,
+    /// Column in the target schema.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<ColumnItem>,
+}
+
+/// Primary key difference.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PrimaryKeyDiff {
+    /// Primary key columns in the source schema.
+    pub source: Vec<String>,
+    /// Primary key columns in the target schema.
+    pub target: Vec<String>,
+}
+
+/// Difference for one index. Indexes are matched by columns and uniqueness, not by name.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct IndexDiff {
+    /// Kind of change (added or removed).
+    pub change: ChangeKind,
+    /// The index that is added or removed.
+    pub index: IndexItem,
+}
+
+/// Difference for one foreign key. Foreign keys are matched by columns and referenced columns.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ForeignKeyDiff {
+    /// Kind of change.
+    pub change: ChangeKind,
+    /// Foreign key in the source schema.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<ForeignKeyItem>,
+    /// Foreign key in the target schema.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<ForeignKeyItem>,
+}
+
+/// One statement of the migration script.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MigrationStep {
+    /// Execution order, starting at 1.
+    pub order: u32,
+    /// What the statement does.
+    pub description: String,
+    /// SQL statement in the target dialect.
+    pub sql: String,
+    /// Whether the statement can lose data (drops, type changes) and needs review.
+    pub destructive: bool,
+}
+
+/// Result of comparing two schemas.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SchemaDiff {
+    /// Source connection ID.
+    pub source_id: String,
+    /// Compared source schema.
+    pub source_schema: String,
+    /// Target connection ID.
+    pub target_id: String,
+    /// Compared target schema.
+    pub target_schema: String,
+    /// Dialect of the migration script (the target's).
+    pub dialect: DbType,
+    /// Whether the schemas are identical.
+    pub identical: bool,
+    /// Per-table differences.
+    pub tables: Vec<TableDiff>,
+    /// Ordered migration steps that make target match source.
+    pub migration: Vec<MigrationStep>,
+    /// Number of destructive migration steps.
+    pub destructive_steps: usize,
+    /// Differences that could not be expressed as migration statements.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
@@ -0,0 +1,105 @@
+//! Metadata search models.
+//!
+//! Contains models for searching table, view, column and index names and
+//! comments across all saved connections.
+
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+/// Kind of indexed database object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchObjectType {
+    /// Table.
+    Table,
+    /// View or materialized view.
+    View,
+    /// Column of a table or view.
+    Column,
+    /// Index of a table.
+    Index,
+}
+
+/// Query parameters for metadata search.
+#[derive(Debug, Clone, Default, Deserialize, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SearchQuery {
+    /// Search text. Matches names exactly, by prefix, by word prefix, by
+    /// substring or fuzzily (characters in order), and comments by substring.
+    /// `table.column` restricts column and index hits to matching tables.
+    #[validate(length(min = 1, max = 200, message = "q must be 1-200 characters"))]
+    pub q: String,
+    /// Only search this connection.
+    pub connection_id: Option<String>,
+    /// Comma-separated object types to include, e.g. `table,view`. Defaults to all.
+    pub types: Option<String>,
+    /// Maximum number of hits (default 50, at most 500).
+    #[validate(range(min = 1, max = 500, message = "limit must be 1-500"))]
+    pub limit: Option<u32>,
+}
+
+/// One search hit.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SearchHit {
+    /// Connection ID.
+    pub connection_id: String,
+    /// Connection display name.
+    pub connection_name: String,
+    /// Schema (or database) of the object.
+    pub schema: String,
+    /// Object type.
+    pub object_type: SearchObjectType,
+    /// Object name.
+    pub name: String,
+    /// Owning table of columns and indexes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub table: Option<String>,
+    /// Extra information: the data type of a column or the columns of an index.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// Object comment.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    /// Relevance; higher is better. Hits are sorted by it.
+    pub score: u32,
+}
+
+/// Search results.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SearchResult {
+    /// Search text.
+    pub query: String,
+    /// Number of matching objects before applying the limit.
+    pub total: usize,
+    /// Ranked hits.
+    pub hits: Vec<SearchHit>,
+    /// Number of connections currently in the index.
+    pub indexed_connections: usize,
+}
+
+/// Index state of one connection.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct IndexedConnection {
+    /// Connection ID.
+    pub connection_id: String,
+    /// Connection display name.
+    pub connection_name: String,
+    /// Number of indexed objects.
+    pub objects: usize,
+    /// Timestamp of the last successful indexing.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indexed_at: Option<String>,
+    /// Error of the last indexing attempt; previously indexed objects stay searchable.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// State of the search index.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SearchIndexStatus {
+    /// Per-connection index state.
+    pub connections: Vec<IndexedConnection>,
+    /// Total number of indexed objects.
+    pub objects: usize,
+}
@@ -0,0 +1,31 @@
+//! Secret management models.
+//!
+//! Contains models for rotating the master key that encrypts stored
+//! connection passwords.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Request body for rotating the master key.
+#[derive(Debug, Clone, Default, Deserialize, ToSchema)]
+pub struct RotateKeyRequest {
+    /// Drop the previous keys once every stored secret is re-encrypted.
+    #[serde(default)]
+    pub retire_previous: bool,
+}
+
+/// Result of a master key rotation.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct KeyRotationResult {
+    /// ID of the new active key.
+    pub active_key_id: String,
+    /// ID of the key that was active before the rotation.
+    pub previous_key_id: String,
+    /// Number of stored secrets re-encrypted with the new key: passwords,
+    /// secret driver options, TLS client keys and SSH credentials.
+    pub re_encrypted: usize,
+    /// IDs of all keys still available for decryption, active key first.
+    pub key_ids: Vec<String>,
+    /// ID of the audit trail entry recording the rotation.
+    pub audit_id: String,
+}
@@ -0,0 +1,134 @@
+//! Schema snapshot models.
+//!
+//! Contains models for versioned schema snapshots, snapshot schedules and
+//! the change timeline between consecutive snapshots.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+use super::connection::DbType;
+use super::metadata::TableDetail;
+use super::schema_diff::TableDiff;
+
+/// What caused a snapshot to be taken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SnapshotTrigger {
+    /// Requested through the API.
+    Manual,
+    /// Taken by the snapshot scheduler.
+    Scheduled,
+}
+
+/// Full schema snapshot as stored on disk.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SchemaSnapshot {
+    /// Connection the snapshot belongs to.
+    pub connection_id: String,
+    /// Version number, increasing per connection and starting at 1.
+    pub version: u32,
+    /// Snapshotted schema.
+    pub schema: String,
+    /// Dialect of the connection at snapshot time.
+    pub dialect: DbType,
+    /// Snapshot timestamp (RFC 3339).
+    pub taken_at: String,
+    /// What caused the snapshot.
+    pub trigger: SnapshotTrigger,
+    /// Structure of every table and view in the schema.
+    pub tables: Vec<TableDetail>,
+}
+
+/// Snapshot listing entry.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SnapshotSummary {
+    /// Version number.
+    pub version: u32,
+    /// Snapshotted schema.
+    pub schema: String,
+    /// Snapshot timestamp (RFC 3339).
+    pub taken_at: String,
+    /// What caused the snapshot.
+    pub trigger: SnapshotTrigger,
+    /// Number of tables and views.
+    pub table_count: usize,
+}
+
+impl From<&SchemaSnapshot> for SnapshotSummary {
+    fn from(snapshot: &SchemaSnapshot) -> Self {
+        Self {
+            version: snapshot.version,
+            schema: snapshot.schema.clone(),
+            taken_at: snapshot.taken_at.clone(),
+            trigger: snapshot.trigger,
+            table_count: snapshot.tables.len(),
+        }
+    }
+}
+
+/// Request body for taking a snapshot on demand.
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct TakeSnapshotRequest {
+    /// Schema to snapshot. Defaults to the connection's current schema.
+    pub schema: Option<String>,
+}
+
+/// Result of taking a snapshot.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SnapshotResult {
+    /// The stored snapshot, or the latest one when a scheduled run found no change.
+    pub snapshot: SnapshotSummary,
+    /// Whether a new version was stored.
+    pub created: bool,
+    /// Changes since the previous snapshot of the same schema.
+    pub changes: Vec<TableDiff>,
+}
+
+/// Request body for configuring periodic snapshots.
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct SnapshotScheduleRequest {
+    /// Interval between snapshots in seconds (at least 60).
+    #[validate(range(min = 60, message = "Interval must be at least 60 seconds"))]
+    pub interval_secs: u64,
+    /// Schema to snapshot. Defaults to the connection's current schema.
+    pub schema: Option<String>,
+    /// URL that receives a POST when drift is detected.
+    #[validate(url(message = "Webhook URL is invalid"))]
+    pub webhook_url: Option<String>,
+}
+
+/// Periodic snapshot configuration and its last run.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SnapshotSchedule {
+    /// Interval between snapshots in seconds.
+    pub interval_secs: u64,
+    /// Schema to snapshot. Defaults to the connection's current schema.
+    pub schema: Option<String>,
+    /// URL that receives a POST when drift is detected.
+    pub webhook_url: Option<String>,
+    /// Time of the last scheduled run (RFC 3339).
+    #[serde(default)]
+    pub last_run_at: Option<String>,
+    /// Error of the last scheduled run, if it failed.
+    #[serde(default)]
+    pub last_error: Option<String>,
+}
+
+/// Changes between two consecutive snapshots of the same schema.
+///
+/// `added` means present in the newer snapshot only; `source` values in the
+/// diffs describe the newer snapshot and `target` values the older one.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SnapshotChange {
+    /// Older snapshot version.
+    pub from_version: u32,
+    /// Newer snapshot version.
+    pub to_version: u32,
+    /// Compared schema.
+    pub schema: String,
+    /// Timestamp of the newer snapshot (RFC 3339).
+    pub detected_at: String,
+    /// Per-table changes.
+    pub tables: Vec<TableDiff>,
+}
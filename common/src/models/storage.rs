@@ -0,0 +1,79 @@
+//! Storage statistics models.
+//!
+//! Contains models for table and index sizes, row estimates, scan counts
+//! and maintenance times of a schema.
+
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+/// Query parameters for storage statistics.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct StorageQuery {
+    /// Schema to inspect. Defaults to the connection's current schema.
+    pub schema: Option<String>,
+    /// Sort field, prefixed with `-` for descending (default `-total_bytes`). One of
+    /// `table`, `row_estimate`, `table_bytes`, `index_bytes`, `total_bytes`,
+    /// `seq_scans`, `index_scans`, `last_vacuum`, `last_analyze`.
+    pub sort: Option<String>,
+    /// Only return tables that have unused indexes.
+    pub unused_indexes: Option<bool>,
+    /// Page number, starting at 1.
+    pub page: Option<u32>,
+    /// Tables per page (default 50, at most 1000).
+    pub page_size: Option<u32>,
+}
+
+/// Storage statistics of one index.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct IndexStorage {
+    /// Index name.
+    pub name: String,
+    /// Size on disk in bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bytes: Option<i64>,
+    /// Number of scans using the index since statistics were reset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scans: Option<i64>,
+    /// Whether the index enforces uniqueness.
+    pub unique: bool,
+    /// Whether the index backs the primary key.
+    pub primary: bool,
+    /// Never scanned and not enforcing a constraint, so a candidate for removal.
+    pub unused: bool,
+}
+
+/// Storage statistics of one table.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TableStorage {
+    /// Schema of the table.
+    pub schema: String,
+    /// Table name.
+    pub table: String,
+    /// Estimated number of rows.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub row_estimate: Option<i64>,
+    /// Size of the table data in bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub table_bytes: Option<i64>,
+    /// Size of all indexes in bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index_bytes: Option<i64>,
+    /// Table and index size in bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_bytes: Option<i64>,
+    /// Number of sequential (full) scans.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seq_scans: Option<i64>,
+    /// Number of index scans over all indexes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index_scans: Option<i64>,
+    /// Last manual or automatic vacuum (PostgreSQL).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_vacuum: Option<String>,
+    /// Last manual or automatic statistics update.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_analyze: Option<String>,
+    /// Per-index statistics.
+    pub indexes: Vec<IndexStorage>,
+}
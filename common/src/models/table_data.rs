@@ -0,0 +1,156 @@
+//! Table data browsing models.
+//!
+//! Contains models for reading table rows with structured filters, sorting,
+//! projection and pagination, and for editing rows by primary key, without
+//! writing SQL.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+/// Comparison operator of a row filter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterOperator {
+    /// `column = value`
+    Eq,
+    /// `column <> value`
+    Ne,
+    /// `column > value`
+    Gt,
+    /// `column >= value`
+    Gte,
+    /// `column < value`
+    Lt,
+    /// `column <= value`
+    Lte,
+    /// `column LIKE value` (value is a LIKE pattern)
+    Like,
+    /// `column NOT LIKE value` (value is a LIKE pattern)
+    NotLike,
+    /// Column contains the value as a substring.
+    Contains,
+    /// Column starts with the value.
+    StartsWith,
+    /// Column ends with the value.
+    EndsWith,
+    /// `column IN (values...)` (value is an array)
+    In,
+    /// `column NOT IN (values...)` (value is an array)
+    NotIn,
+    /// `column IS NULL` (value is ignored)
+    IsNull,
+    /// `column IS NOT NULL` (value is ignored)
+    IsNotNull,
+}
+
+/// One structured row filter. Filters are combined with AND.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RowFilter {
+    /// Column to filter on.
+    pub column: String,
+    /// Comparison operator.
+    pub op: FilterOperator,
+    /// Value to compare with; an array for `in` / `not_in`.
+    #[serde(default)]
+    pub value: serde_json::Value,
+}
+
+/// How the total row count is computed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CountMode {
+    /// Estimate for large unfiltered tables, exact otherwise.
+    #[default]
+    Auto,
+    /// Always run `COUNT(*)`.
+    Exact,
+    /// Use the catalog estimate when the read is unfiltered.
+    Estimated,
+}
+
+/// Query parameters for browsing table rows.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RowsQuery {
+    /// Schema of the table. Defaults to the connection's current schema.
+    pub schema: Option<String>,
+    /// Comma-separated list of columns to return. Defaults to all columns.
+    pub columns: Option<String>,
+    /// JSON array of filters, e.g. `[{"column":"status","op":"eq","value":"new"}]`.
+    pub filters: Option<String>,
+    /// Comma-separated sort columns; prefix with `-` for descending, e.g. `-created_at,id`.
+    /// Defaults to the primary key.
+    pub sort: Option<String>,
+    /// Page number, starting at 1.
+    pub page: Option<u32>,
+    /// Rows per page (default 50, at most 1000).
+    pub page_size: Option<u32>,
+    /// How the total row count is computed.
+    pub count: Option<CountMode>,
+}
+
+/// Kind of row change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RowChangeKind {
+    /// Insert a new row from `values`.
+    Insert,
+    /// Set `values` on the row identified by `key`.
+    Update,
+    /// Delete the row identified by `key`.
+    Delete,
+}
+
+/// One row change, addressed by primary key.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RowChange {
+    /// Kind of change.
+    pub kind: RowChangeKind,
+    /// Primary-key column values identifying the row (update and delete).
+    #[serde(default)]
+    #[schema(value_type = Object)]
+    pub key: Map<String, Value>,
+    /// Column values to insert or set (insert and update).
+    #[serde(default)]
+    #[schema(value_type = Object)]
+    pub values: Map<String, Value>,
+    /// Column values as last read by the client. The change is rejected with a
+    /// conflict if the row no longer has these values.
+    #[serde(default)]
+    #[schema(value_type = Object)]
+    pub original: Map<String, Value>,
+}
+
+/// Request body for applying row changes to a table in one transaction.
+#[derive(Debug, Clone, Deserialize, Validate, ToSchema)]
+pub struct RowChangesRequest {
+    /// Schema of the table. Defaults to the connection's current schema.
+    pub schema: Option<String>,
+    /// Changes to apply in order; either all are applied or none.
+    #[validate(length(min = 1, max = 1000, message = "changes must contain 1-1000 items"))]
+    pub changes: Vec<RowChange>,
+    /// Confirms the changes on a connection labelled `prod`, where they are
+    /// refused without it.
+    #[serde(default)]
+    pub confirm_prod: bool,
+}
+
+/// Outcome of one applied row change.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RowChangeResult {
+    /// Kind of change.
+    pub kind: RowChangeKind,
+    /// Number of rows affected.
+    pub affected: u64,
+}
+
+/// Outcome of a committed batch of row changes.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RowChangesResult {
+    /// Total number of rows affected.
+    pub affected: u64,
+    /// Per-change outcome, in request order.
+    pub changes: Vec<RowChangeResult>,
+}
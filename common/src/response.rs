@@ -207,6 +207,10 @@ pub struct Pagination {
 
     /// Whether there is a previous page.
     pub has_prev: bool,
+
+    /// Whether `total` is an estimate rather than an exact count.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub estimated: bool,
 }
 
 impl Pagination {
@@ -220,6 +224,7 @@ impl Pagination {
             total_pages,
             has_next: page < total_pages,
             has_prev: page > 1,
+            estimated: false,
         }
     }
 }
@@ -242,6 +247,12 @@ impl<T: Serialize> PaginatedData<T> {
             pagination: Pagination::new(page, page_size, total),
         }
     }
+
+    /// Marks the total as an estimate.
+    pub fn with_estimated_total(mut self) -> Self {
+        self.pagination.estimated = true;
+        self
+    }
 }
 
 impl<T: Serialize> ApiResponse<T> {
@@ -0,0 +1,94 @@
+//! File system helpers.
+//!
+//! Provides crash-safe writes for files persisted under the data directory.
+
+use std::path::Path;
+
+use tokio::io::AsyncWriteExt;
+
+/// Writes `contents` to `path` atomically.
+///
+/// The data is written to a temporary file in the same directory, flushed to
+/// disk and then renamed over the destination, so readers never observe a
+/// partially written file. Parent directories are created if missing.
+pub async fn write_atomic(path: impl AsRef<Path>, contents: &[u8]) -> std::io::Result<()> {
+    write_replacing(path.as_ref(), contents, false).await
+}
+
+/// Writes `contents` to `path` atomically, readable by the owner only.
+///
+/// Same as [`write_atomic`], but on Unix the file is created with mode `0600`
+/// before any data is written. Used for key material.
+pub async fn write_private(path: impl AsRef<Path>, contents: &[u8]) -> std::io::Result<()> {
+    write_replacing(path.as_ref(), contents, true).await
+}
+
+async fn write_replacing(path: &Path, contents: &[u8], private: bool) -> std::io::Result<()> {
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    tokio::fs::create_dir_all(dir).await?;
+
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let tmp = dir.join(format!(".{}.{}.tmp", file_name, uuid::Uuid::new_v4()));
+
+    let mut options = tokio::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    if private {
+        options.mode(0o600);
+    }
+    #[cfg(not(unix))]
+    let _ = private;
+    let mut file = options.open(&tmp).await?;
+    let written = async {
+        file.write_all(contents).await?;
+        file.sync_all().await
+    }
+    .await;
+    drop(file);
+    if let Err(e) = written {
+        let _ = tokio::fs::remove_file(&tmp).await;
+        return Err(e);
+    }
+    if let Err(e) = tokio::fs::rename(&tmp, path).await {
+        let _ = tokio::fs::remove_file(&tmp).await;
+        return Err(e);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_write_atomic_replaces_file() {
+        let dir = std::env::temp_dir().join(format!("fs-{}", uuid::Uuid::new_v4()));
+        let path = dir.join("nested").join("state.json");
+
+        write_atomic(&path, b"first").await.unwrap();
+        write_atomic(&path, b"second").await.unwrap();
+
+        assert_eq!(tokio::fs::read(&path).await.unwrap(), b"second");
+        let entries = std::fs::read_dir(path.parent().unwrap()).unwrap().count();
+        assert_eq!(entries, 1, "temporary files must not be left behind");
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_write_private_is_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("fs-{}", uuid::Uuid::new_v4()));
+        let path = dir.join("master.key");
+
+        write_private(&path, b"key").await.unwrap();
+
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
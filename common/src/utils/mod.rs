@@ -1,5 +1,7 @@
 //! Utility functions and helpers.
 
+pub mod dsn;
+pub mod fs;
 pub mod id_generator;
 pub mod sql_validator;
 
@@ -46,6 +46,19 @@ impl SqlValidator {
             || sql_upper.starts_with("UPDATE")
             || sql_upper.starts_with("DELETE")
     }
+
+    /// Checks if the SQL may change data or schema, i.e. it is not a read-only
+    /// SELECT/SHOW/EXPLAIN/DESCRIBE/VALUES statement. A WITH query counts as a
+    /// write when it contains INSERT, UPDATE, DELETE or MERGE.
+    pub fn is_write(sql: &str) -> bool {
+        let sql_upper = sql.trim_start().trim_start_matches('(').to_uppercase();
+        let mut words = sql_upper.split(|c: char| !c.is_ascii_alphanumeric() && c != '_').filter(|w| !w.is_empty());
+        match words.next() {
+            Some("SELECT" | "SHOW" | "EXPLAIN" | "DESCRIBE" | "DESC" | "VALUES") => false,
+            Some("WITH") => words.any(|w| matches!(w, "INSERT" | "UPDATE" | "DELETE" | "MERGE")),
+            _ => true,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -67,4 +80,14 @@ mod tests {
         assert!(SqlValidator::is_select("SELECT * FROM users"));
         assert!(!SqlValidator::is_select("INSERT INTO users"));
     }
+
+    #[test]
+    fn test_is_write() {
+        assert!(!SqlValidator::is_write("  select * from users"));
+        assert!(!SqlValidator::is_write("(SELECT 1) UNION (SELECT 2)"));
+        assert!(!SqlValidator::is_write("WITH t AS (SELECT 1) SELECT * FROM t"));
+        assert!(SqlValidator::is_write("WITH gone AS (DELETE FROM t RETURNING *) SELECT * FROM gone"));
+        assert!(SqlValidator::is_write("update users set name = 'x'"));
+        assert!(SqlValidator::is_write("CREATE INDEX idx ON users (name)"));
+    }
 }
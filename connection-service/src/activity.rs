@@ -0,0 +1,194 @@
+//! 服务器活动服务
+//!
+//! 列出数据库服务器上的会话及其正在执行的 SQL，并支持取消查询或终止会话：
+//! - PostgreSQL：`pg_stat_activity`，`pg_cancel_backend` / `pg_terminate_backend`
+//! - MySQL：`information_schema.PROCESSLIST`，`KILL QUERY` / `KILL`
+//!
+//! 终止会话属于管理操作，无论成功与否都会写入审计日志。
+
+use std::sync::Arc;
+
+use common::errors::{AppError, AppResult};
+use common::models::activity::{ActivityQuery, KillMode, KillSessionRequest, KillSessionResult, SessionActivity};
+use sqlx::{MySqlPool, PgPool, Row};
+
+use crate::audit::{AuditLog, AuditRecord};
+use crate::pool_manager::{DatabasePool, PoolManager};
+
+/// 服务器活动服务
+pub struct ActivityService {
+    pool_manager: Arc<PoolManager>,
+    audit: Arc<AuditLog>,
+}
+
+impl ActivityService {
+    /// 创建新的活动服务实例
+    pub fn new(pool_manager: Arc<PoolManager>, audit: Arc<AuditLog>) -> Self {
+        Self { pool_manager, audit }
+    }
+
+    /// 列出服务器会话，按持续时间从长到短排序
+    pub async fn sessions(&self, id: &str, query: &ActivityQuery) -> AppResult<Vec<SessionActivity>> {
+        let include_idle = query.include_idle.unwrap_or(false);
+        let mut sessions = match self.pool_manager.pool(id).await? {
+            DatabasePool::Postgres(pool) => postgres_sessions(&pool, include_idle).await?,
+            DatabasePool::MySQL(pool) => mysql_sessions(&pool, include_idle).await?,
+            _ => return Err(unsupported()),
+        };
+        sessions.sort_by(|a, b| b.duration_ms.cmp(&a.duration_ms).then(a.pid.cmp(&b.pid)));
+        Ok(sessions)
+    }
+
+    /// 取消会话的查询或终止会话，并写入审计日志
+    pub async fn kill(
+        &self,
+        id: &str,
+        pid: i64,
+        req: KillSessionRequest,
+        request_id: Option<String>,
+    ) -> AppResult<KillSessionResult> {
+        let pool = self.pool_manager.pool(id).await?;
+        let result = match &pool {
+            DatabasePool::Postgres(pool) => postgres_kill(pool, pid, req.mode).await,
+            DatabasePool::MySQL(pool) => mysql_kill(pool, pid, req.mode).await,
+            _ => Err(unsupported()),
+        };
+
+        let action = match req.mode {
+            KillMode::Cancel => "session.cancel",
+            KillMode::Terminate => "session.terminate",
+        };
+        let record = AuditRecord {
+            action,
+            connection_id: Some(id),
+            target: Some(pid.to_string()),
+            request_id,
+        };
+        let audit_id = self
+            .audit
+            .record(record, result.as_ref().map(|_| ()).map_err(|e| e.to_string()), req.reason)
+            .await?;
+        result?;
+        tracing::info!(connection_id = %id, pid, action, "已终止会话");
+        Ok(KillSessionResult { pid, mode: req.mode, audit_id })
+    }
+}
+
+fn unsupported() -> AppError {
+    AppError::UnsupportedDatabaseType("服务器活动仅支持 MySQL 和 PostgreSQL 连接".into())
+}
+
+/// 从 `pg_stat_activity` 读取客户端会话
+pub async fn postgres_sessions(pool: &PgPool, include_idle: bool) -> AppResult<Vec<SessionActivity>> {
+    let rows = sqlx::query(
+        "SELECT pid::int8 AS pid, usename::text AS usename, datname::text AS datname, \
+                host(client_addr) AS client, NULLIF(application_name, '')::text AS application, \
+                state::text AS state, NULLIF(query, '')::text AS query, \
+                to_char(since AT TIME ZONE 'UTC', 'YYYY-MM-DD\"T\"HH24:MI:SS.US\"Z\"') AS started_at, \
+                (GREATEST(EXTRACT(EPOCH FROM clock_timestamp() - since), 0) * 1000)::int8 AS duration_ms, \
+                CASE WHEN wait_event IS NOT NULL THEN wait_event_type || ':' || wait_event END AS wait_event, \
+                pid = pg_backend_pid() AS current \
+         FROM (SELECT *, CASE WHEN state = 'active' THEN query_start ELSE state_change END AS since \
+               FROM pg_stat_activity WHERE backend_type = 'client backend') a \
+         WHERE $1 OR state IS DISTINCT FROM 'idle'",
+    )
+    .bind(include_idle)
+    .fetch_all(pool)
+    .await?;
+
+    rows.iter()
+        .map(|row| {
+            Ok(SessionActivity {
+                pid: row.try_get("pid")?,
+                user: row.try_get("usename")?,
+                database: row.try_get("datname")?,
+                client: row.try_get("client")?,
+                application: row.try_get("application")?,
+                state: row.try_get("state")?,
+                query: row.try_get("query")?,
+                started_at: row.try_get("started_at")?,
+                duration_ms: row.try_get("duration_ms")?,
+                wait_event: row.try_get("wait_event")?,
+                current: row.try_get("current")?,
+            })
+        })
+        .collect()
+}
+
+/// 从 `information_schema.PROCESSLIST` 读取会话
+pub async fn mysql_sessions(pool: &MySqlPool, include_idle: bool) -> AppResult<Vec<SessionActivity>> {
+    let rows = sqlx::query(
+        "SELECT CAST(ID AS SIGNED) AS id, CAST(USER AS CHAR) AS user, CAST(HOST AS CHAR) AS host, \
+                CAST(DB AS CHAR) AS db, CAST(COMMAND AS CHAR) AS command, CAST(TIME AS SIGNED) AS time, \
+                CAST(NULLIF(STATE, '') AS CHAR) AS state, CAST(INFO AS CHAR) AS info, \
+                DATE_FORMAT(UTC_TIMESTAMP() - INTERVAL TIME SECOND, '%Y-%m-%dT%H:%i:%sZ') AS started_at, \
+                CAST(ID = CONNECTION_ID() AS SIGNED) AS current \
+         FROM information_schema.PROCESSLIST \
+         WHERE ? OR COMMAND <> 'Sleep'",
+    )
+    .bind(include_idle)
+    .fetch_all(pool)
+    .await?;
+
+    rows.iter()
+        .map(|row| {
+            let command: Option<String> = row.try_get("command")?;
+            let state = command.map(|c| match c.as_str() {
+                "Sleep" => "idle".to_string(),
+                "Query" => "active".to_string(),
+                other => other.to_lowercase(),
+            });
+            Ok(SessionActivity {
+                pid: row.try_get("id")?,
+                user: row.try_get("user")?,
+                database: row.try_get("db")?,
+                client: row.try_get("host")?,
+                application: None,
+                state,
+                query: row.try_get("info")?,
+                started_at: row.try_get("started_at")?,
+                duration_ms: row.try_get::<Option<i64>, _>("time")?.map(|t| t * 1000),
+                wait_event: row.try_get("state")?,
+                current: row.try_get::<Option<i64>, _>("current")?.unwrap_or(0) != 0,
+            })
+        })
+        .collect()
+}
+
+async fn postgres_kill(pool: &PgPool, pid: i64, mode: KillMode) -> AppResult<()> {
+    let pid = i32::try_from(pid).map_err(|_| AppError::NotFound(format!("session {}", pid)))?;
+    let exists: bool = sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM pg_stat_activity WHERE pid = $1)")
+        .bind(pid)
+        .fetch_one(pool)
+        .await?;
+    if !exists {
+        return Err(AppError::NotFound(format!("session {}", pid)));
+    }
+    let sql = match mode {
+        KillMode::Cancel => "SELECT pg_cancel_backend($1)",
+        KillMode::Terminate => "SELECT pg_terminate_backend($1)",
+    };
+    let signalled: bool = sqlx::query_scalar(sql).bind(pid).fetch_one(pool).await?;
+    if signalled {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden(format!("服务器拒绝终止会话 {}，请检查数据库用户权限", pid)))
+    }
+}
+
+async fn mysql_kill(pool: &MySqlPool, pid: i64, mode: KillMode) -> AppResult<()> {
+    let exists: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM information_schema.PROCESSLIST WHERE ID = ?")
+        .bind(pid)
+        .fetch_one(pool)
+        .await?;
+    if exists == 0 {
+        return Err(AppError::NotFound(format!("session {}", pid)));
+    }
+    // KILL 不支持占位符；pid 为整数，直接拼接是安全的
+    let sql = match mode {
+        KillMode::Cancel => format!("KILL QUERY {}", pid),
+        KillMode::Terminate => format!("KILL {}", pid),
+    };
+    sqlx::raw_sql(&sql).execute(pool).await?;
+    Ok(())
+}
@@ -0,0 +1,131 @@
+//! 审计日志
+//!
+//! 以 JSON Lines 格式只追加地记录特权操作（如终止会话），保存在 `data_dir/audit/audit.jsonl`。
+
+use std::path::PathBuf;
+
+use chrono::Utc;
+use common::errors::AppResult;
+use common::models::audit::{AuditEntry, AuditOutcome, AuditQuery};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+use tracing::warn;
+use validator::Validate;
+
+/// 默认返回的条目数
+const DEFAULT_LIMIT: u32 = 100;
+const AUDIT_FILE: &str = "audit.jsonl";
+
+/// 一次待记录的操作
+pub struct AuditRecord<'a> {
+    pub action: &'a str,
+    pub connection_id: Option<&'a str>,
+    pub target: Option<String>,
+    pub request_id: Option<String>,
+}
+
+/// 审计日志存储
+pub struct AuditLog {
+    path: PathBuf,
+    /// 串行化追加写入
+    write_lock: Mutex<()>,
+}
+
+impl AuditLog {
+    /// 打开 `data_dir/audit` 下的审计日志
+    pub fn open(data_dir: &str) -> Self {
+        Self {
+            path: PathBuf::from(data_dir).join("audit").join(AUDIT_FILE),
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    /// 追加一条记录，返回条目 ID
+    pub async fn record(&self, record: AuditRecord<'_>, result: Result<(), String>, reason: Option<String>) -> AppResult<String> {
+        let (outcome, detail) = match result {
+            Ok(()) => (AuditOutcome::Success, reason),
+            Err(error) => (AuditOutcome::Failure, Some(error)),
+        };
+        let entry = AuditEntry {
+            id: uuid::Uuid::new_v4().to_string(),
+            timestamp: Utc::now().to_rfc3339(),
+            action: record.action.to_string(),
+            connection_id: record.connection_id.map(str::to_string),
+            target: record.target,
+            request_id: record.request_id,
+            outcome,
+            detail,
+        };
+        let mut line = serde_json::to_vec(&entry)?;
+        line.push(b'\n');
+
+        let _guard = self.write_lock.lock().await;
+        if let Some(dir) = self.path.parent() {
+            tokio::fs::create_dir_all(dir).await?;
+        }
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+        file.write_all(&line).await?;
+        file.sync_all().await?;
+        Ok(entry.id)
+    }
+
+    /// 按条件读取最近的条目，新的在前
+    pub async fn recent(&self, query: &AuditQuery) -> AppResult<Vec<AuditEntry>> {
+        query.validate()?;
+        let content = match tokio::fs::read_to_string(&self.path).await {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let limit = query.limit.unwrap_or(DEFAULT_LIMIT) as usize;
+        Ok(content
+            .lines()
+            .rev()
+            .filter_map(|line| match serde_json::from_str::<AuditEntry>(line) {
+                Ok(entry) => Some(entry),
+                Err(e) => {
+                    warn!(error = %e, "跳过无法解析的审计日志行");
+                    None
+                }
+            })
+            .filter(|e| query.connection_id.is_none() || e.connection_id == query.connection_id)
+            .filter(|e| query.action.as_ref().is_none_or(|a| &e.action == a))
+            .take(limit)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_record_and_filter() {
+        let dir = std::env::temp_dir().join(format!("audit-{}", uuid::Uuid::new_v4()));
+        let log = AuditLog::open(dir.to_str().unwrap());
+        for (connection, result) in [("a", Ok(())), ("b", Err("denied".to_string())), ("a", Ok(()))] {
+            let record = AuditRecord {
+                action: "session.terminate",
+                connection_id: Some(connection),
+                target: Some("42".into()),
+                request_id: None,
+            };
+            log.record(record, result, Some("stuck".into())).await.unwrap();
+        }
+
+        let all = log.recent(&AuditQuery::default()).await.unwrap();
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[1].outcome, AuditOutcome::Failure);
+        assert_eq!(all[1].detail.as_deref(), Some("denied"));
+
+        let query = AuditQuery { connection_id: Some("a".into()), limit: Some(1), ..Default::default() };
+        let filtered = log.recent(&query).await.unwrap();
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].id, all[0].id);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
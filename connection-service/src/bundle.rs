@@ -0,0 +1,688 @@
+//! 连接包导入导出
+//!
+//! 导出把选中的连接写成带版本号的连接包，包含文件夹、标签、环境、只读和连接池等全部设置。
+//! 密钥字段（密码、TLS 客户端私钥、SSH 密码/私钥/口令、密码类驱动选项）要么省略并记录在
+//! `omitted_secrets` 中，要么用口令加密后放在 `secrets` 中：PBKDF2-HMAC-SHA256 从口令派生
+//! AES-256-GCM 密钥，每个字段单独加密，`连接 ID:字段名` 作为附加认证数据。
+//!
+//! 导入逐条校验，校验通过的连接只登记不建立连接，连接池在首次使用时打开；
+//! 已存在的 ID 按冲突策略跳过、覆盖或换新 ID 导入，返回逐条报告。
+//! 也可以导入 DBeaver 的 `data-sources.json`。
+
+use std::collections::HashSet;
+use std::num::NonZeroU32;
+use std::sync::Arc;
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chrono::Utc;
+use common::errors::{AppError, AppResult};
+use common::models::bundle::{
+    BundleEncryption, ConflictMode, ConnectionBundle, ExportBundleRequest, ImportBundleRequest, ImportEntryResult,
+    ImportFormat, ImportOutcome, ImportReport, BUNDLE_VERSION,
+};
+use common::models::connection::{ConnectionConfig, CreateConnectionRequest, DbType};
+use common::utils::dsn;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde_json::{json, Map, Value};
+use tracing::info;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::audit::{AuditLog, AuditRecord};
+use crate::pool_manager::PoolManager;
+
+const KDF: &str = "pbkdf2-sha256";
+const KDF_ITERATIONS: u32 = 600_000;
+/// 导入时接受的迭代次数范围，防止构造的连接包拖慢服务
+const MIN_KDF_ITERATIONS: u32 = 100_000;
+const MAX_KDF_ITERATIONS: u32 = 10_000_000;
+const SALT_LEN: usize = 16;
+const KEY_LEN: usize = 32;
+/// 用于校验口令的已知明文及其附加认证数据
+const CHECK_PLAINTEXT: &str = "connection-bundle";
+const CHECK_CONTEXT: &str = "check";
+
+/// 由口令派生的连接包密钥
+struct BundleKey {
+    key: LessSafeKey,
+    rng: SystemRandom,
+}
+
+impl BundleKey {
+    fn derive(passphrase: &str, salt: &[u8], iterations: NonZeroU32) -> Self {
+        let mut bytes = [0u8; KEY_LEN];
+        pbkdf2::derive(pbkdf2::PBKDF2_HMAC_SHA256, iterations, salt, passphrase.as_bytes(), &mut bytes);
+        // 密钥长度固定为 32 字节，构造不会失败
+        let key = LessSafeKey::new(UnboundKey::new(&AES_256_GCM, &bytes).expect("AES-256 密钥长度"));
+        Self { key, rng: SystemRandom::new() }
+    }
+
+    /// 加密为 `base64(nonce || 密文 || tag)`
+    fn seal(&self, plaintext: &str, context: &str) -> AppResult<String> {
+        let mut nonce = [0u8; NONCE_LEN];
+        self.rng.fill(&mut nonce).map_err(|_| AppError::Internal("生成随机数失败".into()))?;
+        let mut sealed = plaintext.as_bytes().to_vec();
+        self.key
+            .seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), Aad::from(context.as_bytes()), &mut sealed)
+            .map_err(|_| AppError::Internal("加密失败".into()))?;
+        let mut payload = nonce.to_vec();
+        payload.extend_from_slice(&sealed);
+        Ok(BASE64.encode(payload))
+    }
+
+    fn open(&self, sealed: &str, context: &str) -> Option<String> {
+        let mut payload = BASE64.decode(sealed).ok()?;
+        if payload.len() < NONCE_LEN {
+            return None;
+        }
+        let mut sealed = payload.split_off(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(&payload).ok()?;
+        let plaintext = self.key.open_in_place(nonce, Aad::from(context.as_bytes()), &mut sealed).ok()?;
+        String::from_utf8(plaintext.to_vec()).ok()
+    }
+}
+
+/// 连接包导入导出服务
+pub struct BundleService {
+    pool_manager: Arc<PoolManager>,
+    audit: Arc<AuditLog>,
+}
+
+impl BundleService {
+    /// 创建新的导入导出服务实例
+    pub fn new(pool_manager: Arc<PoolManager>, audit: Arc<AuditLog>) -> Self {
+        Self { pool_manager, audit }
+    }
+
+    /// 导出选中的连接；设置口令时加密包含密钥并记录审计日志，否则省略密钥
+    pub async fn export(&self, req: ExportBundleRequest, request_id: Option<String>) -> AppResult<ConnectionBundle> {
+        req.validate()?;
+        let mut configs = self.pool_manager.list_connections().await;
+        if !req.ids.is_empty() {
+            if let Some(missing) = req.ids.iter().find(|id| !configs.iter().any(|c| &c.id == *id)) {
+                return Err(AppError::ConnectionNotFound(missing.clone()));
+            }
+            configs.retain(|c| req.ids.contains(&c.id));
+        }
+        if let Some(folder) = &req.folder {
+            configs.retain(|c| c.in_folder(folder));
+        }
+        configs.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.id.cmp(&b.id)));
+
+        let (encryption, key) = match &req.passphrase {
+            Some(passphrase) => {
+                let mut salt = [0u8; SALT_LEN];
+                SystemRandom::new().fill(&mut salt).map_err(|_| AppError::Internal("生成随机数失败".into()))?;
+                let iterations = NonZeroU32::new(KDF_ITERATIONS).expect("迭代次数非零");
+                let key = BundleKey::derive(passphrase, &salt, iterations);
+                let encryption = BundleEncryption {
+                    kdf: KDF.to_string(),
+                    iterations: KDF_ITERATIONS,
+                    salt: BASE64.encode(salt),
+                    check: key.seal(CHECK_PLAINTEXT, CHECK_CONTEXT)?,
+                };
+                (Some(encryption), Some(key))
+            }
+            None => (None, None),
+        };
+
+        let mut connections = Vec::with_capacity(configs.len());
+        for mut config in configs {
+            let secrets = take_secrets(&mut config);
+            let mut entry = serde_json::to_value(&config).map_err(|e| AppError::Internal(e.to_string()))?;
+            let fields = entry.as_object_mut().expect("连接配置序列化为对象");
+            match &key {
+                Some(key) => {
+                    let mut sealed = Map::new();
+                    for (field, value) in secrets {
+                        let context = format!("{}:{}", config.id, field);
+                        sealed.insert(field, Value::String(key.seal(&value, &context)?));
+                    }
+                    if !sealed.is_empty() {
+                        fields.insert("secrets".into(), Value::Object(sealed));
+                    }
+                }
+                None if !secrets.is_empty() => {
+                    let omitted = secrets.into_iter().map(|(field, _)| Value::String(field)).collect();
+                    fields.insert("omitted_secrets".into(), Value::Array(omitted));
+                }
+                None => {}
+            }
+            connections.push(entry);
+        }
+        if key.is_some() {
+            let record = AuditRecord {
+                action: "connections.export",
+                connection_id: None,
+                target: Some(format!("{} 个连接（含密钥）", connections.len())),
+                request_id,
+            };
+            self.audit.record(record, Ok(()), None).await?;
+        }
+        info!(count = connections.len(), with_secrets = key.is_some(), "已导出连接");
+        Ok(ConnectionBundle { version: BUNDLE_VERSION, exported_at: Utc::now().to_rfc3339(), encryption, connections })
+    }
+
+    /// 导入连接包或 DBeaver 配置，返回逐条报告
+    ///
+    /// 数据格式错误、版本不支持或口令错误时整体失败；单条连接的问题只影响该条。
+    /// 导入的连接记录审计日志。
+    pub async fn import(&self, req: ImportBundleRequest, request_id: Option<String>) -> AppResult<ImportReport> {
+        let (entries, key) = match req.format {
+            ImportFormat::Bundle => {
+                let bundle: ConnectionBundle = serde_json::from_value(req.data)
+                    .map_err(|e| AppError::Validation(format!("data: 不是有效的连接包: {}", e)))?;
+                if bundle.version == 0 || bundle.version > BUNDLE_VERSION {
+                    return Err(AppError::Validation(format!(
+                        "data: 不支持的连接包版本 {}，当前支持的最高版本为 {}",
+                        bundle.version, BUNDLE_VERSION
+                    )));
+                }
+                let key = match (&bundle.encryption, &req.passphrase) {
+                    (Some(encryption), Some(passphrase)) => Some(unlock(encryption, passphrase)?),
+                    _ => None,
+                };
+                (bundle.connections.into_iter().map(Ok).collect(), key)
+            }
+            ImportFormat::Dbeaver => (dbeaver::entries(&req.data)?, None),
+        };
+
+        let existing = self.pool_manager.list_connections().await;
+        let mut taken_ids: HashSet<String> = existing.iter().map(|c| c.id.clone()).collect();
+        let mut taken_names: HashSet<String> = existing.iter().map(|c| c.name.clone()).collect();
+        let existing_ids = taken_ids.clone();
+        let now = Utc::now().to_rfc3339();
+
+        let mut results = Vec::with_capacity(entries.len());
+        let mut imported = Vec::new();
+        for (index, entry) in entries.into_iter().enumerate() {
+            let (source_id, name) = match &entry {
+                Ok(entry) | Err((entry, _)) => (
+                    entry.get("id").and_then(Value::as_str).map(str::to_string),
+                    entry.get("name").and_then(Value::as_str).map(str::to_string),
+                ),
+            };
+            let mut result = ImportEntryResult {
+                index,
+                source_id,
+                name,
+                id: None,
+                outcome: ImportOutcome::Failed,
+                missing_secrets: vec![],
+                message: None,
+            };
+            let parsed = entry
+                .map_err(|(_, message)| message)
+                .and_then(|entry| parse_entry(entry, key.as_ref(), &now, &mut result.missing_secrets));
+            let mut config = match parsed {
+                Ok(config) => config,
+                Err(message) => {
+                    result.message = Some(match result.missing_secrets.is_empty() {
+                        true => message,
+                        false => format!("{}（连接包未包含 {}）", message, result.missing_secrets.join(", ")),
+                    });
+                    results.push(result);
+                    continue;
+                }
+            };
+
+            if taken_ids.contains(&config.id) {
+                match req.on_conflict {
+                    ConflictMode::Skip => {
+                        result.outcome = ImportOutcome::Skipped;
+                        result.message = Some(format!("ID {} 已存在", config.id));
+                        results.push(result);
+                        continue;
+                    }
+                    ConflictMode::Overwrite if existing_ids.contains(&config.id) && !imported_id(&imported, &config.id) => {
+                        result.outcome = ImportOutcome::Overwritten;
+                        config.updated_at = Some(now.clone());
+                    }
+                    ConflictMode::Overwrite => {
+                        result.message = Some(format!("ID {} 在导入数据中重复", config.id));
+                        results.push(result);
+                        continue;
+                    }
+                    ConflictMode::Rename => {
+                        result.outcome = ImportOutcome::Renamed;
+                        config.id = Uuid::new_v4().to_string();
+                        config.name = unique_name(&config.name, &taken_names);
+                    }
+                }
+            } else {
+                result.outcome = ImportOutcome::Created;
+            }
+            taken_ids.insert(config.id.clone());
+            taken_names.insert(config.name.clone());
+            result.id = Some(config.id.clone());
+            result.name = Some(config.name.clone());
+            results.push(result);
+            imported.push(config);
+        }
+
+        let count = imported.len();
+        let overwritten = results.iter().filter(|r| r.outcome == ImportOutcome::Overwritten).count();
+        let record = AuditRecord {
+            action: "connections.import",
+            connection_id: None,
+            target: Some(format!("{} 个连接（覆盖 {} 个）", count, overwritten)),
+            request_id,
+        };
+        let outcome = self.pool_manager.import_connections(imported).await;
+        self.audit.record(record, outcome.as_ref().map(|_| ()).map_err(ToString::to_string), None).await?;
+        outcome?;
+        let skipped = results.iter().filter(|r| r.outcome == ImportOutcome::Skipped).count();
+        let failed = results.iter().filter(|r| r.outcome == ImportOutcome::Failed).count();
+        info!(imported = count, skipped, failed, "已导入连接");
+        Ok(ImportReport { imported: count, skipped, failed, results })
+    }
+}
+
+fn imported_id(imported: &[ConnectionConfig], id: &str) -> bool {
+    imported.iter().any(|c| c.id == id)
+}
+
+/// 用口令解开连接包密钥，口令错误时报错
+fn unlock(encryption: &BundleEncryption, passphrase: &str) -> AppResult<BundleKey> {
+    if encryption.kdf != KDF {
+        return Err(AppError::Validation(format!("data.encryption: 不支持的密钥派生算法 {}", encryption.kdf)));
+    }
+    if !(MIN_KDF_ITERATIONS..=MAX_KDF_ITERATIONS).contains(&encryption.iterations) {
+        return Err(AppError::Validation(format!(
+            "data.encryption: 迭代次数必须在 {} 到 {} 之间",
+            MIN_KDF_ITERATIONS, MAX_KDF_ITERATIONS
+        )));
+    }
+    let salt = BASE64
+        .decode(&encryption.salt)
+        .map_err(|_| AppError::Validation("data.encryption: salt 不是有效的 base64".into()))?;
+    let iterations = NonZeroU32::new(encryption.iterations).expect("迭代次数已校验");
+    let key = BundleKey::derive(passphrase, &salt, iterations);
+    if key.open(&encryption.check, CHECK_CONTEXT).as_deref() != Some(CHECK_PLAINTEXT) {
+        return Err(AppError::Validation("passphrase: 口令错误，无法解密连接包".into()));
+    }
+    Ok(key)
+}
+
+/// 解析并校验一条连接，未导入的密钥字段记入 `missing`
+fn parse_entry(
+    mut entry: Value,
+    key: Option<&BundleKey>,
+    now: &str,
+    missing: &mut Vec<String>,
+) -> Result<ConnectionConfig, String> {
+    let fields = entry.as_object_mut().ok_or("连接必须是 JSON 对象")?;
+    let id = match fields.get("id") {
+        Some(Value::String(id)) if !id.trim().is_empty() && id.len() <= 128 => id.clone(),
+        _ => return Err("id: 必须是 1-128 个字符的字符串".into()),
+    };
+    let created_at = fields.get("created_at").and_then(Value::as_str).unwrap_or(now).to_string();
+    let sealed = match fields.remove("secrets") {
+        Some(Value::Object(sealed)) => sealed,
+        Some(_) => return Err("secrets: 必须是对象".into()),
+        None => Map::new(),
+    };
+    if let Some(omitted) = fields.remove("omitted_secrets") {
+        *missing = serde_json::from_value(omitted).map_err(|_| "omitted_secrets: 必须是字符串数组")?;
+    }
+    for (field, value) in sealed {
+        let plaintext = key.and_then(|key| key.open(value.as_str()?, &format!("{}:{}", id, field)));
+        match plaintext {
+            Some(plaintext) => put_secret(fields, &field, plaintext)?,
+            None if key.is_some() => return Err(format!("secrets.{}: 无法解密，连接包已损坏", field)),
+            None => missing.push(field),
+        }
+    }
+
+    let req: CreateConnectionRequest =
+        serde_json::from_value(entry).map_err(|e| format!("连接设置无效: {}", e))?;
+    req.validate().map_err(|e| message(e.into()))?;
+    let config = req.into_config(id, created_at).map_err(message)?;
+    check_target(&config)?;
+    Ok(config)
+}
+
+/// 报告中的错误信息，校验错误去掉类别前缀
+fn message(error: AppError) -> String {
+    match error {
+        AppError::Validation(message) | AppError::InvalidInput(message) => message,
+        other => other.to_string(),
+    }
+}
+
+/// 校验连接目标是否完整；导入时不建立连接，这些问题否则要到首次使用时才暴露
+fn check_target(config: &ConnectionConfig) -> Result<(), String> {
+    match config.db_type {
+        DbType::SQLite if config.file_path.is_none() => Err("SQLite 连接需要 file_path".into()),
+        DbType::SQLite if config.ssh_tunnel.is_some() => Err("ssh_tunnel: SQLite 连接不能使用 SSH 隧道".into()),
+        DbType::SQLite => Ok(()),
+        ref other if config.host.is_none() => Err(format!("{} 连接需要 host", other)),
+        _ => Ok(()),
+    }
+}
+
+/// 取出配置中的全部密钥，返回字段名和明文；内联私钥之外的私钥路径不是密钥，予以保留
+fn take_secrets(config: &mut ConnectionConfig) -> Vec<(String, String)> {
+    let mut secrets = Vec::new();
+    if let Some(password) = config.password.take() {
+        secrets.push(("password".to_string(), password));
+    }
+    let keys: Vec<String> = config.options.keys().filter(|k| dsn::is_secret_option(k)).cloned().collect();
+    for key in keys {
+        if let Some(value) = config.options.remove(&key) {
+            secrets.push((format!("options.{}", key), value));
+        }
+    }
+    if let Some(client_key) = config.tls.as_mut().and_then(|tls| tls.client_key.take()) {
+        secrets.push(("tls.client_key".to_string(), client_key));
+    }
+    if let Some(ssh) = &mut config.ssh_tunnel {
+        let inline_key = ssh.has_inline_key();
+        for (field, value) in [
+            ("password", ssh.password.take()),
+            ("private_key", if inline_key { ssh.private_key.take() } else { None }),
+            ("passphrase", ssh.passphrase.take()),
+        ] {
+            if let Some(value) = value {
+                secrets.push((format!("ssh_tunnel.{}", field), value));
+            }
+        }
+    }
+    secrets
+}
+
+/// 把解密后的密钥放回连接设置；`a.b` 形式的字段写入嵌套对象
+fn put_secret(fields: &mut Map<String, Value>, field: &str, value: String) -> Result<(), String> {
+    let known = field == "password"
+        || field == "tls.client_key"
+        || matches!(field, "ssh_tunnel.password" | "ssh_tunnel.private_key" | "ssh_tunnel.passphrase")
+        || field.strip_prefix("options.").is_some_and(dsn::is_secret_option);
+    if !known {
+        return Err(format!("secrets.{}: 未知的密钥字段", field));
+    }
+    match field.split_once('.') {
+        None => {
+            fields.insert(field.to_string(), Value::String(value));
+        }
+        Some((section, name)) => {
+            let section = fields.entry(section).or_insert_with(|| json!({}));
+            let section = section.as_object_mut().ok_or_else(|| format!("{}: 必须是对象", field))?;
+            section.insert(name.to_string(), Value::String(value));
+        }
+    }
+    Ok(())
+}
+
+/// 名称已被使用时依次追加 ` (2)`、` (3)`……
+fn unique_name(name: &str, taken: &HashSet<String>) -> String {
+    if !taken.contains(name) {
+        return name.to_string();
+    }
+    (2..)
+        .map(|n| format!("{} ({})", name, n))
+        .find(|candidate| !taken.contains(candidate))
+        .expect("总能找到未使用的名称")
+}
+
+/// DBeaver `data-sources.json` 转换
+///
+/// 每个 DBeaver 连接转换为与连接包相同结构的条目，沿用同一套校验和冲突处理。
+/// DBeaver 把用户名和密码保存在加密的 `credentials-config.json` 中，这里只能导入
+/// 明文保存在 `configuration` 中的用户名。
+mod dbeaver {
+    use super::*;
+
+    /// 转换全部连接；无法转换的连接以 `Err((原始条目, 原因))` 返回
+    pub fn entries(data: &Value) -> AppResult<Vec<Result<Value, (Value, String)>>> {
+        let connections = data
+            .get("connections")
+            .and_then(Value::as_object)
+            .ok_or_else(|| AppError::Validation("data: 不是有效的 DBeaver data-sources.json，缺少 connections".into()))?;
+        Ok(connections
+            .iter()
+            .map(|(id, source)| {
+                convert(id, source).map_err(|message| {
+                    (json!({ "id": id, "name": source.get("name").cloned().unwrap_or(Value::Null) }), message)
+                })
+            })
+            .collect())
+    }
+
+    fn convert(id: &str, source: &Value) -> Result<Value, String> {
+        let provider = text(source, "provider").unwrap_or_default();
+        let driver = text(source, "driver").unwrap_or_default();
+        let db_type = db_type(&provider, &driver)
+            .ok_or_else(|| format!("不支持的 DBeaver 驱动 {}/{}", provider, driver))?;
+        let configuration = source.get("configuration").cloned().unwrap_or_else(|| json!({}));
+        let mut entry = json!({
+            "id": id,
+            "name": source.get("name").cloned().unwrap_or_else(|| json!(id)),
+            "db_type": db_type,
+            "read_only": source.get("read-only").and_then(Value::as_bool).unwrap_or(false),
+        });
+        let fields = entry.as_object_mut().expect("对象字面量");
+        if let Some(folder) = text(source, "folder") {
+            fields.insert("folder".into(), json!(folder));
+        }
+        // 内置连接类型 dev/test/prod 对应环境标签，自定义类型不导入
+        if let Some(kind) = text(&configuration, "type").filter(|k| matches!(k.as_str(), "dev" | "test" | "prod")) {
+            fields.insert("environment".into(), json!(kind));
+        }
+        if db_type == DbType::SQLite {
+            let path = text(&configuration, "database")
+                .or_else(|| text(&configuration, "url").and_then(|url| url.strip_prefix("jdbc:sqlite:").map(str::to_string)))
+                .ok_or("SQLite 连接缺少数据库文件路径")?;
+            fields.insert("file_path".into(), json!(path));
+        } else {
+            for (from, to) in [("host", "host"), ("database", "database"), ("user", "username"), ("password", "password")] {
+                if let Some(value) = text(&configuration, from) {
+                    fields.insert(to.into(), json!(value));
+                }
+            }
+            if let Some(port) = number(&configuration, "port") {
+                fields.insert("port".into(), json!(port));
+            }
+        }
+        if let Some(ssh) = ssh_tunnel(&configuration)? {
+            fields.insert("ssh_tunnel".into(), ssh);
+        }
+        Ok(entry)
+    }
+
+    fn ssh_tunnel(configuration: &Value) -> Result<Option<Value>, String> {
+        let Some(handler) = configuration.pointer("/handlers/ssh_tunnel") else { return Ok(None) };
+        if !handler.get("enabled").and_then(Value::as_bool).unwrap_or(false) {
+            return Ok(None);
+        }
+        let properties = handler.get("properties").cloned().unwrap_or_else(|| json!({}));
+        let host = text(&properties, "host").ok_or("ssh_tunnel: DBeaver SSH 隧道缺少 host")?;
+        let mut ssh = json!({
+            "host": host,
+            "port": number(&properties, "port").unwrap_or(22),
+            "user": text(handler, "user").or_else(|| text(&properties, "user")).unwrap_or_default(),
+        });
+        if let Some(key_path) = text(&properties, "keyPath") {
+            ssh["private_key"] = json!(key_path);
+        }
+        if let Some(password) = text(handler, "password") {
+            ssh["password"] = json!(password);
+        }
+        Ok(Some(ssh))
+    }
+
+    fn db_type(provider: &str, driver: &str) -> Option<DbType> {
+        let driver = driver.to_ascii_lowercase();
+        Some(match provider.to_ascii_lowercase().as_str() {
+            "mysql" if driver.contains("mariadb") => DbType::MariaDB,
+            "mysql" => DbType::MySQL,
+            "postgresql" => DbType::Postgres,
+            "sqlite" => DbType::SQLite,
+            "oracle" => DbType::Oracle,
+            "mssql" | "sqlserver" => DbType::SqlServer,
+            "clickhouse" => DbType::ClickHouse,
+            "db2" => DbType::DB2,
+            "generic" if driver.contains("sqlite") => DbType::SQLite,
+            "generic" if driver.contains("clickhouse") => DbType::ClickHouse,
+            "generic" if driver.contains("mariadb") => DbType::MariaDB,
+            _ => return None,
+        })
+    }
+
+    /// 非空字符串字段
+    fn text(value: &Value, key: &str) -> Option<String> {
+        value.get(key).and_then(Value::as_str).map(str::trim).filter(|s| !s.is_empty()).map(str::to_string)
+    }
+
+    /// 数字或数字字符串字段
+    fn number(value: &Value, key: &str) -> Option<u16> {
+        match value.get(key)? {
+            Value::Number(n) => n.as_u64().and_then(|n| u16::try_from(n).ok()),
+            Value::String(s) => s.trim().parse().ok(),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    fn service(pool_manager: Arc<PoolManager>) -> BundleService {
+        let dir = std::env::temp_dir().join(format!("bundle-audit-{}", Uuid::new_v4()));
+        BundleService::new(pool_manager, Arc::new(AuditLog::open(dir.to_str().unwrap())))
+    }
+
+    fn import_request(data: Value, on_conflict: ConflictMode, passphrase: Option<&str>) -> ImportBundleRequest {
+        serde_json::from_value(json!({
+            "data": data,
+            "on_conflict": on_conflict,
+            "passphrase": passphrase,
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_export_import_round_trip_with_conflicts() {
+        let path = std::env::temp_dir().join(format!("bundle-{}.db", Uuid::new_v4()));
+        let source = test_support::manager();
+        let req: CreateConnectionRequest = serde_json::from_value(json!({
+            "name": "orders",
+            "db_type": "sqlite",
+            "file_path": path.to_string_lossy(),
+            "password": "s3cret",
+            "options": { "sslpassword": "pem", "mode": "rwc" },
+            "folder": "shop/backend",
+            "tags": ["billing"],
+            "environment": "prod",
+            "read_only": true,
+        }))
+        .unwrap();
+        source.add_connection(req.into_config("c1".into(), "2026-01-01T00:00:00Z".into()).unwrap()).await.unwrap();
+        let exporter = service(source.clone());
+
+        // 不设口令时省略密钥
+        let plain = exporter.export(ExportBundleRequest::default(), None).await.unwrap();
+        let entry = &plain.connections[0];
+        assert!(entry.get("password").is_none() && entry.get("secrets").is_none());
+        assert_eq!(entry["omitted_secrets"], json!(["password", "options.sslpassword"]));
+        assert_eq!(entry["options"], json!({ "mode": "rwc" }));
+
+        let passphrase = Some("correct horse".to_string());
+        let bundle = exporter.export(ExportBundleRequest { passphrase, ..Default::default() }, None).await.unwrap();
+        let data = serde_json::to_value(&bundle).unwrap();
+        assert!(!data.to_string().contains("s3cret"));
+
+        let target = test_support::manager();
+        let importer = service(target.clone());
+        let wrong = importer.import(import_request(data.clone(), ConflictMode::Skip, Some("wrong passphrase")), None).await;
+        assert!(matches!(wrong, Err(AppError::Validation(ref m)) if m.contains("口令")));
+
+        let report = importer.import(import_request(data.clone(), ConflictMode::Skip, Some("correct horse")), None).await.unwrap();
+        assert_eq!((report.imported, report.results[0].outcome), (1, ImportOutcome::Created));
+        let imported = target.get_connection("c1").await.unwrap();
+        assert_eq!(imported.password.as_deref(), Some("s3cret"));
+        assert_eq!(imported.options.get("sslpassword").map(String::as_str), Some("pem"));
+        assert_eq!((imported.folder.as_deref(), imported.read_only), (Some("shop/backend"), true));
+        assert!(target.get_pool("c1").await.is_none(), "导入不建立连接");
+
+        // 冲突处理：跳过、换新 ID、覆盖
+        let skipped = importer.import(import_request(data.clone(), ConflictMode::Skip, None), None).await.unwrap();
+        assert_eq!((skipped.skipped, skipped.results[0].outcome), (1, ImportOutcome::Skipped));
+        let renamed = importer.import(import_request(data.clone(), ConflictMode::Rename, None), None).await.unwrap();
+        let result = &renamed.results[0];
+        assert_eq!((result.outcome, result.name.as_deref()), (ImportOutcome::Renamed, Some("orders (2)")));
+        assert_eq!(result.missing_secrets, ["password", "options.sslpassword"]);
+        assert!(target.get_connection(result.id.as_deref().unwrap()).await.unwrap().password.is_none());
+        let plain = serde_json::to_value(&plain).unwrap();
+        let overwritten = importer.import(import_request(plain, ConflictMode::Overwrite, None), None).await.unwrap();
+        assert_eq!(overwritten.results[0].outcome, ImportOutcome::Overwritten);
+        assert!(target.get_connection("c1").await.unwrap().password.is_none());
+
+        let mut future = data;
+        future["version"] = json!(BUNDLE_VERSION + 1);
+        assert!(importer.import(import_request(future, ConflictMode::Skip, None), None).await.is_err());
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn test_import_dbeaver_data_sources() {
+        let data = json!({
+            "folders": { "shop": {}, "shop/db": { "parent": "shop" } },
+            "connections": {
+                "postgres-jdbc-1": {
+                    "provider": "postgresql",
+                    "driver": "postgres-jdbc",
+                    "name": "orders",
+                    "folder": "shop/db",
+                    "read-only": true,
+                    "configuration": {
+                        "host": "db.internal",
+                        "port": "5432",
+                        "database": "orders",
+                        "user": "app",
+                        "type": "prod",
+                        "handlers": {
+                            "ssh_tunnel": {
+                                "enabled": true,
+                                "user": "deploy",
+                                "properties": { "host": "bastion", "port": 2222, "keyPath": "/home/me/.ssh/id_ed25519" }
+                            }
+                        }
+                    }
+                },
+                "sqlite-1": {
+                    "provider": "generic",
+                    "driver": "sqlite_jdbc",
+                    "name": "local",
+                    "configuration": { "url": "jdbc:sqlite:/tmp/local.db", "type": "dev" }
+                },
+                "mongo-1": { "provider": "mongodb", "driver": "mongo", "name": "events", "configuration": {} }
+            }
+        });
+        let target = test_support::manager();
+        let req = ImportBundleRequest {
+            format: ImportFormat::Dbeaver,
+            data,
+            on_conflict: ConflictMode::Skip,
+            passphrase: None,
+        };
+        let report = service(target.clone()).import(req, None).await.unwrap();
+        assert_eq!((report.imported, report.failed), (2, 1));
+        assert_eq!(report.results[2].name.as_deref(), Some("events"));
+        assert!(report.results[2].message.as_deref().unwrap().contains("mongodb"));
+
+        let orders = target.get_connection("postgres-jdbc-1").await.unwrap();
+        assert_eq!(orders.db_type, DbType::Postgres);
+        assert_eq!((orders.port, orders.username.as_deref()), (Some(5432), Some("app")));
+        assert_eq!(orders.folder.as_deref(), Some("shop/db"));
+        assert!(orders.requires_write_confirmation() && orders.read_only);
+        let ssh = orders.ssh_tunnel.unwrap();
+        assert_eq!((ssh.bastion(), ssh.private_key.as_deref()), ("deploy@bastion:2222".into(), Some("/home/me/.ssh/id_ed25519")));
+        let local = target.get_connection("sqlite-1").await.unwrap();
+        assert_eq!(local.file_path.as_deref(), Some("/tmp/local.db"));
+    }
+}
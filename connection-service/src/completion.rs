@@ -0,0 +1,619 @@
+//! SQL 自动补全服务
+//!
+//! 根据光标位置分析 SQL 上下文，从 schema 缓存中给出排序后的候选项：
+//! - `FROM` / `JOIN` / `INTO` / `UPDATE` 之后补全 schema、表和视图
+//! - `SELECT` / `WHERE` / `ON` 等之后补全语句中引用的表的列、表别名和函数
+//! - `别名.` / `表.` 之后补全该表的列，`schema.` 之后补全该 schema 的表
+//! - 其他位置补全当前方言的关键字
+//!
+//! 候选项按前缀、单词边界和模糊匹配程度排序。
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use common::errors::AppResult;
+use common::models::completion::{CompletionItem, CompletionKind, CompletionRequest, CompletionResult};
+use common::models::metadata::{TableDetail, TableKind};
+use validator::Validate;
+
+use crate::dialect::Dialect;
+use crate::pool_manager::PoolManager;
+use crate::schema_cache::{SchemaCache, SchemaModel};
+
+/// 默认候选项数量
+const DEFAULT_LIMIT: u32 = 50;
+
+/// 各方言通用的关键字
+const COMMON_KEYWORDS: &[&str] = &[
+    "SELECT", "FROM", "WHERE", "AND", "OR", "NOT", "IN", "IS", "NULL", "LIKE", "BETWEEN", "EXISTS",
+    "AS", "ON", "JOIN", "INNER", "LEFT", "RIGHT", "FULL", "OUTER", "CROSS", "USING", "GROUP", "BY",
+    "ORDER", "ASC", "DESC", "HAVING", "LIMIT", "OFFSET", "DISTINCT", "UNION", "ALL", "INSERT",
+    "INTO", "VALUES", "UPDATE", "SET", "DELETE", "CREATE", "ALTER", "DROP", "TABLE", "VIEW",
+    "INDEX", "PRIMARY", "KEY", "FOREIGN", "REFERENCES", "DEFAULT", "CASE", "WHEN", "THEN", "ELSE",
+    "END", "WITH", "TRUE", "FALSE", "BEGIN", "COMMIT", "ROLLBACK", "TRUNCATE", "EXPLAIN",
+];
+
+/// 方言特有的关键字
+const MYSQL_KEYWORDS: &[&str] = &["SHOW", "DESCRIBE", "REPLACE", "IGNORE", "DUPLICATE", "AUTO_INCREMENT", "REGEXP", "STRAIGHT_JOIN"];
+const POSTGRES_KEYWORDS: &[&str] = &["RETURNING", "ILIKE", "SIMILAR", "LATERAL", "RECURSIVE", "CONFLICT", "DO", "NOTHING", "ANALYZE", "VACUUM"];
+const SQLITE_KEYWORDS: &[&str] = &["PRAGMA", "GLOB", "REPLACE", "RETURNING", "CONFLICT", "VACUUM", "ATTACH", "AUTOINCREMENT"];
+
+/// 各方言通用的函数
+const COMMON_FUNCTIONS: &[&str] = &[
+    "COUNT", "SUM", "AVG", "MIN", "MAX", "COALESCE", "NULLIF", "CAST", "LOWER", "UPPER", "LENGTH",
+    "SUBSTR", "TRIM", "REPLACE", "ROUND", "ABS",
+];
+
+/// 方言特有的函数
+const MYSQL_FUNCTIONS: &[&str] = &["NOW", "CONCAT", "DATE_FORMAT", "IFNULL", "GROUP_CONCAT", "JSON_EXTRACT", "DATEDIFF", "CHAR_LENGTH"];
+const POSTGRES_FUNCTIONS: &[&str] = &["NOW", "CONCAT", "STRING_AGG", "ARRAY_AGG", "DATE_TRUNC", "TO_CHAR", "JSONB_BUILD_OBJECT", "GENERATE_SERIES"];
+const SQLITE_FUNCTIONS: &[&str] = &["DATETIME", "STRFTIME", "IFNULL", "GROUP_CONCAT", "JSON_EXTRACT", "PRINTF", "INSTR"];
+
+/// 后面跟表名的关键字
+const TABLE_KEYWORDS: &[&str] = &["FROM", "JOIN", "INTO", "UPDATE", "TABLE", "DESCRIBE", "TRUNCATE"];
+
+/// 后面跟列或表达式的关键字
+const COLUMN_KEYWORDS: &[&str] = &[
+    "SELECT", "WHERE", "ON", "AND", "OR", "NOT", "BY", "SET", "HAVING", "WHEN", "THEN", "ELSE",
+    "RETURNING", "DISTINCT", "USING", "IN", "LIKE", "BETWEEN", "IS",
+];
+
+/// SQL 补全服务
+pub struct CompletionService {
+    pool_manager: Arc<PoolManager>,
+    cache: Arc<SchemaCache>,
+}
+
+impl CompletionService {
+    /// 创建新的补全服务实例
+    pub fn new(pool_manager: Arc<PoolManager>, cache: Arc<SchemaCache>) -> Self {
+        Self { pool_manager, cache }
+    }
+
+    /// 计算光标处的补全候选项
+    pub async fn complete(&self, req: CompletionRequest) -> AppResult<CompletionResult> {
+        req.validate()?;
+        let id = &req.connection_id;
+        let dialect = self.pool_manager.pool(id).await?.dialect()?;
+        let default = self.cache.get(&self.pool_manager, id, req.schema.as_deref()).await?;
+
+        let analysis = analyze(&req.sql, req.cursor);
+        // 语句中显式引用的其他 schema，以及 `schema.` 限定符
+        let mut others: Vec<Arc<SchemaModel>> = Vec::new();
+        let mut wanted: HashSet<String> = analysis.tables.iter().filter_map(|t| t.schema.clone()).collect();
+        if let Some(first) = analysis.qualifier.first() {
+            if default.has_schema(first) {
+                wanted.insert(first.clone());
+            }
+        }
+        for schema in wanted {
+            if !schema.eq_ignore_ascii_case(&default.schema) && default.has_schema(&schema) {
+                let name = default.schemas.iter().find(|s| s.eq_ignore_ascii_case(&schema)).unwrap_or(&schema);
+                others.push(self.cache.get(&self.pool_manager, id, Some(name)).await?);
+            }
+        }
+
+        let models = Models { default: &default, others: &others };
+        let limit = req.limit.unwrap_or(DEFAULT_LIMIT) as usize;
+        Ok(CompletionResult {
+            items: suggest(dialect, &analysis, &models, limit),
+            prefix: analysis.prefix,
+            schema_loaded_at: default.loaded_at.clone(),
+        })
+    }
+}
+
+// ============== 词法分析 ==============
+
+/// 词法单元
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// 未加引号的标识符或关键字
+    Word(String),
+    /// 加引号的标识符
+    Quoted(String),
+    /// 标点和运算符
+    Punct(char),
+    /// 字符串、数字和注释
+    Other,
+}
+
+/// 带位置（字节偏移）的词法单元
+#[derive(Debug, Clone)]
+struct Lexeme {
+    token: Token,
+    start: usize,
+    end: usize,
+    /// 字符串、注释或引号标识符未闭合
+    open: bool,
+}
+
+fn lex(sql: &str) -> Vec<Lexeme> {
+    let chars: Vec<(usize, char)> = sql.char_indices().collect();
+    let byte_at = |i: usize| chars.get(i).map(|c| c.0).unwrap_or(sql.len());
+    let mut lexemes = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let (start, c) = chars[i];
+        let next = chars.get(i + 1).map(|c| c.1);
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        let (token, end, open) = if c == '-' && next == Some('-') {
+            let end = (i..chars.len()).find(|&j| chars[j].1 == '\n').unwrap_or(chars.len());
+            (Token::Other, end, end == chars.len())
+        } else if c == '/' && next == Some('*') {
+            let close = (i + 2..chars.len()).find(|&j| chars[j].1 == '*' && chars.get(j + 1).map(|c| c.1) == Some('/'));
+            match close {
+                Some(j) => (Token::Other, j + 2, false),
+                None => (Token::Other, chars.len(), true),
+            }
+        } else if matches!(c, '\'' | '"' | '`' | '[') {
+            let close_char = if c == '[' { ']' } else { c };
+            let close = (i + 1..chars.len()).find(|&j| chars[j].1 == close_char);
+            let (end, open) = match close {
+                Some(j) => (j + 1, false),
+                None => (chars.len(), true),
+            };
+            let content_end = if open { chars.len() } else { end - 1 };
+            let content: String = chars[i + 1..content_end].iter().map(|c| c.1).collect();
+            let token = if c == '\'' { Token::Other } else { Token::Quoted(content) };
+            (token, end, open)
+        } else if c.is_alphanumeric() || c == '_' || c == '$' {
+            let end = (i..chars.len())
+                .find(|&j| !(chars[j].1.is_alphanumeric() || matches!(chars[j].1, '_' | '$')))
+                .unwrap_or(chars.len());
+            let word: String = chars[i..end].iter().map(|c| c.1).collect();
+            let token = if c.is_ascii_digit() { Token::Other } else { Token::Word(word) };
+            (token, end, false)
+        } else {
+            (Token::Punct(c), i + 1, false)
+        };
+        lexemes.push(Lexeme { token, start, end: byte_at(end), open });
+        i = end;
+    }
+    lexemes
+}
+
+fn is_keyword(word: &str) -> bool {
+    let upper = word.to_ascii_uppercase();
+    [COMMON_KEYWORDS, MYSQL_KEYWORDS, POSTGRES_KEYWORDS, SQLITE_KEYWORDS]
+        .iter()
+        .any(|list| list.contains(&upper.as_str()))
+}
+
+fn keyword_in(token: &Token, list: &[&str]) -> bool {
+    matches!(token, Token::Word(w) if list.contains(&w.to_ascii_uppercase().as_str()))
+}
+
+fn identifier(token: &Token) -> Option<&str> {
+    match token {
+        Token::Word(w) if !is_keyword(w) => Some(w),
+        Token::Quoted(q) => Some(q),
+        _ => None,
+    }
+}
+
+// ============== 上下文分析 ==============
+
+/// 光标处期望的内容
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Context {
+    /// 光标在字符串或注释中，不补全
+    None,
+    /// 关键字
+    Keyword,
+    /// 表名
+    Table,
+    /// 列或表达式
+    Column,
+    /// `限定符.` 之后
+    Qualified,
+}
+
+/// 语句中引用的表
+#[derive(Debug, Clone, PartialEq)]
+struct TableRef {
+    schema: Option<String>,
+    table: String,
+    alias: Option<String>,
+}
+
+/// 光标处的分析结果
+#[derive(Debug)]
+struct Analysis {
+    prefix: String,
+    qualifier: Vec<String>,
+    context: Context,
+    tables: Vec<TableRef>,
+}
+
+/// 分析光标（字符偏移）处的上下文
+fn analyze(sql: &str, cursor: usize) -> Analysis {
+    let cursor = sql.char_indices().nth(cursor).map(|(i, _)| i).unwrap_or(sql.len());
+    let lexemes = lex(sql);
+
+    // 光标所在语句
+    let statement_start = lexemes
+        .iter()
+        .rposition(|l| l.token == Token::Punct(';') && l.end <= cursor)
+        .map(|i| i + 1)
+        .unwrap_or(0);
+    let statement_end = lexemes[statement_start..]
+        .iter()
+        .position(|l| l.token == Token::Punct(';') && l.start >= cursor)
+        .map(|i| statement_start + i)
+        .unwrap_or(lexemes.len());
+    let statement = &lexemes[statement_start..statement_end];
+    let tables = table_refs(statement);
+    let mut analysis = Analysis {
+        prefix: String::new(),
+        qualifier: Vec::new(),
+        context: Context::None,
+        tables,
+    };
+
+    // 光标所在（或紧邻其前）的单词作为前缀
+    let current = statement.iter().position(|l| l.start < cursor && cursor <= l.end);
+    let mut before_end = statement.iter().take_while(|l| l.end <= cursor).count();
+    if let Some(i) = current {
+        let lexeme = &statement[i];
+        match &lexeme.token {
+            Token::Word(_) => {
+                analysis.prefix = sql[lexeme.start..cursor].to_string();
+                before_end = i;
+            }
+            Token::Quoted(_) if lexeme.open || cursor < lexeme.end => {
+                analysis.prefix = sql[lexeme.start..cursor].chars().skip(1).collect();
+                before_end = i;
+            }
+            Token::Other if lexeme.open || cursor < lexeme.end => return analysis,
+            _ => {}
+        }
+    }
+    let before = &statement[..before_end];
+
+    // `a.b.` 形式的限定符
+    let mut rest = before;
+    while let [head @ .., name, dot] = rest {
+        if dot.token != Token::Punct('.') {
+            break;
+        }
+        match &name.token {
+            Token::Word(w) | Token::Quoted(w) => analysis.qualifier.insert(0, w.clone()),
+            _ => break,
+        }
+        rest = head;
+    }
+    if !analysis.qualifier.is_empty() {
+        analysis.context = Context::Qualified;
+        return analysis;
+    }
+
+    let clause = before.iter().rev().find_map(|l| match &l.token {
+        Token::Word(w) if keyword_in(&l.token, TABLE_KEYWORDS) || keyword_in(&l.token, COLUMN_KEYWORDS) => {
+            Some(w.to_ascii_uppercase())
+        }
+        _ => None,
+    });
+    analysis.context = match before.last().map(|l| &l.token) {
+        None => Context::Keyword,
+        Some(token) if keyword_in(token, TABLE_KEYWORDS) => Context::Table,
+        Some(Token::Punct(',')) if clause.as_deref() == Some("FROM") => Context::Table,
+        Some(token) if keyword_in(token, COLUMN_KEYWORDS) => Context::Column,
+        // 表达式之后期望关键字，运算符、逗号和括号之后期望列
+        Some(Token::Word(_) | Token::Quoted(_) | Token::Other | Token::Punct(')')) => Context::Keyword,
+        Some(Token::Punct(_)) => match clause {
+            Some(_) => Context::Column,
+            None => Context::Keyword,
+        },
+    };
+    analysis
+}
+
+/// 收集 FROM / JOIN / UPDATE / INTO 之后引用的表及别名
+fn table_refs(statement: &[Lexeme]) -> Vec<TableRef> {
+    let mut refs = Vec::new();
+    let mut i = 0;
+    while i < statement.len() {
+        if !keyword_in(&statement[i].token, &["FROM", "JOIN", "UPDATE", "INTO"]) {
+            i += 1;
+            continue;
+        }
+        let is_from = keyword_in(&statement[i].token, &["FROM"]);
+        i += 1;
+        while let Some((table_ref, next)) = table_ref(statement, i) {
+            refs.push(table_ref);
+            i = next;
+            // FROM a, b
+            if is_from && statement.get(i).map(|l| &l.token) == Some(&Token::Punct(',')) {
+                i += 1;
+            } else {
+                break;
+            }
+        }
+    }
+    refs
+}
+
+/// 解析 `[schema.]table [[AS] alias]`，返回引用和下一个位置
+fn table_ref(statement: &[Lexeme], start: usize) -> Option<(TableRef, usize)> {
+    let token = |i: usize| statement.get(i).map(|l| &l.token);
+    let mut i = start;
+    let first = identifier(token(i)?)?.to_string();
+    i += 1;
+    let (schema, table) = if token(i) == Some(&Token::Punct('.')) {
+        match token(i + 1).and_then(identifier) {
+            Some(table) => {
+                i += 2;
+                (Some(first), table.to_string())
+            }
+            None => (None, first),
+        }
+    } else {
+        (None, first)
+    };
+    if token(i).is_some_and(|t| keyword_in(t, &["AS"])) {
+        i += 1;
+    }
+    let alias = token(i).and_then(identifier).map(str::to_string);
+    if alias.is_some() {
+        i += 1;
+    }
+    Some((TableRef { schema, table, alias }, i))
+}
+
+// ============== 候选项 ==============
+
+/// 补全时可用的 schema 模型
+struct Models<'a> {
+    default: &'a SchemaModel,
+    others: &'a [Arc<SchemaModel>],
+}
+
+impl Models<'_> {
+    fn schema(&self, name: Option<&str>) -> Option<&SchemaModel> {
+        match name {
+            None => Some(self.default),
+            Some(name) if name.eq_ignore_ascii_case(&self.default.schema) => Some(self.default),
+            Some(name) => self
+                .others
+                .iter()
+                .find(|m| m.schema.eq_ignore_ascii_case(name))
+                .map(|m| m.as_ref()),
+        }
+    }
+
+    fn table(&self, schema: Option<&str>, name: &str) -> Option<&TableDetail> {
+        self.schema(schema)?.table(name)
+    }
+}
+
+/// 生成、过滤并排序候选项
+fn suggest(dialect: Dialect, analysis: &Analysis, models: &Models, limit: usize) -> Vec<CompletionItem> {
+    // (标签, 类型, 说明, 上下文权重)
+    let mut candidates: Vec<(String, CompletionKind, Option<String>, u32)> = Vec::new();
+    let columns_of = |table: &TableDetail, weight: u32, out: &mut Vec<_>| {
+        for column in &table.columns {
+            out.push((
+                column.name.clone(),
+                CompletionKind::Column,
+                Some(format!("{}.{} {}", table.table.name, column.name, column.data_type)),
+                weight,
+            ));
+        }
+    };
+
+    match analysis.context {
+        Context::None => return Vec::new(),
+        Context::Qualified => match analysis.qualifier.as_slice() {
+            [name] => {
+                let scoped = analysis.tables.iter().find(|t| {
+                    t.alias.as_deref().is_some_and(|a| a.eq_ignore_ascii_case(name))
+                        || (t.alias.is_none() && t.table.eq_ignore_ascii_case(name))
+                });
+                if let Some(table) = scoped.and_then(|t| models.table(t.schema.as_deref(), &t.table)) {
+                    columns_of(table, 30, &mut candidates);
+                } else if let Some(schema) = models.schema(Some(name)).filter(|_| models.default.has_schema(name)) {
+                    push_tables(schema, 30, &mut candidates);
+                } else if let Some(table) = models.table(None, name) {
+                    columns_of(table, 30, &mut candidates);
+                }
+            }
+            [.., schema, table] => {
+                if let Some(table) = models.table(Some(schema), table) {
+                    columns_of(table, 30, &mut candidates);
+                }
+            }
+            [] => {}
+        },
+        Context::Table => {
+            push_tables(models.default, 30, &mut candidates);
+            for schema in &models.default.schemas {
+                candidates.push((schema.clone(), CompletionKind::Schema, None, 20));
+            }
+        }
+        Context::Column => {
+            for table_ref in &analysis.tables {
+                if let Some(table) = models.table(table_ref.schema.as_deref(), &table_ref.table) {
+                    columns_of(table, 30, &mut candidates);
+                }
+                match &table_ref.alias {
+                    Some(alias) => candidates.push((alias.clone(), CompletionKind::Alias, Some(table_ref.table.clone()), 25)),
+                    None => candidates.push((table_ref.table.clone(), CompletionKind::Table, None, 25)),
+                }
+            }
+            push_words(functions(dialect), CompletionKind::Function, 15, &mut candidates);
+            push_words(keywords(dialect), CompletionKind::Keyword, 10, &mut candidates);
+        }
+        Context::Keyword => push_words(keywords(dialect), CompletionKind::Keyword, 30, &mut candidates),
+    }
+
+    let mut seen = HashSet::new();
+    let mut items: Vec<CompletionItem> = candidates
+        .into_iter()
+        .filter_map(|(label, kind, detail, weight)| {
+            let quality = match_quality(&analysis.prefix, &label)?;
+            seen.insert((label.clone(), kind, detail.clone())).then(|| CompletionItem {
+                label,
+                kind,
+                detail,
+                score: quality + weight,
+            })
+        })
+        .collect();
+    items.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then(a.label.len().cmp(&b.label.len()))
+            .then(a.label.cmp(&b.label))
+    });
+    items.truncate(limit);
+    items
+}
+
+fn push_tables(model: &SchemaModel, weight: u32, out: &mut Vec<(String, CompletionKind, Option<String>, u32)>) {
+    for table in &model.tables {
+        let kind = match table.table.kind {
+            TableKind::View | TableKind::MaterializedView => CompletionKind::View,
+            _ => CompletionKind::Table,
+        };
+        out.push((table.table.name.clone(), kind, Some(model.schema.clone()), weight));
+    }
+}
+
+fn push_words(words: Vec<&str>, kind: CompletionKind, weight: u32, out: &mut Vec<(String, CompletionKind, Option<String>, u32)>) {
+    out.extend(words.into_iter().map(|w| (w.to_string(), kind, None, weight)));
+}
+
+fn keywords(dialect: Dialect) -> Vec<&'static str> {
+    let extra = match dialect {
+        Dialect::MySql => MYSQL_KEYWORDS,
+        Dialect::Postgres => POSTGRES_KEYWORDS,
+        Dialect::Sqlite => SQLITE_KEYWORDS,
+    };
+    COMMON_KEYWORDS.iter().chain(extra).copied().collect()
+}
+
+fn functions(dialect: Dialect) -> Vec<&'static str> {
+    let extra = match dialect {
+        Dialect::MySql => MYSQL_FUNCTIONS,
+        Dialect::Postgres => POSTGRES_FUNCTIONS,
+        Dialect::Sqlite => SQLITE_FUNCTIONS,
+    };
+    COMMON_FUNCTIONS.iter().chain(extra).copied().collect()
+}
+
+/// 匹配程度：完全相同 > 前缀 > 单词边界 > 包含 > 模糊（按顺序出现），不匹配返回 None
+pub fn match_quality(prefix: &str, label: &str) -> Option<u32> {
+    if prefix.is_empty() {
+        return Some(50);
+    }
+    let prefix = prefix.to_lowercase();
+    let label = label.to_lowercase();
+    if label == prefix {
+        Some(100)
+    } else if label.starts_with(&prefix) {
+        Some(80)
+    } else if label.split(['_', '.']).any(|part| part.starts_with(&prefix)) {
+        Some(60)
+    } else if label.contains(&prefix) {
+        Some(45)
+    } else {
+        let mut chars = label.chars();
+        prefix.chars().all(|p| chars.any(|c| c == p)).then_some(30)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::models::metadata::{ColumnItem, TableItem};
+
+    fn table(name: &str, columns: &[&str]) -> TableDetail {
+        TableDetail {
+            table: TableItem {
+                schema: "public".into(),
+                name: name.into(),
+                kind: TableKind::Table,
+                row_estimate: None,
+                comment: None,
+            },
+            columns: columns
+                .iter()
+                .enumerate()
+                .map(|(i, c)| ColumnItem {
+                    name: c.to_string(),
+                    ordinal: i as u32 + 1,
+                    data_type: "text".into(),
+                    nullable: true,
+                    default_value: None,
+                    is_primary_key: false,
+                    auto_increment: false,
+                    comment: None,
+                })
+                .collect(),
+            primary_key: None,
+            indexes: vec![],
+            foreign_keys: vec![],
+        }
+    }
+
+    fn model() -> SchemaModel {
+        SchemaModel {
+            schema: "public".into(),
+            schemas: vec!["public".into(), "audit".into()],
+            tables: vec![table("users", &["id", "email", "user_name"]), table("orders", &["id", "user_id", "status"])],
+            loaded_at: String::new(),
+        }
+    }
+
+    fn complete(sql: &str) -> Vec<(String, CompletionKind)> {
+        let cursor = sql.find('|').unwrap();
+        let sql = sql.replace('|', "");
+        let model = model();
+        let models = Models { default: &model, others: &[] };
+        suggest(Dialect::Postgres, &analyze(&sql, cursor), &models, 50)
+            .into_iter()
+            .map(|i| (i.label, i.kind))
+            .collect()
+    }
+
+    #[test]
+    fn test_context_and_aliases() {
+        let tables = complete("SELECT * FROM o|");
+        assert_eq!(tables[0], ("orders".into(), CompletionKind::Table));
+
+        let aliased = complete("SELECT o.| FROM orders AS o JOIN users u ON u.id = o.user_id");
+        let labels: Vec<_> = aliased.iter().map(|(l, _)| l.as_str()).collect();
+        assert_eq!(labels, vec!["id", "status", "user_id"]);
+
+        let columns = complete("SELECT * FROM users u WHERE em|");
+        assert_eq!(columns[0], ("email".into(), CompletionKind::Column));
+
+        // 单词边界和模糊匹配
+        let fuzzy = complete("SELECT name| FROM users");
+        assert_eq!(fuzzy[0], ("user_name".into(), CompletionKind::Column));
+
+        let keywords = complete("SELECT id FROM users WHER|");
+        assert_eq!(keywords[0], ("WHERE".into(), CompletionKind::Keyword));
+
+        assert!(complete("SELECT 'us|' FROM users").is_empty());
+        assert!(complete("SELECT 1; -- FROM us|").is_empty());
+    }
+
+    #[test]
+    fn test_table_refs() {
+        let sql = "UPDATE audit.log SET x = 1; SELECT * FROM users u, public.orders WHERE ";
+        let analysis = analyze(sql, sql.len());
+        assert_eq!(
+            analysis.tables,
+            vec![
+                TableRef { schema: None, table: "users".into(), alias: Some("u".into()) },
+                TableRef { schema: Some("public".into()), table: "orders".into(), alias: None },
+            ]
+        );
+        assert_eq!(analysis.context, Context::Column);
+    }
+}
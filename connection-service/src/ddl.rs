@@ -0,0 +1,563 @@
+//! DDL 生成服务
+//!
+//! 导出表、视图及其索引的 CREATE 语句：
+//! - MySQL：`SHOW CREATE TABLE` / `SHOW CREATE VIEW`
+//! - SQLite：`sqlite_master.sql`
+//! - PostgreSQL：根据系统目录重建
+//!
+//! 指定目标方言时，根据元数据模型按目标方言重新生成 DDL，并给出类型映射报告。
+
+use std::sync::Arc;
+
+use common::errors::{AppError, AppResult};
+use common::models::connection::DbType;
+use common::models::ddl::{TableDdl, TypeMapping};
+use common::models::metadata::{ColumnItem, ForeignKeyItem, IndexItem, TableDetail, TableKind};
+use sqlx::{MySqlPool, PgPool, Row, SqlitePool};
+
+use crate::dialect::Dialect;
+use crate::metadata::MetadataService;
+use crate::pool_manager::{DatabasePool, PoolManager};
+
+/// DDL 生成服务
+pub struct DdlService {
+    pool_manager: Arc<PoolManager>,
+}
+
+impl DdlService {
+    /// 创建新的 DDL 服务实例
+    pub fn new(pool_manager: Arc<PoolManager>) -> Self {
+        Self { pool_manager }
+    }
+
+    /// 生成表或视图的 DDL；`target` 不为空时转换为目标方言
+    pub async fn table_ddl(
+        &self,
+        id: &str,
+        schema: Option<&str>,
+        table: &str,
+        target: Option<&DbType>,
+    ) -> AppResult<TableDdl> {
+        let pool = self.pool_manager.pool(id).await?;
+        let source = pool.dialect()?;
+        let detail = MetadataService::new(self.pool_manager.clone())
+            .table(id, schema, table)
+            .await?;
+
+        let target = target.map(Dialect::from_db_type).transpose()?;
+        if let Some(target) = target.filter(|t| *t != source) {
+            if detail.table.kind != TableKind::Table {
+                return Err(AppError::InvalidInput(format!(
+                    "{} 是视图，视图定义无法自动转换方言",
+                    table
+                )));
+            }
+            let rendered = DdlWriter::new(source, target).create_table(&detail);
+            return Ok(TableDdl {
+                schema: detail.table.schema,
+                table: detail.table.name,
+                kind: detail.table.kind,
+                dialect: target.db_type(),
+                ddl: join_statements(&rendered.statements),
+                type_mappings: rendered.type_mappings,
+                warnings: rendered.warnings,
+            });
+        }
+
+        let statements = match &pool {
+            DatabasePool::MySQL(pool) => mysql_ddl(pool, &detail).await?,
+            DatabasePool::Postgres(pool) => postgres_ddl(pool, &detail).await?,
+            DatabasePool::SQLite(pool) => sqlite_ddl(pool, &detail).await?,
+            _ => unreachable!("dialect() 已拒绝不支持的连接类型"),
+        };
+        Ok(TableDdl {
+            schema: detail.table.schema,
+            table: detail.table.name,
+            kind: detail.table.kind,
+            dialect: source.db_type(),
+            ddl: join_statements(&statements),
+            type_mappings: vec![],
+            warnings: vec![],
+        })
+    }
+}
+
+fn join_statements(statements: &[String]) -> String {
+    statements
+        .iter()
+        .map(|s| format!("{};", s.trim_end().trim_end_matches(';')))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+// ============== 原生 DDL ==============
+
+async fn mysql_ddl(pool: &MySqlPool, detail: &TableDetail) -> AppResult<Vec<String>> {
+    let keyword = match detail.table.kind {
+        TableKind::Table => "TABLE",
+        _ => "VIEW",
+    };
+    let sql = format!(
+        "SHOW CREATE {} {}",
+        keyword,
+        Dialect::MySql.qualified_table(Some(&detail.table.schema), &detail.table.name)
+    );
+    let row = sqlx::query(&sql).fetch_one(pool).await?;
+    Ok(vec![row.try_get(1)?])
+}
+
+async fn sqlite_ddl(pool: &SqlitePool, detail: &TableDetail) -> AppResult<Vec<String>> {
+    let sql = format!(
+        "SELECT sql FROM {}.sqlite_master \
+         WHERE tbl_name = ?1 AND sql IS NOT NULL \
+         ORDER BY CASE type WHEN 'table' THEN 0 WHEN 'view' THEN 0 WHEN 'index' THEN 1 ELSE 2 END, name",
+        Dialect::Sqlite.quote_ident(&detail.table.schema)
+    );
+    let rows = sqlx::query(&sql)
+        .bind(&detail.table.name)
+        .fetch_all(pool)
+        .await?;
+    rows.iter().map(|row| Ok(row.try_get("sql")?)).collect()
+}
+
+/// 根据系统目录重建 PostgreSQL DDL
+async fn postgres_ddl(pool: &PgPool, detail: &TableDetail) -> AppResult<Vec<String>> {
+    let dialect = Dialect::Postgres;
+    let name = dialect.qualified_table(Some(&detail.table.schema), &detail.table.name);
+
+    if detail.table.kind != TableKind::Table {
+        let definition: String = sqlx::query_scalar("SELECT pg_get_viewdef($1::regclass, true)")
+            .bind(&name)
+            .fetch_one(pool)
+            .await?;
+        let keyword = match detail.table.kind {
+            TableKind::MaterializedView => "MATERIALIZED VIEW",
+            _ => "VIEW",
+        };
+        let mut statements = vec![format!("CREATE {} {} AS\n{}", keyword, name, definition.trim_end())];
+        statements.extend(postgres_indexes(pool, &name).await?);
+        statements.extend(postgres_comments(detail, &name));
+        return Ok(statements);
+    }
+
+    let columns = sqlx::query(
+        "SELECT a.attname::text AS column_name, format_type(a.atttypid, a.atttypmod) AS data_type, \
+                a.attnotnull AS not_null, pg_get_expr(d.adbin, d.adrelid) AS column_default, \
+                a.attidentity::text AS identity, a.attgenerated::text AS generated \
+         FROM pg_attribute a \
+         LEFT JOIN pg_attrdef d ON d.adrelid = a.attrelid AND d.adnum = a.attnum \
+         WHERE a.attrelid = $1::regclass AND a.attnum > 0 AND NOT a.attisdropped \
+         ORDER BY a.attnum",
+    )
+    .bind(&name)
+    .fetch_all(pool)
+    .await?;
+
+    let mut sequences = Vec::new();
+    let mut lines = Vec::new();
+    for row in &columns {
+        let column: String = row.try_get("column_name")?;
+        let data_type: String = row.try_get("data_type")?;
+        let default: Option<String> = row.try_get("column_default")?;
+        let identity: String = row.try_get("identity")?;
+        let generated: String = row.try_get("generated")?;
+
+        let mut line = format!("    {} {}", dialect.quote_ident(&column), data_type);
+        match (identity.as_str(), generated.as_str(), &default) {
+            ("a", _, _) => line.push_str(" GENERATED ALWAYS AS IDENTITY"),
+            ("d", _, _) => line.push_str(" GENERATED BY DEFAULT AS IDENTITY"),
+            (_, "s", Some(expr)) => line.push_str(&format!(" GENERATED ALWAYS AS ({}) STORED", expr)),
+            (_, _, Some(expr)) => {
+                if let Some(sequence) = sequence_name(expr) {
+                    sequences.push(format!("CREATE SEQUENCE IF NOT EXISTS {}", sequence));
+                }
+                line.push_str(&format!(" DEFAULT {}", expr));
+            }
+            _ => {}
+        }
+        if row.try_get::<bool, _>("not_null")? {
+            line.push_str(" NOT NULL");
+        }
+        lines.push(line);
+    }
+
+    let constraints = sqlx::query(
+        "SELECT conname::text AS constraint_name, pg_get_constraintdef(oid, true) AS definition \
+         FROM pg_constraint \
+         WHERE conrelid = $1::regclass AND contype IN ('p', 'u', 'c', 'f', 'x') \
+         ORDER BY CASE contype WHEN 'p' THEN 0 WHEN 'u' THEN 1 WHEN 'c' THEN 2 WHEN 'f' THEN 3 ELSE 4 END, conname",
+    )
+    .bind(&name)
+    .fetch_all(pool)
+    .await?;
+    for row in &constraints {
+        let constraint: String = row.try_get("constraint_name")?;
+        let definition: String = row.try_get("definition")?;
+        lines.push(format!("    CONSTRAINT {} {}", dialect.quote_ident(&constraint), definition));
+    }
+
+    let mut statements = sequences;
+    statements.push(format!("CREATE TABLE {} (\n{}\n)", name, lines.join(",\n")));
+    statements.extend(postgres_indexes(pool, &name).await?);
+    statements.extend(postgres_comments(detail, &name));
+    Ok(statements)
+}
+
+/// 不属于约束的索引定义
+async fn postgres_indexes(pool: &PgPool, name: &str) -> AppResult<Vec<String>> {
+    Ok(sqlx::query_scalar(
+        "SELECT pg_get_indexdef(i.indexrelid) \
+         FROM pg_index i JOIN pg_class c ON c.oid = i.indexrelid \
+         WHERE i.indrelid = $1::regclass \
+           AND NOT EXISTS (SELECT 1 FROM pg_constraint con \
+                           WHERE con.conindid = i.indexrelid AND con.conrelid = i.indrelid) \
+         ORDER BY c.relname",
+    )
+    .bind(name)
+    .fetch_all(pool)
+    .await?)
+}
+
+fn postgres_comments(detail: &TableDetail, name: &str) -> Vec<String> {
+    let dialect = Dialect::Postgres;
+    let keyword = match detail.table.kind {
+        TableKind::Table => "TABLE",
+        TableKind::View => "VIEW",
+        TableKind::MaterializedView => "MATERIALIZED VIEW",
+    };
+    let mut statements = Vec::new();
+    if let Some(comment) = &detail.table.comment {
+        statements.push(format!("COMMENT ON {} {} IS {}", keyword, name, dialect.quote_literal(comment)));
+    }
+    for column in &detail.columns {
+        if let Some(comment) = &column.comment {
+            statements.push(format!(
+                "COMMENT ON COLUMN {}.{} IS {}",
+                name,
+                dialect.quote_ident(&column.name),
+                dialect.quote_literal(comment)
+            ));
+        }
+    }
+    statements
+}
+
+/// 从 `nextval('seq'::regclass)` 中提取序列名
+fn sequence_name(default: &str) -> Option<&str> {
+    let rest = default.strip_prefix("nextval('")?;
+    let end = rest.find('\'')?;
+    Some(&rest[..end])
+}
+
+// ============== 方言转换 ==============
+
+/// 按目标方言生成的 DDL
+#[derive(Debug, Default)]
+pub struct RenderedDdl {
+    pub statements: Vec<String>,
+    pub type_mappings: Vec<TypeMapping>,
+    pub warnings: Vec<String>,
+}
+
+/// 根据元数据模型按目标方言生成 DDL
+pub struct DdlWriter {
+    source: Dialect,
+    target: Dialect,
+}
+
+impl DdlWriter {
+    /// 创建从 `source` 方言到 `target` 方言的 DDL 生成器
+    pub fn new(source: Dialect, target: Dialect) -> Self {
+        Self { source, target }
+    }
+
+    /// 生成 CREATE TABLE 及其索引、注释（不带 schema 前缀，便于迁移到其他环境）
+    pub fn create_table(&self, detail: &TableDetail) -> RenderedDdl {
+        let mut out = RenderedDdl::default();
+        let table = &detail.table.name;
+        let quoted = self.target.quote_ident(table);
+
+        // SQLite 的自增只能用于内联的 INTEGER PRIMARY KEY
+        let inline_pk = match (&detail.primary_key, self.target) {
+            (Some(pk), Dialect::Sqlite) if pk.columns.len() == 1 => detail
+                .column(&pk.columns[0])
+                .filter(|c| c.auto_increment)
+                .map(|c| c.name.clone()),
+            _ => None,
+        };
+
+        let mut lines: Vec<String> = detail
+            .columns
+            .iter()
+            .map(|c| format!("    {}", self.column_definition(table, c, inline_pk.as_deref() == Some(&c.name), &mut out)))
+            .collect();
+        if let (Some(pk), None) = (&detail.primary_key, &inline_pk) {
+            lines.push(format!("    PRIMARY KEY ({})", self.column_list(&pk.columns)));
+        }
+        for fk in &detail.foreign_keys {
+            lines.push(format!("    {}", self.foreign_key(fk)));
+        }
+
+        let mut create = format!("CREATE TABLE {} (\n{}\n)", quoted, lines.join(",\n"));
+        if let (Dialect::MySql, Some(comment)) = (self.target, &detail.table.comment) {
+            create.push_str(&format!(" COMMENT={}", self.target.quote_literal(comment)));
+        }
+        out.statements.push(create);
+
+        for index in detail.indexes.iter().filter(|i| !i.primary) {
+            // 唯一约束在 MySQL/PostgreSQL 中同样以唯一索引体现
+            if let Some(statement) = self.create_index(table, index, detail, &mut out) {
+                out.statements.push(statement);
+            }
+        }
+
+        match self.target {
+            Dialect::Postgres => {
+                if let Some(comment) = &detail.table.comment {
+                    out.statements.push(format!(
+                        "COMMENT ON TABLE {} IS {}",
+                        quoted,
+                        self.target.quote_literal(comment)
+                    ));
+                }
+                for column in &detail.columns {
+                    if let Some(comment) = &column.comment {
+                        out.statements.push(format!(
+                            "COMMENT ON COLUMN {}.{} IS {}",
+                            quoted,
+                            self.target.quote_ident(&column.name),
+                            self.target.quote_literal(comment)
+                        ));
+                    }
+                }
+            }
+            Dialect::Sqlite => {
+                if detail.table.comment.is_some() || detail.columns.iter().any(|c| c.comment.is_some()) {
+                    out.warnings.push(format!("{}: SQLite 不支持表和列注释，注释已省略", table));
+                }
+            }
+            Dialect::MySql => {}
+        }
+        out
+    }
+
+    /// 生成列定义（不含前导缩进），并记录类型映射
+    pub fn column_definition(
+        &self,
+        table: &str,
+        column: &ColumnItem,
+        inline_pk: bool,
+        out: &mut RenderedDdl,
+    ) -> String {
+        let mapping = self.target.translate_type(self.source, &column.name, &column.data_type);
+        let mut definition = format!("{} {}", self.target.quote_ident(&column.name), mapping.target_type);
+
+        let auto_increment = column.auto_increment;
+        if inline_pk {
+            definition.push_str(" PRIMARY KEY AUTOINCREMENT");
+        } else if auto_increment && self.target == Dialect::Postgres {
+            if matches!(mapping.target_type.as_str(), "smallint" | "integer" | "bigint") {
+                definition.push_str(" GENERATED BY DEFAULT AS IDENTITY");
+            } else {
+                out.warnings.push(format!(
+                    "{}.{}: {} 不能作为自增列，自增属性已省略",
+                    table, column.name, mapping.target_type
+                ));
+            }
+        }
+        if !column.nullable && !inline_pk {
+            definition.push_str(" NOT NULL");
+        }
+        if let Some(default) = &column.default_value {
+            if !(auto_increment && default.to_lowercase().starts_with("nextval(")) {
+                match self.target.translate_default(self.source, default) {
+                    Some(expr) => definition.push_str(&format!(" DEFAULT {}", expr)),
+                    None => out.warnings.push(format!(
+                        "{}.{}: 默认值 {} 无法转换，已省略",
+                        table, column.name, default
+                    )),
+                }
+            }
+        }
+        if auto_increment && self.target == Dialect::MySql {
+            definition.push_str(" AUTO_INCREMENT");
+        }
+        if auto_increment && self.target == Dialect::Sqlite && !inline_pk {
+            out.warnings.push(format!(
+                "{}.{}: SQLite 只支持单列 INTEGER PRIMARY KEY 自增，自增属性已省略",
+                table, column.name
+            ));
+        }
+        if let (Dialect::MySql, Some(comment)) = (self.target, &column.comment) {
+            definition.push_str(&format!(" COMMENT {}", self.target.quote_literal(comment)));
+        }
+
+        if mapping.lossy {
+            out.warnings.push(format!(
+                "{}.{}: {} -> {} 为有损转换",
+                table, column.name, mapping.source_type, mapping.target_type
+            ));
+        }
+        out.type_mappings.push(mapping);
+        definition
+    }
+
+    /// 生成 CREATE INDEX 语句；包含无法转换的表达式时返回 `None`
+    pub fn create_index(
+        &self,
+        table: &str,
+        index: &IndexItem,
+        detail: &TableDetail,
+        out: &mut RenderedDdl,
+    ) -> Option<String> {
+        let mut columns = Vec::with_capacity(index.columns.len());
+        for column in &index.columns {
+            match detail.column(column) {
+                Some(c) => {
+                    if self.target == Dialect::MySql {
+                        let target_type = self.target.translate_type(self.source, &c.name, &c.data_type).target_type;
+                        if target_type == "longtext" || target_type == "longblob" {
+                            out.warnings.push(format!(
+                                "{}.{}: MySQL 中对 {} 列建立索引需要指定前缀长度",
+                                table, index.name, target_type
+                            ));
+                        }
+                    }
+                    columns.push(self.target.quote_ident(column));
+                }
+                None => {
+                    out.warnings.push(format!(
+                        "{}.{}: 表达式索引 {} 无法转换，已省略",
+                        table, index.name, column
+                    ));
+                    return None;
+                }
+            }
+        }
+        Some(format!(
+            "CREATE {}INDEX {} ON {} ({})",
+            if index.unique { "UNIQUE " } else { "" },
+            self.target.quote_ident(&index.name),
+            self.target.quote_ident(table),
+            columns.join(", ")
+        ))
+    }
+
+    /// 生成外键约束子句；被引用列未知时省略列清单，即引用对方主键
+    pub fn foreign_key(&self, fk: &ForeignKeyItem) -> String {
+        let mut clause = format!(
+            "CONSTRAINT {} FOREIGN KEY ({}) REFERENCES {}",
+            self.target.quote_ident(&fk.name),
+            self.column_list(&fk.columns),
+            self.target.quote_ident(&fk.referenced_table),
+        );
+        if !fk.referenced_columns.is_empty() {
+            clause.push_str(&format!(" ({})", self.column_list(&fk.referenced_columns)));
+        }
+        for (keyword, rule) in [("ON UPDATE", &fk.on_update), ("ON DELETE", &fk.on_delete)] {
+            if let Some(rule) = rule.as_deref().filter(|r| *r != "NO ACTION" && *r != "RESTRICT") {
+                clause.push_str(&format!(" {} {}", keyword, rule));
+            }
+        }
+        clause
+    }
+
+    fn column_list(&self, columns: &[String]) -> String {
+        columns
+            .iter()
+            .map(|c| self.target.quote_ident(c))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::models::metadata::{PrimaryKeyItem, TableItem};
+
+    fn column(name: &str, data_type: &str, nullable: bool) -> ColumnItem {
+        ColumnItem {
+            name: name.to_string(),
+            ordinal: 0,
+            data_type: data_type.to_string(),
+            nullable,
+            default_value: None,
+            is_primary_key: false,
+            auto_increment: false,
+            comment: None,
+        }
+    }
+
+    fn orders() -> TableDetail {
+        let mut id = column("id", "integer", false);
+        id.is_primary_key = true;
+        id.auto_increment = true;
+        id.default_value = Some("nextval('orders_id_seq'::regclass)".to_string());
+        let mut status = column("status", "character varying(20)", true);
+        status.default_value = Some("'new'::character varying".to_string());
+        TableDetail {
+            table: TableItem {
+                schema: "public".to_string(),
+                name: "orders".to_string(),
+                kind: TableKind::Table,
+                row_estimate: None,
+                comment: Some("customer orders".to_string()),
+            },
+            columns: vec![id, column("user_id", "integer", false), status, column("placed_at", "timestamp with time zone", true)],
+            primary_key: Some(PrimaryKeyItem {
+                name: Some("orders_pkey".to_string()),
+                columns: vec!["id".to_string()],
+            }),
+            indexes: vec![IndexItem {
+                name: "idx_orders_status".to_string(),
+                columns: vec!["status".to_string()],
+                unique: false,
+                primary: false,
+                index_type: Some("btree".to_string()),
+            }],
+            foreign_keys: vec![ForeignKeyItem {
+                name: "orders_user_id_fkey".to_string(),
+                columns: vec!["user_id".to_string()],
+                referenced_schema: Some("public".to_string()),
+                referenced_table: "users".to_string(),
+                referenced_columns: vec!["id".to_string()],
+                on_update: Some("NO ACTION".to_string()),
+                on_delete: Some("CASCADE".to_string()),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_postgres_to_mysql() {
+        let rendered = DdlWriter::new(Dialect::Postgres, Dialect::MySql).create_table(&orders());
+        assert_eq!(
+            rendered.statements[0],
+            "CREATE TABLE `orders` (\n\
+             \x20   `id` int NOT NULL AUTO_INCREMENT,\n\
+             \x20   `user_id` int NOT NULL,\n\
+             \x20   `status` varchar(20) DEFAULT 'new',\n\
+             \x20   `placed_at` datetime(6),\n\
+             \x20   PRIMARY KEY (`id`),\n\
+             \x20   CONSTRAINT `orders_user_id_fkey` FOREIGN KEY (`user_id`) REFERENCES `users` (`id`) ON DELETE CASCADE\n\
+             ) COMMENT='customer orders'"
+        );
+        assert_eq!(rendered.statements[1], "CREATE INDEX `idx_orders_status` ON `orders` (`status`)");
+        let lossy: Vec<_> = rendered.type_mappings.iter().filter(|m| m.lossy).map(|m| m.column.as_str()).collect();
+        assert_eq!(lossy, vec!["placed_at"]);
+    }
+
+    #[test]
+    fn test_postgres_to_sqlite_inlines_autoincrement_key() {
+        let rendered = DdlWriter::new(Dialect::Postgres, Dialect::Sqlite).create_table(&orders());
+        assert!(rendered.statements[0].contains("\"id\" INTEGER PRIMARY KEY AUTOINCREMENT,"));
+        assert!(!rendered.statements[0].contains("    PRIMARY KEY"));
+        assert!(rendered.warnings.iter().any(|w| w.contains("注释")));
+    }
+
+    #[test]
+    fn test_sequence_name() {
+        assert_eq!(sequence_name("nextval('users_id_seq'::regclass)"), Some("users_id_seq"));
+        assert_eq!(sequence_name("now()"), None);
+    }
+}
@@ -0,0 +1,381 @@
+//! 连接诊断服务
+//!
+//! 在保存连接之前试连：按顺序执行 DNS 解析、TCP 连接、建立 SSH 隧道、登录（含 TLS 协商）、
+//! 读取服务器版本和探测延迟，逐步报告耗时和结果。某一步失败后，后续步骤标记为跳过。
+//! 配置了 SSH 隧道时，DNS 和 TCP 检查针对跳板机，数据库地址由跳板机解析。
+//!
+//! 试连使用临时连接池，结束后立即关闭，不注册也不持久化任何内容。
+//! TLS 在登录时协商，其结果在登录成功后从服务器读取：
+//! - PostgreSQL：`pg_stat_ssl`
+//! - MySQL：会话状态 `Ssl_version` / `Ssl_cipher`
+//! - Redis：本地 TLS 转发握手时协商的版本和加密套件
+
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use common::errors::{AppError, AppResult};
+use common::models::connection::{ConnectionConfig, CreateConnectionRequest, DbType};
+use common::models::diagnostics::{ConnectionDiagnostics, DiagnosticStage, DiagnosticStep, StepStatus};
+use sqlx::Row;
+use tokio::net::{lookup_host, TcpStream};
+use validator::Validate;
+
+use crate::pool_manager::{DatabasePool, PoolHandle, PoolManager};
+
+/// 连接诊断服务
+pub struct DiagnosticsService {
+    pool_manager: Arc<PoolManager>,
+}
+
+/// 逐步记录诊断结果
+struct Steps {
+    steps: Vec<DiagnosticStep>,
+    failed: bool,
+}
+
+impl Steps {
+    fn ok(&mut self, stage: DiagnosticStage, started: Instant, detail: impl Into<Option<String>>) {
+        self.push(stage, StepStatus::Ok, Some(started), detail.into());
+    }
+
+    fn fail(&mut self, stage: DiagnosticStage, started: Instant, error: impl ToString) {
+        self.push(stage, StepStatus::Failed, Some(started), Some(error.to_string()));
+        self.failed = true;
+    }
+
+    fn skip(&mut self, stage: DiagnosticStage, reason: &str) {
+        self.push(stage, StepStatus::Skipped, None, Some(reason.to_string()));
+    }
+
+    /// 将尚未执行的步骤标记为跳过
+    fn skip_rest(&mut self, reason: &str) {
+        for stage in STAGES {
+            if !self.steps.iter().any(|s| s.stage == stage) {
+                self.skip(stage, reason);
+            }
+        }
+    }
+
+    fn push(&mut self, stage: DiagnosticStage, status: StepStatus, started: Option<Instant>, detail: Option<String>) {
+        let duration_ms = started.map(|s| s.elapsed().as_millis() as u64);
+        self.steps.push(DiagnosticStep { stage, status, duration_ms, detail });
+    }
+}
+
+const STAGES: [DiagnosticStage; 7] = [
+    DiagnosticStage::Dns,
+    DiagnosticStage::Tcp,
+    DiagnosticStage::Tunnel,
+    DiagnosticStage::Tls,
+    DiagnosticStage::Auth,
+    DiagnosticStage::Version,
+    DiagnosticStage::Latency,
+];
+
+impl DiagnosticsService {
+    /// 创建新的连接诊断服务实例
+    pub fn new(pool_manager: Arc<PoolManager>) -> Self {
+        Self { pool_manager }
+    }
+
+    /// 试连并返回诊断结果
+    ///
+    /// 请求本身无效时返回错误；连接失败体现在结果的 `success` 和各步骤中。
+    pub async fn run(&self, req: CreateConnectionRequest) -> AppResult<ConnectionDiagnostics> {
+        req.validate()?;
+        let config = req.into_config("dry-run".into(), Utc::now().to_rfc3339())?;
+        let started = Instant::now();
+        let timeout = self.pool_manager.connect_timeout();
+        let mut steps = Steps { steps: Vec::new(), failed: false };
+
+        let target = match config.db_type {
+            DbType::SQLite => {
+                steps.skip(DiagnosticStage::Dns, "本地文件");
+                steps.skip(DiagnosticStage::Tcp, "本地文件");
+                steps.skip(DiagnosticStage::Tunnel, "本地文件");
+                steps.skip(DiagnosticStage::Tls, "本地文件");
+                config.file_path.clone()
+            }
+            _ => {
+                let host = config
+                    .host
+                    .clone()
+                    .ok_or_else(|| AppError::Validation(format!("{} requires host", config.db_type)))?;
+                let port = config
+                    .port
+                    .ok_or_else(|| AppError::Validation(format!("{} requires port", config.db_type)))?;
+                match &config.ssh_tunnel {
+                    Some(ssh) => probe_network(&mut steps, &ssh.host, ssh.port, timeout).await,
+                    None if host.starts_with('/') => {
+                        steps.skip(DiagnosticStage::Dns, "Unix 套接字");
+                        steps.skip(DiagnosticStage::Tcp, "Unix 套接字");
+                    }
+                    None => probe_network(&mut steps, &host, port, timeout).await,
+                }
+                let target = if host.contains(':') { format!("[{}]:{}", host, port) } else { format!("{}:{}", host, port) };
+                Some(match &config.ssh_tunnel {
+                    Some(ssh) => format!("{} via {}", target, ssh.bastion()),
+                    None => target,
+                })
+            }
+        };
+
+        let mut ssh = None;
+        if !steps.failed && config.db_type != DbType::SQLite {
+            if config.ssh_tunnel.is_none() {
+                steps.skip(DiagnosticStage::Tunnel, "未配置 SSH 隧道");
+            } else {
+                let tunnel_started = Instant::now();
+                match self.pool_manager.open_tunnel(&config).await {
+                    Ok(tunnel) => {
+                        let detail = tunnel.as_ref().map(|t| format!("已转发到本地端口 {}", t.local_addr().port()));
+                        steps.ok(DiagnosticStage::Tunnel, tunnel_started, detail);
+                        ssh = tunnel;
+                    }
+                    Err(e) => steps.fail(DiagnosticStage::Tunnel, tunnel_started, e),
+                }
+            }
+        }
+
+        let mut server_version = None;
+        let mut latency_ms = None;
+        if steps.failed {
+            steps.skip_rest("前置步骤失败");
+        } else if let Some(reason) = sqlite_missing(&config) {
+            steps.skip_rest(&reason);
+        } else {
+            let auth_started = Instant::now();
+            match self.pool_manager.open_pool_through(&config, ssh).await {
+                Ok(handle) if matches!(handle.pool, DatabasePool::Unsupported) => {
+                    steps.skip_rest("暂不支持该数据库类型的登录检查")
+                }
+                Ok(handle) => {
+                    let pool = &handle.pool;
+                    let login_ms = auth_started.elapsed();
+                    if config.db_type != DbType::SQLite {
+                        let tls_started = Instant::now();
+                        match tls_status(&handle).await {
+                            Ok(detail) => steps.ok(DiagnosticStage::Tls, tls_started, detail),
+                            Err(e) => steps.skip(DiagnosticStage::Tls, &format!("无法读取 TLS 状态: {}", e)),
+                        }
+                    }
+                    steps.steps.push(DiagnosticStep {
+                        stage: DiagnosticStage::Auth,
+                        status: StepStatus::Ok,
+                        duration_ms: Some(login_ms.as_millis() as u64),
+                        detail: config.username.clone().map(|user| format!("以 {} 登录", user)),
+                    });
+
+                    let version_started = Instant::now();
+                    match server_version_of(pool).await {
+                        Ok(version) => {
+                            steps.ok(DiagnosticStage::Version, version_started, version.clone());
+                            server_version = Some(version);
+                        }
+                        Err(e) => steps.fail(DiagnosticStage::Version, version_started, e),
+                    }
+
+                    let ping_started = Instant::now();
+                    match pool.ping().await {
+                        Ok(latency) => {
+                            steps.ok(DiagnosticStage::Latency, ping_started, None);
+                            latency_ms = Some(latency.as_millis() as u64);
+                        }
+                        Err(e) => steps.fail(DiagnosticStage::Latency, ping_started, e),
+                    }
+                    handle.close().await;
+                }
+                Err(e) => {
+                    let message = e.to_string();
+                    if config.db_type != DbType::SQLite && is_tls_error(&message) {
+                        steps.fail(DiagnosticStage::Tls, auth_started, message);
+                    } else {
+                        if config.db_type != DbType::SQLite {
+                            steps.skip(DiagnosticStage::Tls, "登录失败，未能确定");
+                        }
+                        steps.fail(DiagnosticStage::Auth, auth_started, message);
+                    }
+                    steps.skip_rest("前置步骤失败");
+                }
+            }
+        }
+
+        Ok(ConnectionDiagnostics {
+            success: !steps.failed,
+            db_type: config.db_type,
+            target,
+            steps: steps.steps,
+            server_version,
+            latency_ms,
+            total_ms: started.elapsed().as_millis() as u64,
+        })
+    }
+}
+
+/// 解析主机名并尝试 TCP 连接，记录两步结果
+async fn probe_network(steps: &mut Steps, host: &str, port: u16, timeout: Duration) {
+    let started = Instant::now();
+    let addrs: Vec<SocketAddr> = match tokio::time::timeout(timeout, lookup_host((host, port))).await {
+        Ok(Ok(addrs)) => addrs.collect(),
+        Ok(Err(e)) => return steps.fail(DiagnosticStage::Dns, started, format!("无法解析 {}: {}", host, e)),
+        Err(_) => return steps.fail(DiagnosticStage::Dns, started, format!("解析 {} 超时", host)),
+    };
+    if addrs.is_empty() {
+        return steps.fail(DiagnosticStage::Dns, started, format!("{} 没有可用地址", host));
+    }
+    let list = addrs.iter().map(|a| a.ip().to_string()).collect::<Vec<_>>().join(", ");
+    steps.ok(DiagnosticStage::Dns, started, list);
+
+    let started = Instant::now();
+    let mut last_error = None;
+    for addr in &addrs {
+        match tokio::time::timeout(timeout, TcpStream::connect(addr)).await {
+            Ok(Ok(_)) => return steps.ok(DiagnosticStage::Tcp, started, format!("已连接 {}", addr)),
+            Ok(Err(e)) => last_error = Some(format!("{}: {}", addr, e)),
+            Err(_) => last_error = Some(format!("{}: 连接超时", addr)),
+        }
+    }
+    steps.fail(DiagnosticStage::Tcp, started, last_error.unwrap_or_default());
+}
+
+/// SQLite 文件不存在时返回跳过原因；试连不应创建文件
+fn sqlite_missing(config: &ConnectionConfig) -> Option<String> {
+    let path = config.file_path.as_deref().filter(|_| config.db_type == DbType::SQLite)?;
+    if path == ":memory:" || Path::new(path).exists() {
+        return None;
+    }
+    Some(format!("文件 {} 不存在，保存连接时将创建", path))
+}
+
+/// 读取连接实际协商的 TLS 版本和加密套件
+async fn tls_status(handle: &PoolHandle) -> AppResult<Option<String>> {
+    let query_error = |e: sqlx::Error| AppError::DatabaseQuery(e.to_string());
+    let plaintext = || Some("未加密".to_string());
+    match &handle.pool {
+        DatabasePool::Postgres(pool) => {
+            let row = sqlx::query(
+                "SELECT ssl, version, cipher FROM pg_stat_ssl WHERE pid = pg_backend_pid()",
+            )
+            .fetch_optional(pool)
+            .await
+            .map_err(query_error)?;
+            Ok(match row {
+                Some(row) if row.try_get::<bool, _>("ssl").unwrap_or(false) => Some(format!(
+                    "{} {}",
+                    row.try_get::<Option<String>, _>("version").ok().flatten().unwrap_or_default(),
+                    row.try_get::<Option<String>, _>("cipher").ok().flatten().unwrap_or_default()
+                )),
+                _ => plaintext(),
+            })
+        }
+        DatabasePool::MySQL(pool) => {
+            let rows = sqlx::query("SHOW SESSION STATUS WHERE Variable_name IN ('Ssl_version', 'Ssl_cipher')")
+                .fetch_all(pool)
+                .await
+                .map_err(query_error)?;
+            let values: Vec<String> = rows
+                .iter()
+                .filter_map(|row| row.try_get::<String, _>(1).ok())
+                .filter(|v| !v.is_empty())
+                .collect();
+            Ok(if values.is_empty() { plaintext() } else { Some(values.join(" ")) })
+        }
+        DatabasePool::Redis(_) => Ok(handle.tunnel_tls().map(str::to_string).or_else(plaintext)),
+        _ => Ok(None),
+    }
+}
+
+/// 读取服务器版本
+async fn server_version_of(pool: &DatabasePool) -> AppResult<String> {
+    let query_error = |e: sqlx::Error| AppError::DatabaseQuery(e.to_string());
+    match pool {
+        DatabasePool::Postgres(pool) => sqlx::query_scalar("SHOW server_version")
+            .fetch_one(pool)
+            .await
+            .map_err(query_error),
+        DatabasePool::MySQL(pool) => sqlx::query_scalar("SELECT VERSION()")
+            .fetch_one(pool)
+            .await
+            .map_err(query_error),
+        DatabasePool::SQLite(pool) => sqlx::query_scalar("SELECT sqlite_version()")
+            .fetch_one(pool)
+            .await
+            .map_err(query_error),
+        DatabasePool::Redis(manager) => {
+            let mut conn = manager.clone();
+            let info: String = redis::cmd("INFO")
+                .arg("server")
+                .query_async(&mut conn)
+                .await
+                .map_err(|e| AppError::RedisOperation(e.to_string()))?;
+            info.lines()
+                .find_map(|line| line.strip_prefix("redis_version:"))
+                .map(|v| v.trim().to_string())
+                .ok_or_else(|| AppError::RedisOperation("INFO 中没有 redis_version".into()))
+        }
+        DatabasePool::Unsupported => Err(AppError::UnsupportedDatabaseType("Connection type not supported yet".into())),
+    }
+}
+
+/// 登录错误是否由 TLS 协商引起
+fn is_tls_error(message: &str) -> bool {
+    let message = message.to_ascii_lowercase();
+    ["tls", "ssl", "certificate", "handshake"].iter().any(|k| message.contains(k))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    fn request(json: serde_json::Value) -> CreateConnectionRequest {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_sqlite_does_not_register() {
+        let manager = test_support::manager();
+        let service = DiagnosticsService::new(manager.clone());
+
+        let result = service
+            .run(request(serde_json::json!({"name": "mem", "db_type": "sqlite", "file_path": ":memory:"})))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert!(result.server_version.is_some());
+        let status = |stage| result.steps.iter().find(|s| s.stage == stage).unwrap().status;
+        assert_eq!(status(DiagnosticStage::Tcp), StepStatus::Skipped);
+        assert_eq!(status(DiagnosticStage::Auth), StepStatus::Ok);
+        assert_eq!(status(DiagnosticStage::Latency), StepStatus::Ok);
+        assert!(manager.list_connections().await.is_empty());
+
+        let path = std::env::temp_dir().join(format!("dry-run-{}.db", uuid::Uuid::new_v4()));
+        let result = service
+            .run(request(serde_json::json!({"name": "f", "db_type": "sqlite", "file_path": path})))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_reports_refused_port() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        drop(listener);
+
+        let service = DiagnosticsService::new(test_support::manager());
+        let result = service
+            .run(request(serde_json::json!({"name": "pg", "db_type": "postgres", "host": "127.0.0.1", "port": port})))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        let statuses: Vec<_> = result.steps.iter().map(|s| (s.stage, s.status)).collect();
+        assert_eq!(statuses[0], (DiagnosticStage::Dns, StepStatus::Ok));
+        assert_eq!(statuses[1], (DiagnosticStage::Tcp, StepStatus::Failed));
+        assert!(statuses[2..].iter().all(|(_, s)| *s == StepStatus::Skipped));
+    }
+}
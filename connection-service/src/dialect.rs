@@ -0,0 +1,417 @@
+//! SQL 方言辅助
+//!
+//! 处理不同数据库之间的差异：
+//! - 标识符和字符串字面量的引用
+//! - 列类型在方言之间的转换（记录有损转换）
+//! - 默认值表达式的转换
+
+use common::errors::{AppError, AppResult};
+use common::models::connection::DbType;
+use common::models::ddl::TypeMapping;
+
+/// 支持元数据和 SQL 生成的 SQL 方言
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dialect {
+    MySql,
+    Postgres,
+    Sqlite,
+}
+
+impl Dialect {
+    /// 根据数据库类型获取方言
+    pub fn from_db_type(db_type: &DbType) -> AppResult<Self> {
+        match db_type {
+            DbType::MySQL | DbType::MariaDB => Ok(Dialect::MySql),
+            DbType::Postgres => Ok(Dialect::Postgres),
+            DbType::SQLite => Ok(Dialect::Sqlite),
+            other => Err(AppError::UnsupportedDatabaseType(format!(
+                "{} 不支持 SQL 方言转换",
+                other
+            ))),
+        }
+    }
+
+    /// 方言对应的数据库类型
+    pub fn db_type(&self) -> DbType {
+        match self {
+            Dialect::MySql => DbType::MySQL,
+            Dialect::Postgres => DbType::Postgres,
+            Dialect::Sqlite => DbType::SQLite,
+        }
+    }
+
+    /// 引用标识符（表名、列名等），内部的引号会被转义
+    pub fn quote_ident(&self, ident: &str) -> String {
+        match self {
+            Dialect::MySql => format!("`{}`", ident.replace('`', "``")),
+            Dialect::Postgres | Dialect::Sqlite => format!("\"{}\"", ident.replace('"', "\"\"")),
+        }
+    }
+
+    /// 引用可能带 schema 前缀的表名
+    pub fn qualified_table(&self, schema: Option<&str>, table: &str) -> String {
+        match schema {
+            Some(schema) => format!("{}.{}", self.quote_ident(schema), self.quote_ident(table)),
+            None => self.quote_ident(table),
+        }
+    }
+
+    /// 引用字符串字面量
+    pub fn quote_literal(&self, value: &str) -> String {
+        match self {
+            Dialect::MySql => format!("'{}'", value.replace('\\', "\\\\").replace('\'', "''")),
+            Dialect::Postgres | Dialect::Sqlite => format!("'{}'", value.replace('\'', "''")),
+        }
+    }
+
+    /// 将 `source` 方言的原生列类型转换为当前方言的类型
+    pub fn translate_type(&self, source: Dialect, column: &str, native: &str) -> TypeMapping {
+        let (target_type, lossy, note) = if source == *self {
+            (native.to_string(), false, None)
+        } else {
+            let logical = LogicalType::parse(source, native);
+            let (target_type, lossy, note) = self.render_type(&logical);
+            (target_type, lossy, note.map(str::to_string))
+        };
+        TypeMapping {
+            column: column.to_string(),
+            source_type: native.to_string(),
+            target_type,
+            lossy,
+            note,
+        }
+    }
+
+    /// 转换列默认值表达式；无法在目标方言中表达时返回 `None`
+    pub fn translate_default(&self, source: Dialect, expr: &str) -> Option<String> {
+        if source == *self {
+            return Some(expr.to_string());
+        }
+        let expr = strip_pg_cast(expr.trim());
+        let lower = expr.to_lowercase();
+
+        // 序列默认值由自增属性表达
+        if lower.starts_with("nextval(") {
+            return None;
+        }
+        if matches!(
+            lower.as_str(),
+            "now()" | "current_timestamp" | "current_timestamp()" | "localtimestamp" | "transaction_timestamp()"
+        ) || lower.starts_with("current_timestamp(")
+        {
+            return Some("CURRENT_TIMESTAMP".to_string());
+        }
+        match (lower.as_str(), self) {
+            ("true", Dialect::MySql | Dialect::Sqlite) => return Some("1".to_string()),
+            ("false", Dialect::MySql | Dialect::Sqlite) => return Some("0".to_string()),
+            _ => {}
+        }
+        // 字符串字面量按目标方言重新引用
+        if let Some(literal) = unquote_literal(&expr) {
+            return Some(self.quote_literal(&literal));
+        }
+        Some(expr)
+    }
+
+    /// 渲染逻辑类型，返回 (类型, 是否有损, 说明)
+    fn render_type(&self, logical: &LogicalType) -> (String, bool, Option<&'static str>) {
+        use LogicalType::*;
+        let plain = |t: &str| (t.to_string(), false, None);
+        match self {
+            Dialect::MySql => match logical {
+                Boolean => plain("tinyint(1)"),
+                TinyInt { unsigned } => plain(&with_unsigned("tinyint", *unsigned)),
+                SmallInt { unsigned } => plain(&with_unsigned("smallint", *unsigned)),
+                Int { unsigned } => plain(&with_unsigned("int", *unsigned)),
+                BigInt { unsigned } => plain(&with_unsigned("bigint", *unsigned)),
+                Decimal { precision: Some(p), scale } if *p <= 65 => {
+                    plain(&format!("decimal({},{})", p, scale.unwrap_or(0)))
+                }
+                Decimal { .. } => (
+                    "decimal(65,30)".to_string(),
+                    true,
+                    Some("unbounded precision limited to decimal(65,30)"),
+                ),
+                Float => plain("float"),
+                Double => plain("double"),
+                Char(n) => plain(&format!("char({})", n.unwrap_or(1))),
+                Varchar(Some(n)) if *n <= 16383 => plain(&format!("varchar({})", n)),
+                Varchar(_) | Text => plain("longtext"),
+                Binary => plain("longblob"),
+                Date => plain("date"),
+                Time => plain("time(6)"),
+                Timestamp => plain("datetime(6)"),
+                TimestampTz => ("datetime(6)".to_string(), true, Some("time zone offset is not stored")),
+                Year => plain("year"),
+                Json => plain("json"),
+                Uuid => ("char(36)".to_string(), false, Some("stored as text")),
+                Enum(values) => plain(&format!(
+                    "enum({})",
+                    values.iter().map(|v| self.quote_literal(v)).collect::<Vec<_>>().join(",")
+                )),
+                Other(_) => ("longtext".to_string(), true, Some("no equivalent type, stored as text")),
+            },
+            Dialect::Postgres => match logical {
+                Boolean => plain("boolean"),
+                TinyInt { .. } => plain("smallint"),
+                SmallInt { unsigned: false } => plain("smallint"),
+                SmallInt { unsigned: true } => ("integer".to_string(), false, Some("widened to hold unsigned range")),
+                Int { unsigned: false } => plain("integer"),
+                Int { unsigned: true } => ("bigint".to_string(), false, Some("widened to hold unsigned range")),
+                BigInt { unsigned: false } => plain("bigint"),
+                BigInt { unsigned: true } => ("numeric(20,0)".to_string(), false, Some("widened to hold unsigned range")),
+                Decimal { precision: Some(p), scale } => plain(&format!("numeric({},{})", p, scale.unwrap_or(0))),
+                Decimal { precision: None, .. } => plain("numeric"),
+                Float => plain("real"),
+                Double => plain("double precision"),
+                Char(n) => plain(&format!("character({})", n.unwrap_or(1))),
+                Varchar(Some(n)) => plain(&format!("character varying({})", n)),
+                Varchar(None) | Text => plain("text"),
+                Binary => plain("bytea"),
+                Date => plain("date"),
+                Time => plain("time"),
+                Timestamp => plain("timestamp"),
+                TimestampTz => plain("timestamp with time zone"),
+                Year => ("smallint".to_string(), false, Some("year stored as a number")),
+                Json => plain("jsonb"),
+                Uuid => plain("uuid"),
+                Enum(_) => ("text".to_string(), true, Some("enum values are not enforced")),
+                Other(_) => ("text".to_string(), true, Some("no equivalent type, stored as text")),
+            },
+            Dialect::Sqlite => match logical {
+                Boolean => ("INTEGER".to_string(), false, Some("stored as 0/1")),
+                TinyInt { .. } | SmallInt { .. } | Int { .. } | BigInt { unsigned: false } => plain("INTEGER"),
+                BigInt { unsigned: true } => (
+                    "INTEGER".to_string(),
+                    true,
+                    Some("values above 2^63-1 do not fit"),
+                ),
+                Decimal { .. } => ("NUMERIC".to_string(), true, Some("precision and scale are not enforced")),
+                Float | Double => plain("REAL"),
+                Char(Some(_)) | Varchar(Some(_)) => ("TEXT".to_string(), true, Some("length limit is not enforced")),
+                Char(None) | Varchar(None) | Text => plain("TEXT"),
+                Binary => plain("BLOB"),
+                Date | Time | Timestamp => ("TEXT".to_string(), false, Some("stored as ISO-8601 text")),
+                TimestampTz => ("TEXT".to_string(), false, Some("stored as ISO-8601 text with offset")),
+                Year => plain("INTEGER"),
+                Json | Uuid => ("TEXT".to_string(), false, Some("stored as text")),
+                Enum(_) => ("TEXT".to_string(), true, Some("enum values are not enforced")),
+                Other(_) => ("TEXT".to_string(), true, Some("no equivalent type, stored as text")),
+            },
+        }
+    }
+}
+
+/// 与方言无关的列类型
+#[derive(Debug, Clone, PartialEq)]
+enum LogicalType {
+    Boolean,
+    TinyInt { unsigned: bool },
+    SmallInt { unsigned: bool },
+    Int { unsigned: bool },
+    BigInt { unsigned: bool },
+    Decimal { precision: Option<u32>, scale: Option<u32> },
+    Float,
+    Double,
+    Char(Option<u32>),
+    Varchar(Option<u32>),
+    Text,
+    Binary,
+    Date,
+    Time,
+    Timestamp,
+    TimestampTz,
+    Year,
+    Json,
+    Uuid,
+    Enum(Vec<String>),
+    Other(String),
+}
+
+impl LogicalType {
+    /// 解析原生类型，如 `int(11) unsigned`、`character varying(20)`、`VARCHAR(255)`
+    fn parse(dialect: Dialect, native: &str) -> Self {
+        let trimmed = native.trim();
+        if trimmed.ends_with("[]") {
+            return LogicalType::Other(native.to_string());
+        }
+
+        // 拆出括号内的参数，剩余部分作为类型名
+        let (name, args) = match (trimmed.find('('), trimmed.rfind(')')) {
+            (Some(open), Some(close)) if close > open => (
+                format!("{} {}", &trimmed[..open], &trimmed[close + 1..]).to_lowercase(),
+                Some(&trimmed[open + 1..close]),
+            ),
+            _ => (trimmed.to_lowercase(), None),
+        };
+        let unsigned = name.split_whitespace().any(|w| w == "unsigned");
+        let name = name
+            .split_whitespace()
+            .filter(|w| !matches!(*w, "unsigned" | "signed" | "zerofill"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let numbers: Vec<u32> = args
+            .map(|a| a.split(',').filter_map(|n| n.trim().parse().ok()).collect())
+            .unwrap_or_default();
+        let first = numbers.first().copied();
+
+        match name.as_str() {
+            "bool" | "boolean" => LogicalType::Boolean,
+            "tinyint" if dialect == Dialect::MySql && first == Some(1) => LogicalType::Boolean,
+            "tinyint" => LogicalType::TinyInt { unsigned },
+            "smallint" | "int2" | "smallserial" => LogicalType::SmallInt { unsigned },
+            "int" | "integer" if dialect == Dialect::Sqlite => LogicalType::BigInt { unsigned },
+            "mediumint" | "int" | "integer" | "int4" | "serial" => LogicalType::Int { unsigned },
+            "bigint" | "int8" | "bigserial" => LogicalType::BigInt { unsigned },
+            "decimal" | "numeric" | "dec" => LogicalType::Decimal {
+                precision: first,
+                scale: numbers.get(1).copied(),
+            },
+            "float" | "real" | "float4" => LogicalType::Float,
+            "double" | "double precision" | "float8" => LogicalType::Double,
+            "char" | "character" | "nchar" | "bpchar" => LogicalType::Char(first),
+            "varchar" | "character varying" | "nvarchar" | "varchar2" => LogicalType::Varchar(first),
+            "text" | "tinytext" | "mediumtext" | "longtext" | "clob" | "citext" => LogicalType::Text,
+            "blob" | "tinyblob" | "mediumblob" | "longblob" | "bytea" | "binary" | "varbinary" => {
+                LogicalType::Binary
+            }
+            "date" => LogicalType::Date,
+            "time" | "time without time zone" => LogicalType::Time,
+            "datetime" | "timestamp" | "timestamp without time zone" => LogicalType::Timestamp,
+            "timestamptz" | "timestamp with time zone" => LogicalType::TimestampTz,
+            "year" => LogicalType::Year,
+            "json" | "jsonb" => LogicalType::Json,
+            "uuid" => LogicalType::Uuid,
+            "enum" => LogicalType::Enum(args.map(split_literals).unwrap_or_default()),
+            _ if dialect == Dialect::Sqlite => Self::sqlite_affinity(&name, native),
+            _ => LogicalType::Other(native.to_string()),
+        }
+    }
+
+    /// SQLite 按类型亲和性规则推断未知类型名
+    fn sqlite_affinity(name: &str, native: &str) -> Self {
+        if name.contains("int") {
+            LogicalType::BigInt { unsigned: false }
+        } else if name.contains("char") || name.contains("clob") || name.contains("text") {
+            LogicalType::Text
+        } else if name.contains("blob") {
+            LogicalType::Binary
+        } else if name.contains("real") || name.contains("floa") || name.contains("doub") {
+            LogicalType::Double
+        } else if name.is_empty() {
+            LogicalType::Other(native.to_string())
+        } else {
+            LogicalType::Decimal { precision: None, scale: None }
+        }
+    }
+}
+
+fn with_unsigned(base: &str, unsigned: bool) -> String {
+    if unsigned {
+        format!("{} unsigned", base)
+    } else {
+        base.to_string()
+    }
+}
+
+/// 去掉 PostgreSQL 的类型转换后缀，如 `'new'::character varying`
+fn strip_pg_cast(expr: &str) -> String {
+    if let Some(literal) = expr.strip_prefix('\'') {
+        let mut chars = literal.char_indices().peekable();
+        while let Some((i, c)) = chars.next() {
+            if c == '\'' {
+                if matches!(chars.peek(), Some((_, '\''))) {
+                    chars.next();
+                    continue;
+                }
+                let end = i + 2;
+                if expr[end..].starts_with("::") {
+                    return expr[..end].to_string();
+                }
+                break;
+            }
+        }
+        return expr.to_string();
+    }
+    match expr.find("::") {
+        Some(pos) if !expr.contains('(') => expr[..pos].to_string(),
+        _ => expr.to_string(),
+    }
+}
+
+/// 解析单引号字符串字面量，不是字面量时返回 `None`
+fn unquote_literal(expr: &str) -> Option<String> {
+    let inner = expr.strip_prefix('\'')?.strip_suffix('\'')?;
+    Some(inner.replace("''", "'"))
+}
+
+/// 拆分逗号分隔的字符串字面量列表，如 `'a','b,c'`
+fn split_literals(list: &str) -> Vec<String> {
+    let mut values = Vec::new();
+    let mut current = String::new();
+    let mut in_literal = false;
+    let mut chars = list.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, in_literal) {
+            ('\'', false) => in_literal = true,
+            ('\'', true) if chars.peek() == Some(&'\'') => {
+                current.push('\'');
+                chars.next();
+            }
+            ('\'', true) => {
+                in_literal = false;
+                values.push(std::mem::take(&mut current));
+            }
+            (c, true) => current.push(c),
+            _ => {}
+        }
+    }
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quote_ident_escapes_quotes() {
+        assert_eq!(Dialect::MySql.quote_ident("a`b"), "`a``b`");
+        assert_eq!(Dialect::Postgres.quote_ident("a\"b"), "\"a\"\"b\"");
+    }
+
+    #[test]
+    fn test_translate_mysql_to_postgres() {
+        let pg = Dialect::Postgres;
+        let mapping = pg.translate_type(Dialect::MySql, "id", "int unsigned");
+        assert_eq!(mapping.target_type, "bigint");
+        assert!(!mapping.lossy);
+        assert_eq!(pg.translate_type(Dialect::MySql, "f", "tinyint(1)").target_type, "boolean");
+        assert_eq!(pg.translate_type(Dialect::MySql, "n", "varchar(64)").target_type, "character varying(64)");
+        assert!(pg.translate_type(Dialect::MySql, "e", "enum('a','b')").lossy);
+    }
+
+    #[test]
+    fn test_translate_postgres_to_mysql_and_sqlite() {
+        let mapping = Dialect::MySql.translate_type(Dialect::Postgres, "t", "timestamp(3) with time zone");
+        assert_eq!(mapping.target_type, "datetime(6)");
+        assert!(mapping.lossy);
+        let mapping = Dialect::Sqlite.translate_type(Dialect::Postgres, "n", "numeric(10,2)");
+        assert_eq!(mapping.target_type, "NUMERIC");
+        assert!(mapping.lossy);
+        let mapping = Dialect::MySql.translate_type(Dialect::Sqlite, "id", "INTEGER");
+        assert_eq!(mapping.target_type, "bigint");
+    }
+
+    #[test]
+    fn test_translate_default() {
+        let mysql = Dialect::MySql;
+        assert_eq!(
+            mysql.translate_default(Dialect::Postgres, "'new'::character varying").as_deref(),
+            Some("'new'")
+        );
+        assert_eq!(mysql.translate_default(Dialect::Postgres, "now()").as_deref(), Some("CURRENT_TIMESTAMP"));
+        assert_eq!(mysql.translate_default(Dialect::Postgres, "nextval('s'::regclass)"), None);
+        assert_eq!(mysql.translate_default(Dialect::Postgres, "true").as_deref(), Some("1"));
+        assert_eq!(mysql.translate_default(Dialect::Postgres, "0").as_deref(), Some("0"));
+    }
+}
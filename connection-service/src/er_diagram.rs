@@ -0,0 +1,381 @@
+//! ER 图导出服务
+//!
+//! 根据目录中的主键和外键构建表关系图，并渲染为：
+//! - Mermaid `erDiagram` 文本
+//! - Graphviz DOT 文本
+//! - JSON 节点/边
+//!
+//! 支持按表名前缀或表名列表过滤，以及限制为距焦点表 N 跳以内的子图。
+
+use std::collections::{HashSet, VecDeque};
+use std::sync::Arc;
+
+use common::errors::{AppError, AppResult};
+use common::models::er_diagram::{ErColumn, ErDiagram, ErDiagramQuery, ErEdge, ErFormat, ErNode};
+use common::models::metadata::TableDetail;
+
+use crate::metadata::MetadataService;
+use crate::pool_manager::PoolManager;
+
+/// 指定焦点表但未指定跳数时的默认跳数
+const DEFAULT_HOPS: u32 = 1;
+
+/// ER 图服务
+pub struct ErDiagramService {
+    pool_manager: Arc<PoolManager>,
+}
+
+impl ErDiagramService {
+    /// 创建新的 ER 图服务实例
+    pub fn new(pool_manager: Arc<PoolManager>) -> Self {
+        Self { pool_manager }
+    }
+
+    /// 渲染 schema 的 ER 图
+    pub async fn render(&self, id: &str, query: &ErDiagramQuery) -> AppResult<ErDiagram> {
+        let details = MetadataService::new(self.pool_manager.clone())
+            .schema_details(id, query.schema.as_deref())
+            .await?;
+        let (nodes, edges) = build_graph(&details, query)?;
+        let format = query.format.unwrap_or_default();
+        let content = match format {
+            ErFormat::Mermaid => Some(render_mermaid(&nodes, &edges)),
+            ErFormat::Dot => Some(render_dot(&nodes, &edges)),
+            ErFormat::Json => None,
+        };
+        Ok(ErDiagram {
+            format,
+            content,
+            nodes,
+            edges,
+        })
+    }
+}
+
+/// 按查询条件过滤表并构建节点和边
+fn build_graph(details: &[TableDetail], query: &ErDiagramQuery) -> AppResult<(Vec<ErNode>, Vec<ErEdge>)> {
+    let listed: Option<HashSet<&str>> = query
+        .tables
+        .as_deref()
+        .map(|t| t.split(',').map(str::trim).filter(|t| !t.is_empty()).collect());
+    let mut selected: Vec<&TableDetail> = details
+        .iter()
+        .filter(|d| query.prefix.as_deref().is_none_or(|p| d.table.name.starts_with(p)))
+        .filter(|d| listed.as_ref().is_none_or(|l| l.contains(d.table.name.as_str())))
+        .collect();
+
+    let names: HashSet<&str> = selected.iter().map(|d| d.table.name.as_str()).collect();
+    let links: Vec<(&str, &str)> = selected
+        .iter()
+        .flat_map(|d| {
+            d.foreign_keys
+                .iter()
+                .map(move |fk| (d.table.name.as_str(), fk.referenced_table.as_str()))
+        })
+        .filter(|(_, to)| names.contains(to))
+        .collect();
+
+    if let Some(focus) = query.focus.as_deref() {
+        if !names.contains(focus) {
+            return Err(AppError::NotFound(format!("table {}", focus)));
+        }
+        let reachable = within_hops(focus, &links, query.hops.unwrap_or(DEFAULT_HOPS));
+        selected.retain(|d| reachable.contains(d.table.name.as_str()));
+    }
+
+    let included: HashSet<&str> = selected.iter().map(|d| d.table.name.as_str()).collect();
+    let mut nodes = Vec::with_capacity(selected.len());
+    let mut edges = Vec::new();
+    for detail in &selected {
+        let pk: &[String] = detail.primary_key.as_ref().map(|pk| pk.columns.as_slice()).unwrap_or(&[]);
+        nodes.push(ErNode {
+            id: detail.table.name.clone(),
+            kind: detail.table.kind,
+            columns: detail
+                .columns
+                .iter()
+                .map(|c| ErColumn {
+                    name: c.name.clone(),
+                    data_type: c.data_type.clone(),
+                    nullable: c.nullable,
+                    primary_key: pk.contains(&c.name),
+                    foreign_key: detail.foreign_keys.iter().any(|fk| fk.columns.contains(&c.name)),
+                })
+                .collect(),
+        });
+
+        for fk in detail.foreign_keys.iter().filter(|fk| included.contains(fk.referenced_table.as_str())) {
+            let unique = pk == fk.columns.as_slice()
+                || detail.indexes.iter().any(|i| i.unique && i.columns == fk.columns);
+            edges.push(ErEdge {
+                name: fk.name.clone(),
+                from: detail.table.name.clone(),
+                from_columns: fk.columns.clone(),
+                to: fk.referenced_table.clone(),
+                to_columns: fk.referenced_columns.clone(),
+                one_to_one: unique,
+                optional: fk
+                    .columns
+                    .iter()
+                    .any(|c| detail.column(c).is_some_and(|c| c.nullable)),
+            });
+        }
+    }
+    Ok((nodes, edges))
+}
+
+/// 沿外键（不区分方向）广度优先搜索，返回 `hops` 跳以内的表
+fn within_hops<'a>(focus: &'a str, links: &[(&'a str, &'a str)], hops: u32) -> HashSet<&'a str> {
+    let mut reached = HashSet::from([focus]);
+    let mut queue = VecDeque::from([(focus, 0)]);
+    while let Some((table, depth)) = queue.pop_front() {
+        if depth == hops {
+            continue;
+        }
+        for (from, to) in links {
+            let next = match (*from == table, *to == table) {
+                (true, _) => *to,
+                (_, true) => *from,
+                _ => continue,
+            };
+            if reached.insert(next) {
+                queue.push_back((next, depth + 1));
+            }
+        }
+    }
+    reached
+}
+
+// ============== 渲染 ==============
+
+/// Mermaid 标识符只能包含字母、数字、`_` 和 `-`
+fn mermaid_ident(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_alphanumeric() || c == '_' || c == '-' { c } else { '_' })
+        .collect()
+}
+
+/// Mermaid 属性类型不能包含空格和逗号
+fn mermaid_type(data_type: &str) -> String {
+    data_type
+        .chars()
+        .map(|c| if c.is_alphanumeric() || "_()[]".contains(c) { c } else { '_' })
+        .collect()
+}
+
+fn render_mermaid(nodes: &[ErNode], edges: &[ErEdge]) -> String {
+    let mut out = String::from("erDiagram\n");
+    for node in nodes {
+        out.push_str(&format!("    {} {{\n", mermaid_ident(&node.id)));
+        for column in &node.columns {
+            let keys: Vec<&str> = [(column.primary_key, "PK"), (column.foreign_key, "FK")]
+                .iter()
+                .filter(|(set, _)| *set)
+                .map(|(_, key)| *key)
+                .collect();
+            out.push_str(&format!(
+                "        {} {}{}\n",
+                mermaid_type(&column.data_type),
+                mermaid_ident(&column.name),
+                if keys.is_empty() { String::new() } else { format!(" {}", keys.join(", ")) }
+            ));
+        }
+        out.push_str("    }\n");
+    }
+    for edge in edges {
+        // 被引用表在左侧：左端基数固定为 1（可选外键为 0..1），右端为引用方基数
+        let left = if edge.optional { "|o" } else { "||" };
+        let right = if edge.one_to_one { "o|" } else { "o{" };
+        out.push_str(&format!(
+            "    {} {}--{} {} : \"{}\"\n",
+            mermaid_ident(&edge.to),
+            left,
+            right,
+            mermaid_ident(&edge.from),
+            edge.name.replace('"', "'")
+        ));
+    }
+    out
+}
+
+fn dot_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn render_dot(nodes: &[ErNode], edges: &[ErEdge]) -> String {
+    let mut out = String::from("digraph er {\n    graph [rankdir=LR];\n    node [shape=plaintext];\n");
+    for node in nodes {
+        let mut rows = format!(
+            "<TR><TD BGCOLOR=\"lightgrey\"><B>{}</B></TD></TR>",
+            html_escape(&node.id)
+        );
+        for (i, column) in node.columns.iter().enumerate() {
+            let keys: Vec<&str> = [(column.primary_key, "PK"), (column.foreign_key, "FK")]
+                .iter()
+                .filter(|(set, _)| *set)
+                .map(|(_, key)| *key)
+                .collect();
+            let name = if column.primary_key {
+                format!("<U>{}</U>", html_escape(&column.name))
+            } else {
+                html_escape(&column.name)
+            };
+            rows.push_str(&format!(
+                "<TR><TD ALIGN=\"LEFT\" PORT=\"c{}\">{}: {}{}</TD></TR>",
+                i,
+                name,
+                html_escape(&column.data_type),
+                if keys.is_empty() { String::new() } else { format!(" ({})", keys.join(", ")) }
+            ));
+        }
+        out.push_str(&format!(
+            "    \"{}\" [label=<<TABLE BORDER=\"0\" CELLBORDER=\"1\" CELLSPACING=\"0\">{}</TABLE>>];\n",
+            dot_escape(&node.id),
+            rows
+        ));
+    }
+
+    let port = |table: &str, columns: &[String]| -> String {
+        nodes
+            .iter()
+            .find(|n| n.id == table)
+            .and_then(|n| n.columns.iter().position(|c| columns.first() == Some(&c.name)))
+            .map(|i| format!(":c{}", i))
+            .unwrap_or_default()
+    };
+    for edge in edges {
+        out.push_str(&format!(
+            "    \"{}\"{} -> \"{}\"{} [label=\"{}\"{}];\n",
+            dot_escape(&edge.from),
+            port(&edge.from, &edge.from_columns),
+            dot_escape(&edge.to),
+            port(&edge.to, &edge.to_columns),
+            dot_escape(&edge.name),
+            if edge.optional { ", style=dashed" } else { "" }
+        ));
+    }
+    out.push_str("}\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::models::metadata::{ColumnItem, ForeignKeyItem, PrimaryKeyItem, TableItem, TableKind};
+
+    fn table(name: &str, references: &[&str]) -> TableDetail {
+        let column = |name: &str, nullable: bool| ColumnItem {
+            name: name.to_string(),
+            ordinal: 0,
+            data_type: "integer".to_string(),
+            nullable,
+            default_value: None,
+            is_primary_key: name == "id",
+            auto_increment: false,
+            comment: None,
+        };
+        let mut columns = vec![column("id", false)];
+        let mut foreign_keys = Vec::new();
+        for target in references {
+            let fk_column = format!("{}_id", target);
+            columns.push(column(&fk_column, true));
+            foreign_keys.push(ForeignKeyItem {
+                name: format!("fk_{}_{}", name, target),
+                columns: vec![fk_column],
+                referenced_schema: None,
+                referenced_table: target.to_string(),
+                referenced_columns: vec!["id".to_string()],
+                on_update: None,
+                on_delete: None,
+            });
+        }
+        TableDetail {
+            table: TableItem {
+                schema: "public".to_string(),
+                name: name.to_string(),
+                kind: TableKind::Table,
+                row_estimate: None,
+                comment: None,
+            },
+            columns,
+            primary_key: Some(PrimaryKeyItem {
+                name: None,
+                columns: vec!["id".to_string()],
+            }),
+            indexes: vec![],
+            foreign_keys,
+        }
+    }
+
+    fn schema() -> Vec<TableDetail> {
+        // shop_items -> shop_orders -> shop_users <- audit_log
+        vec![
+            table("audit_log", &["shop_users"]),
+            table("shop_items", &["shop_orders"]),
+            table("shop_orders", &["shop_users"]),
+            table("shop_users", &[]),
+        ]
+    }
+
+    fn ids(nodes: &[ErNode]) -> Vec<&str> {
+        nodes.iter().map(|n| n.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_filters_by_prefix_and_hops() {
+        let details = schema();
+        let query = ErDiagramQuery {
+            prefix: Some("shop_".to_string()),
+            ..Default::default()
+        };
+        let (nodes, edges) = build_graph(&details, &query).unwrap();
+        assert_eq!(ids(&nodes), vec!["shop_items", "shop_orders", "shop_users"]);
+        assert_eq!(edges.len(), 2);
+
+        let query = ErDiagramQuery {
+            focus: Some("shop_users".to_string()),
+            hops: Some(1),
+            ..Default::default()
+        };
+        let (nodes, edges) = build_graph(&details, &query).unwrap();
+        assert_eq!(ids(&nodes), vec!["audit_log", "shop_orders", "shop_users"]);
+        assert!(edges.iter().all(|e| e.to == "shop_users"));
+
+        let query = ErDiagramQuery {
+            focus: Some("missing".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(build_graph(&details, &query), Err(AppError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_render_mermaid_and_dot() {
+        let details = schema();
+        let query = ErDiagramQuery {
+            tables: Some("shop_orders, shop_users".to_string()),
+            ..Default::default()
+        };
+        let (nodes, edges) = build_graph(&details, &query).unwrap();
+        assert_eq!(
+            render_mermaid(&nodes, &edges),
+            "erDiagram\n\
+             \x20   shop_orders {\n\
+             \x20       integer id PK\n\
+             \x20       integer shop_users_id FK\n\
+             \x20   }\n\
+             \x20   shop_users {\n\
+             \x20       integer id PK\n\
+             \x20   }\n\
+             \x20   shop_users |o--o{ shop_orders : \"fk_shop_orders_shop_users\"\n"
+        );
+        let dot = render_dot(&nodes, &edges);
+        assert!(dot.contains("\"shop_orders\":c1 -> \"shop_users\":c0 [label=\"fk_shop_orders_shop_users\", style=dashed];"));
+    }
+}
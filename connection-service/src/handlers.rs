@@ -1,33 +1,75 @@
 //! Handler模块
 
 use axum::{
-    extract::{Path, State},
-    Json,
+    extract::{Path, Query, State},
+    http::HeaderMap,
+    Extension, Json,
 };
 use chrono::{DateTime, Utc};
 use serde::Serialize;
 use utoipa::ToSchema;
 
 use common::errors::AppError;
-use common::models::connection::{ConnectionItem, CreateConnectionRequest};
-use common::response::ApiResponse;
+use common::middleware::{authorize_admin, RequestId};
+use common::models::activity::{
+    ActivityQuery, KillSessionRequest, KillSessionResult, LockReport, SessionActivity,
+};
+use common::models::audit::{AuditEntry, AuditQuery};
+use common::models::bundle::{ConnectionBundle, ExportBundleRequest, ImportBundleRequest, ImportReport};
+use common::models::completion::{CompletionRequest, CompletionResult, SchemaCacheInfo, SchemaCacheQuery};
+use common::models::connection::{
+    ConnectionDsn, ConnectionItem, ConnectionListQuery, CreateConnectionRequest, Environment, UpdateConnectionRequest,
+};
+use common::models::ddl::{DdlQuery, TableDdl};
+use common::models::diagnostics::ConnectionDiagnostics;
+use common::models::er_diagram::{ErDiagram, ErDiagramQuery};
+use common::models::metadata::{SchemaItem, SchemaQuery, TableDetail, TableItem};
+use common::models::pool::{ConnectionHealth, HealthOverview, PoolStats, PoolStatsQuery, TunnelStatus};
+use common::models::profile::{ProfileJob, ProfileRequest};
+use common::models::schema_diff::{SchemaDiff, SchemaDiffRequest};
+use common::models::search::{SearchIndexStatus, SearchQuery, SearchResult};
+use common::models::secrets::{KeyRotationResult, RotateKeyRequest};
+use common::models::snapshot::{
+    SchemaSnapshot, SnapshotChange, SnapshotResult, SnapshotSchedule, SnapshotScheduleRequest,
+    SnapshotSummary, SnapshotTrigger, TakeSnapshotRequest,
+};
+use common::models::storage::{StorageQuery, TableStorage};
+use common::models::table_data::{RowChangesRequest, RowChangesResult, RowsQuery};
+use common::response::{ApiResponse, PaginatedData};
+use crate::activity::ActivityService;
+use crate::bundle::BundleService;
+use crate::completion::CompletionService;
+use crate::ddl::DdlService;
+use crate::diagnostics::DiagnosticsService;
+use crate::er_diagram::ErDiagramService;
+use crate::locks::LockService;
+use crate::metadata::MetadataService;
+use crate::profile::ProfileService;
+use crate::schema_diff::SchemaDiffService;
+use crate::secrets::SecretService;
+use crate::snapshot::SnapshotService;
+use crate::storage::StorageService;
+use crate::table_data::TableDataService;
 use crate::service::ConnectionService;
 use crate::state::AppState;
 
-/// 列出所有已保存的数据库连接
+/// 按条件筛选并分页列出已保存的数据库连接
 #[utoipa::path(
     get,
     path = "/api/connections",
     tag = "connections",
+    params(ConnectionListQuery),
     responses(
-        (status = 200, description = "连接列表", body = ApiResponse<Vec<ConnectionItem>>)
+        (status = 200, description = "排序后当前页的连接及分页信息", body = ApiResponse<PaginatedData<ConnectionItem>>),
+        (status = 400, description = "排序字段无效")
     )
 )]
 pub async fn list_connections(
     State(state): State<AppState>,
-) -> Result<Json<ApiResponse<Vec<ConnectionItem>>>, AppError> {
+    Query(query): Query<ConnectionListQuery>,
+) -> Result<Json<ApiResponse<PaginatedData<ConnectionItem>>>, AppError> {
     let service = ConnectionService::new(state.pool_manager);
-    let data = service.list().await;
+    let data = service.list_page(&query).await?;
     Ok(Json(ApiResponse::ok_with_service(data, "connection-service")))
 }
 
@@ -72,6 +114,81 @@ pub async fn get_connection(
     Ok(Json(ApiResponse::ok_with_service(data, "connection-service")))
 }
 
+/// 部分更新数据库连接，未提供的字段保持不变，`null` 清空字段
+#[utoipa::path(
+    patch,
+    path = "/api/connections/{id}",
+    tag = "connections",
+    params(
+        ("id" = String, Path, description = "连接 ID")
+    ),
+    request_body = UpdateConnectionRequest,
+    responses(
+        (status = 200, description = "连接已更新", body = ApiResponse<ConnectionItem>),
+        (status = 404, description = "连接未找到"),
+        (status = 502, description = "无法用新配置建立连接，原连接保持不变")
+    )
+)]
+pub async fn update_connection(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(req): Json<UpdateConnectionRequest>,
+) -> Result<Json<ApiResponse<ConnectionItem>>, AppError> {
+    let service = ConnectionService::new(state.pool_manager);
+    let data = service.update(&id, req).await?;
+    state.schema_cache.invalidate(&id).await;
+    Ok(Json(ApiResponse::ok_with_service(data, "connection-service")))
+}
+
+/// 整体替换数据库连接配置，保留连接 ID 和创建时间
+#[utoipa::path(
+    put,
+    path = "/api/connections/{id}",
+    tag = "connections",
+    params(
+        ("id" = String, Path, description = "连接 ID")
+    ),
+    request_body = CreateConnectionRequest,
+    responses(
+        (status = 200, description = "连接已更新", body = ApiResponse<ConnectionItem>),
+        (status = 404, description = "连接未找到"),
+        (status = 502, description = "无法用新配置建立连接，原连接保持不变")
+    )
+)]
+pub async fn replace_connection(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(req): Json<CreateConnectionRequest>,
+) -> Result<Json<ApiResponse<ConnectionItem>>, AppError> {
+    let service = ConnectionService::new(state.pool_manager);
+    let data = service.update(&id, req.into_update()?).await?;
+    state.schema_cache.invalidate(&id).await;
+    Ok(Json(ApiResponse::ok_with_service(data, "connection-service")))
+}
+
+/// 将连接导出为连接 URL（DSN），密码已脱敏
+#[utoipa::path(
+    get,
+    path = "/api/connections/{id}/dsn",
+    tag = "connections",
+    params(
+        ("id" = String, Path, description = "连接 ID")
+    ),
+    responses(
+        (status = 200, description = "连接 URL", body = ApiResponse<ConnectionDsn>),
+        (status = 400, description = "数据库类型没有 URL 形式"),
+        (status = 404, description = "连接未找到")
+    )
+)]
+pub async fn export_dsn(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<ApiResponse<ConnectionDsn>>, AppError> {
+    let service = ConnectionService::new(state.pool_manager);
+    let data = service.dsn(&id).await?;
+    Ok(Json(ApiResponse::ok_with_service(data, "connection-service")))
+}
+
 /// 根据 ID 删除数据库连接
 #[utoipa::path(
     delete,
@@ -91,6 +208,8 @@ pub async fn delete_connection(
 ) -> Result<Json<ApiResponse<bool>>, AppError> {
     let service = ConnectionService::new(state.pool_manager);
     service.delete(&id).await?;
+    state.schema_cache.invalidate(&id).await;
+    state.snapshots.remove_connection(&id).await?;
     Ok(Json(ApiResponse::ok_with_service(true, "connection-service")))
 }
 
@@ -112,13 +231,16 @@ pub async fn test_connection(
     Path(id): Path<String>,
 ) -> Result<Json<ApiResponse<ConnectionTestResult>>, AppError> {
     let service = ConnectionService::new(state.pool_manager);
-    match service.test(&id).await {
+    let result = service.test(&id).await;
+    let tunnel = service.tunnel_status(&id).await;
+    match result {
         Ok(latency_ms) => Ok(Json(ApiResponse::ok_with_service(
             ConnectionTestResult {
                 id,
                 success: true,
                 latency_ms: Some(latency_ms),
                 error: None,
+                tunnel,
             },
             "connection-service",
         ))),
@@ -128,12 +250,191 @@ pub async fn test_connection(
                 success: false,
                 latency_ms: None,
                 error: Some(e.to_string()),
+                tunnel,
             },
             "connection-service",
         ))),
     }
 }
 
+/// 试连未保存的连接配置，返回逐步诊断结果
+///
+/// 使用临时连接池，结束后立即关闭，不保存连接。连接失败时仍返回 200，
+/// 由 `success` 和各步骤说明失败原因。
+#[utoipa::path(
+    post,
+    path = "/api/connections/test",
+    tag = "connections",
+    request_body = CreateConnectionRequest,
+    responses(
+        (status = 200, description = "诊断结果", body = ApiResponse<ConnectionDiagnostics>),
+        (status = 422, description = "连接配置无效")
+    )
+)]
+pub async fn dry_run_connection(
+    State(state): State<AppState>,
+    Json(req): Json<CreateConnectionRequest>,
+) -> Result<Json<ApiResponse<ConnectionDiagnostics>>, AppError> {
+    let service = DiagnosticsService::new(state.pool_manager);
+    let data = service.run(req).await?;
+    Ok(Json(ApiResponse::ok_with_service(data, "connection-service")))
+}
+
+/// 导出连接包
+///
+/// 设置口令时连接包包含加密的密钥，需要管理员令牌并记录审计日志；否则省略密钥。
+#[utoipa::path(
+    post,
+    path = "/api/connections/export",
+    tag = "connections",
+    params(
+        ("X-Admin-Token" = Option<String>, Header, description = "管理员令牌，导出密钥时必需")
+    ),
+    request_body = ExportBundleRequest,
+    responses(
+        (status = 200, description = "连接包", body = ApiResponse<ConnectionBundle>),
+        (status = 401, description = "导出密钥时缺少管理员令牌"),
+        (status = 403, description = "管理员令牌无效或管理操作未启用"),
+        (status = 404, description = "连接未找到")
+    )
+)]
+pub async fn export_connections(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    request_id: Option<Extension<RequestId>>,
+    req: Option<Json<ExportBundleRequest>>,
+) -> Result<Json<ApiResponse<ConnectionBundle>>, AppError> {
+    let req = req.map(|Json(req)| req).unwrap_or_default();
+    if req.passphrase.is_some() {
+        authorize_admin(&headers, state.config.admin_token.as_deref())?;
+    }
+    let request_id = request_id.map(|Extension(r)| r.0);
+    let service = BundleService::new(state.pool_manager, state.audit);
+    let data = service.export(req, request_id).await?;
+    Ok(Json(ApiResponse::ok_with_service(data, "connection-service")))
+}
+
+/// 导入连接包或 DBeaver data-sources.json
+///
+/// 逐条校验并按冲突策略处理已存在的 ID，返回逐条报告；导入的连接在首次使用时才建立连接。
+/// 导入可覆盖已保存连接的地址和密钥，需要管理员令牌并记录审计日志。
+#[utoipa::path(
+    post,
+    path = "/api/connections/import",
+    tag = "connections",
+    params(
+        ("X-Admin-Token" = String, Header, description = "管理员令牌")
+    ),
+    request_body = ImportBundleRequest,
+    responses(
+        (status = 200, description = "逐条导入报告", body = ApiResponse<ImportReport>),
+        (status = 400, description = "数据格式错误、版本不支持或口令错误"),
+        (status = 401, description = "缺少管理员令牌"),
+        (status = 403, description = "管理员令牌无效或管理操作未启用")
+    )
+)]
+pub async fn import_connections(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    request_id: Option<Extension<RequestId>>,
+    Json(req): Json<ImportBundleRequest>,
+) -> Result<Json<ApiResponse<ImportReport>>, AppError> {
+    authorize_admin(&headers, state.config.admin_token.as_deref())?;
+    let request_id = request_id.map(|Extension(r)| r.0);
+    let service = BundleService::new(state.pool_manager, state.audit);
+    let data = service.import(req, request_id).await?;
+    Ok(Json(ApiResponse::ok_with_service(data, "connection-service")))
+}
+
+/// 获取连接池统计
+///
+/// 包括连接数、空闲与使用中的连接、获取连接的等待时间、最近一次探测和错误。
+#[utoipa::path(
+    get,
+    path = "/api/connections/{id}/pool",
+    tag = "connections",
+    params(
+        ("id" = String, Path, description = "连接 ID"),
+        PoolStatsQuery
+    ),
+    responses(
+        (status = 200, description = "连接池统计", body = ApiResponse<PoolStats>),
+        (status = 404, description = "连接未找到")
+    )
+)]
+pub async fn get_pool_stats(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<PoolStatsQuery>,
+) -> Result<Json<ApiResponse<PoolStats>>, AppError> {
+    let service = ConnectionService::new(state.pool_manager);
+    let data = service.pool_stats(&id, query.probe).await?;
+    Ok(Json(ApiResponse::ok_with_service(data, "connection-service")))
+}
+
+/// 获取所有连接的连接池统计
+///
+/// `probe=true` 时只探测已打开的连接池，不会打开空闲连接。
+#[utoipa::path(
+    get,
+    path = "/api/connections/pools",
+    tag = "connections",
+    params(PoolStatsQuery),
+    responses(
+        (status = 200, description = "连接池统计列表", body = ApiResponse<Vec<PoolStats>>)
+    )
+)]
+pub async fn list_pool_stats(
+    State(state): State<AppState>,
+    Query(query): Query<PoolStatsQuery>,
+) -> Result<Json<ApiResponse<Vec<PoolStats>>>, AppError> {
+    let service = ConnectionService::new(state.pool_manager);
+    let data = service.all_pool_stats(query.probe).await;
+    Ok(Json(ApiResponse::ok_with_service(data, "connection-service")))
+}
+
+/// 获取连接的健康历史
+///
+/// 包括当前状态及持续时间、最近的上线/下线变化、连续失败次数、连接池重建次数
+/// 和最近探测的延迟分位数。已打开的连接池由后台健康监控定期探测。
+#[utoipa::path(
+    get,
+    path = "/api/connections/{id}/health",
+    tag = "connections",
+    params(("id" = String, Path, description = "连接 ID")),
+    responses(
+        (status = 200, description = "连接健康历史", body = ApiResponse<ConnectionHealth>),
+        (status = 404, description = "连接未找到")
+    )
+)]
+pub async fn get_connection_health(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<ApiResponse<ConnectionHealth>>, AppError> {
+    let service = ConnectionService::new(state.pool_manager);
+    let data = service.health(&id).await?;
+    Ok(Json(ApiResponse::ok_with_service(data, "connection-service")))
+}
+
+/// 获取所有连接的健康历史
+///
+/// 网关的聚合健康检查使用其中的正常与故障连接数。
+#[utoipa::path(
+    get,
+    path = "/api/connections/health",
+    tag = "connections",
+    responses(
+        (status = 200, description = "所有连接的健康历史", body = ApiResponse<HealthOverview>)
+    )
+)]
+pub async fn list_connection_health(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<HealthOverview>>, AppError> {
+    let service = ConnectionService::new(state.pool_manager);
+    let data = service.health_overview().await;
+    Ok(Json(ApiResponse::ok_with_service(data, "connection-service")))
+}
+
 /// 健康检查端点
 #[utoipa::path(
     get,
@@ -181,6 +482,7 @@ pub async fn get_pool_info(
         host: conn.host,
         port: conn.port,
         database: conn.database,
+        environment: conn.environment,
     })))
 }
 
@@ -192,6 +494,9 @@ pub struct ConnectionTestResult {
     pub latency_ms: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    /// 连接经由的 SSH 隧道状态
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tunnel: Option<TunnelStatus>,
 }
 
 #[derive(Serialize, ToSchema)]
@@ -210,6 +515,737 @@ pub struct PoolInfo {
     pub host: Option<String>,
     pub port: Option<u16>,
     pub database: Option<String>,
+    /// 环境标签；`prod` 连接执行写语句需要确认
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub environment: Option<Environment>,
+}
+
+// ============================================================
+// 元数据接口
+// ============================================================
+
+/// 列出服务器上的数据库
+#[utoipa::path(
+    get,
+    path = "/api/connections/{id}/databases",
+    tag = "metadata",
+    params(
+        ("id" = String, Path, description = "连接 ID")
+    ),
+    responses(
+        (status = 200, description = "数据库列表", body = ApiResponse<Vec<SchemaItem>>),
+        (status = 404, description = "连接未找到")
+    )
+)]
+pub async fn list_databases(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<ApiResponse<Vec<SchemaItem>>>, AppError> {
+    let service = MetadataService::new(state.pool_manager);
+    let data = service.databases(&id).await?;
+    Ok(Json(ApiResponse::ok_with_service(data, "connection-service")))
+}
+
+/// 列出当前数据库中的 schema
+#[utoipa::path(
+    get,
+    path = "/api/connections/{id}/schemas",
+    tag = "metadata",
+    params(
+        ("id" = String, Path, description = "连接 ID")
+    ),
+    responses(
+        (status = 200, description = "Schema 列表", body = ApiResponse<Vec<SchemaItem>>),
+        (status = 404, description = "连接未找到")
+    )
+)]
+pub async fn list_schemas(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<ApiResponse<Vec<SchemaItem>>>, AppError> {
+    let service = MetadataService::new(state.pool_manager);
+    let data = service.schemas(&id).await?;
+    Ok(Json(ApiResponse::ok_with_service(data, "connection-service")))
+}
+
+/// 列出表和视图（含行数估计）
+#[utoipa::path(
+    get,
+    path = "/api/connections/{id}/tables",
+    tag = "metadata",
+    params(
+        ("id" = String, Path, description = "连接 ID"),
+        SchemaQuery
+    ),
+    responses(
+        (status = 200, description = "表列表", body = ApiResponse<Vec<TableItem>>),
+        (status = 404, description = "连接未找到")
+    )
+)]
+pub async fn list_tables(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<SchemaQuery>,
+) -> Result<Json<ApiResponse<Vec<TableItem>>>, AppError> {
+    let service = MetadataService::new(state.pool_manager);
+    let data = service.tables(&id, query.schema.as_deref()).await?;
+    Ok(Json(ApiResponse::ok_with_service(data, "connection-service")))
+}
+
+/// 获取表结构（列、主键、索引、外键）
+#[utoipa::path(
+    get,
+    path = "/api/connections/{id}/tables/{table}",
+    tag = "metadata",
+    params(
+        ("id" = String, Path, description = "连接 ID"),
+        ("table" = String, Path, description = "表名"),
+        SchemaQuery
+    ),
+    responses(
+        (status = 200, description = "表结构", body = ApiResponse<TableDetail>),
+        (status = 404, description = "连接或表未找到")
+    )
+)]
+pub async fn get_table(
+    State(state): State<AppState>,
+    Path((id, table)): Path<(String, String)>,
+    Query(query): Query<SchemaQuery>,
+) -> Result<Json<ApiResponse<TableDetail>>, AppError> {
+    let service = MetadataService::new(state.pool_manager);
+    let data = service.table(&id, query.schema.as_deref(), &table).await?;
+    Ok(Json(ApiResponse::ok_with_service(data, "connection-service")))
+}
+
+/// 获取 schema 中所有表的完整结构
+#[utoipa::path(
+    get,
+    path = "/api/connections/{id}/catalog",
+    tag = "metadata",
+    params(
+        ("id" = String, Path, description = "连接 ID"),
+        SchemaQuery
+    ),
+    responses(
+        (status = 200, description = "Schema 中所有表的结构", body = ApiResponse<Vec<TableDetail>>),
+        (status = 404, description = "连接未找到")
+    )
+)]
+pub async fn get_catalog(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<SchemaQuery>,
+) -> Result<Json<ApiResponse<Vec<TableDetail>>>, AppError> {
+    let service = MetadataService::new(state.pool_manager);
+    let data = service.schema_details(&id, query.schema.as_deref()).await?;
+    Ok(Json(ApiResponse::ok_with_service(data, "connection-service")))
+}
+
+/// 导出表或视图的 DDL，可选转换为其他数据库方言
+#[utoipa::path(
+    get,
+    path = "/api/connections/{id}/tables/{table}/ddl",
+    tag = "metadata",
+    params(
+        ("id" = String, Path, description = "连接 ID"),
+        ("table" = String, Path, description = "表名"),
+        DdlQuery
+    ),
+    responses(
+        (status = 200, description = "CREATE 语句及类型映射报告", body = ApiResponse<TableDdl>),
+        (status = 400, description = "视图无法转换方言或目标类型不支持"),
+        (status = 404, description = "连接或表未找到")
+    )
+)]
+pub async fn get_table_ddl(
+    State(state): State<AppState>,
+    Path((id, table)): Path<(String, String)>,
+    Query(query): Query<DdlQuery>,
+) -> Result<Json<ApiResponse<TableDdl>>, AppError> {
+    let service = DdlService::new(state.pool_manager);
+    let data = service
+        .table_ddl(&id, query.schema.as_deref(), &table, query.target.as_ref())
+        .await?;
+    Ok(Json(ApiResponse::ok_with_service(data, "connection-service")))
+}
+
+/// 比较两个连接的 schema，生成差异和迁移脚本
+#[utoipa::path(
+    post,
+    path = "/api/connections/diff",
+    tag = "metadata",
+    request_body = SchemaDiffRequest,
+    responses(
+        (status = 200, description = "结构差异及使目标与源一致的迁移步骤", body = ApiResponse<SchemaDiff>),
+        (status = 404, description = "连接未找到")
+    )
+)]
+pub async fn diff_schemas(
+    State(state): State<AppState>,
+    Json(req): Json<SchemaDiffRequest>,
+) -> Result<Json<ApiResponse<SchemaDiff>>, AppError> {
+    let service = SchemaDiffService::new(state.pool_manager);
+    let data = service.diff(req).await?;
+    Ok(Json(ApiResponse::ok_with_service(data, "connection-service")))
+}
+
+/// 导出 schema 的 ER 图
+#[utoipa::path(
+    get,
+    path = "/api/connections/{id}/er-diagram",
+    tag = "metadata",
+    params(
+        ("id" = String, Path, description = "连接 ID"),
+        ErDiagramQuery
+    ),
+    responses(
+        (status = 200, description = "Mermaid / DOT 文本及节点和边", body = ApiResponse<ErDiagram>),
+        (status = 404, description = "连接或焦点表未找到")
+    )
+)]
+pub async fn get_er_diagram(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<ErDiagramQuery>,
+) -> Result<Json<ApiResponse<ErDiagram>>, AppError> {
+    let service = ErDiagramService::new(state.pool_manager);
+    let data = service.render(&id, &query).await?;
+    Ok(Json(ApiResponse::ok_with_service(data, "connection-service")))
+}
+
+// ============================================================
+// 表数据接口
+// ============================================================
+
+/// 分页浏览表数据，支持过滤、排序和列投影
+#[utoipa::path(
+    get,
+    path = "/api/connections/{id}/tables/{table}/rows",
+    tag = "data",
+    params(
+        ("id" = String, Path, description = "连接 ID"),
+        ("table" = String, Path, description = "表名"),
+        RowsQuery
+    ),
+    responses(
+        (status = 200, description = "当前页的行（列名到值的对象）及分页信息", body = ApiResponse<PaginatedData<serde_json::Value>>),
+        (status = 400, description = "列不存在或过滤条件无效"),
+        (status = 404, description = "连接或表未找到")
+    )
+)]
+pub async fn get_table_rows(
+    State(state): State<AppState>,
+    Path((id, table)): Path<(String, String)>,
+    Query(query): Query<RowsQuery>,
+) -> Result<Json<ApiResponse<PaginatedData<serde_json::Value>>>, AppError> {
+    let service = TableDataService::new(state.pool_manager);
+    let data = service.rows(&id, &table, &query).await?;
+    Ok(Json(ApiResponse::ok_with_service(data, "connection-service")))
+}
+
+/// 按主键插入、更新或删除行（同一事务，乐观并发）
+#[utoipa::path(
+    post,
+    path = "/api/connections/{id}/tables/{table}/rows",
+    tag = "data",
+    params(
+        ("id" = String, Path, description = "连接 ID"),
+        ("table" = String, Path, description = "表名")
+    ),
+    request_body = RowChangesRequest,
+    responses(
+        (status = 200, description = "修改已提交", body = ApiResponse<RowChangesResult>),
+        (status = 400, description = "表没有主键、主键不完整或列不存在"),
+        (status = 403, description = "只读连接"),
+        (status = 404, description = "连接或表未找到"),
+        (status = 409, description = "目标行不存在或已被修改，全部修改已回滚")
+    )
+)]
+pub async fn apply_row_changes(
+    State(state): State<AppState>,
+    Path((id, table)): Path<(String, String)>,
+    Json(req): Json<RowChangesRequest>,
+) -> Result<Json<ApiResponse<RowChangesResult>>, AppError> {
+    let service = TableDataService::new(state.pool_manager);
+    let data = service.apply_changes(&id, &table, req).await?;
+    Ok(Json(ApiResponse::ok_with_service(data, "connection-service")))
+}
+
+/// 统计表中各列的数据特征，大表在后台运行
+#[utoipa::path(
+    post,
+    path = "/api/connections/{id}/tables/{table}/profile",
+    tag = "data",
+    params(
+        ("id" = String, Path, description = "连接 ID"),
+        ("table" = String, Path, description = "表名")
+    ),
+    request_body = ProfileRequest,
+    responses(
+        (status = 200, description = "已完成的画像，或运行中的后台任务", body = ApiResponse<ProfileJob>),
+        (status = 400, description = "列不存在"),
+        (status = 404, description = "连接或表未找到")
+    )
+)]
+pub async fn profile_table(
+    State(state): State<AppState>,
+    Path((id, table)): Path<(String, String)>,
+    req: Option<Json<ProfileRequest>>,
+) -> Result<Json<ApiResponse<ProfileJob>>, AppError> {
+    let req = req.map(|Json(req)| req).unwrap_or_default();
+    let service = ProfileService::new(state.pool_manager, state.profile_jobs);
+    let data = service.start(&id, &table, req).await?;
+    Ok(Json(ApiResponse::ok_with_service(data, "connection-service")))
+}
+
+/// 查询列画像任务的状态和结果
+#[utoipa::path(
+    get,
+    path = "/api/connections/{id}/profile-jobs/{job_id}",
+    tag = "data",
+    params(
+        ("id" = String, Path, description = "连接 ID"),
+        ("job_id" = String, Path, description = "任务 ID")
+    ),
+    responses(
+        (status = 200, description = "画像任务", body = ApiResponse<ProfileJob>),
+        (status = 404, description = "任务未找到")
+    )
+)]
+pub async fn get_profile_job(
+    State(state): State<AppState>,
+    Path((id, job_id)): Path<(String, String)>,
+) -> Result<Json<ApiResponse<ProfileJob>>, AppError> {
+    let service = ProfileService::new(state.pool_manager, state.profile_jobs);
+    let data = service.job(&id, &job_id).await?;
+    Ok(Json(ApiResponse::ok_with_service(data, "connection-service")))
+}
+
+// ============================================================
+// SQL 补全接口
+// ============================================================
+
+/// 根据光标位置给出 SQL 补全候选项
+#[utoipa::path(
+    post,
+    path = "/api/sql/complete",
+    tag = "sql",
+    request_body = CompletionRequest,
+    responses(
+        (status = 200, description = "排序后的补全候选项", body = ApiResponse<CompletionResult>),
+        (status = 404, description = "连接未找到"),
+        (status = 422, description = "请求参数无效")
+    )
+)]
+pub async fn complete_sql(
+    State(state): State<AppState>,
+    Json(req): Json<CompletionRequest>,
+) -> Result<Json<ApiResponse<CompletionResult>>, AppError> {
+    let service = CompletionService::new(state.pool_manager, state.schema_cache);
+    let data = service.complete(req).await?;
+    Ok(Json(ApiResponse::ok_with_service(data, "connection-service")))
+}
+
+/// 重新加载连接的 schema 缓存
+#[utoipa::path(
+    post,
+    path = "/api/connections/{id}/schema-cache/refresh",
+    tag = "sql",
+    params(
+        ("id" = String, Path, description = "连接 ID"),
+        SchemaCacheQuery
+    ),
+    responses(
+        (status = 200, description = "重新加载后的缓存信息", body = ApiResponse<SchemaCacheInfo>),
+        (status = 404, description = "连接未找到")
+    )
+)]
+pub async fn refresh_schema_cache(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<SchemaCacheQuery>,
+) -> Result<Json<ApiResponse<SchemaCacheInfo>>, AppError> {
+    let data = state
+        .schema_cache
+        .refresh(&state.pool_manager, &id, query.schema.as_deref())
+        .await?;
+    Ok(Json(ApiResponse::ok_with_service(data, "connection-service")))
+}
+
+// ============================================================
+// 服务器监控接口
+// ============================================================
+
+/// 列出服务器上的会话及正在执行的 SQL
+#[utoipa::path(
+    get,
+    path = "/api/connections/{id}/activity",
+    tag = "monitoring",
+    params(
+        ("id" = String, Path, description = "连接 ID"),
+        ActivityQuery
+    ),
+    responses(
+        (status = 200, description = "会话列表，按持续时间从长到短", body = ApiResponse<Vec<SessionActivity>>),
+        (status = 400, description = "数据库类型不支持"),
+        (status = 404, description = "连接未找到")
+    )
+)]
+pub async fn get_activity(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<ActivityQuery>,
+) -> Result<Json<ApiResponse<Vec<SessionActivity>>>, AppError> {
+    let service = ActivityService::new(state.pool_manager, state.audit);
+    let data = service.sessions(&id, &query).await?;
+    Ok(Json(ApiResponse::ok_with_service(data, "connection-service")))
+}
+
+/// 取消会话的查询或终止会话（需要管理员令牌，记录审计日志）
+#[utoipa::path(
+    post,
+    path = "/api/connections/{id}/activity/{pid}/kill",
+    tag = "monitoring",
+    params(
+        ("id" = String, Path, description = "连接 ID"),
+        ("pid" = i64, Path, description = "会话进程 ID"),
+        ("X-Admin-Token" = String, Header, description = "管理员令牌")
+    ),
+    request_body = KillSessionRequest,
+    responses(
+        (status = 200, description = "会话已终止", body = ApiResponse<KillSessionResult>),
+        (status = 401, description = "缺少管理员令牌"),
+        (status = 403, description = "管理员令牌无效或管理操作未启用"),
+        (status = 404, description = "连接或会话未找到")
+    )
+)]
+pub async fn kill_session(
+    State(state): State<AppState>,
+    Path((id, pid)): Path<(String, i64)>,
+    headers: HeaderMap,
+    request_id: Option<Extension<RequestId>>,
+    req: Option<Json<KillSessionRequest>>,
+) -> Result<Json<ApiResponse<KillSessionResult>>, AppError> {
+    authorize_admin(&headers, state.config.admin_token.as_deref())?;
+    let req = req.map(|Json(req)| req).unwrap_or_default();
+    let request_id = request_id.map(|Extension(r)| r.0);
+    let service = ActivityService::new(state.pool_manager, state.audit);
+    let data = service.kill(&id, pid, req, request_id).await?;
+    Ok(Json(ApiResponse::ok_with_service(data, "connection-service")))
+}
+
+/// 获取当前锁和阻塞者 → 等待者树
+#[utoipa::path(
+    get,
+    path = "/api/connections/{id}/locks",
+    tag = "monitoring",
+    params(
+        ("id" = String, Path, description = "连接 ID")
+    ),
+    responses(
+        (status = 200, description = "锁列表和阻塞树", body = ApiResponse<LockReport>),
+        (status = 400, description = "数据库类型或版本不支持"),
+        (status = 404, description = "连接未找到")
+    )
+)]
+pub async fn get_locks(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<ApiResponse<LockReport>>, AppError> {
+    let service = LockService::new(state.pool_manager);
+    let data = service.report(&id).await?;
+    Ok(Json(ApiResponse::ok_with_service(data, "connection-service")))
+}
+
+/// 获取 schema 中各表和索引的存储统计
+#[utoipa::path(
+    get,
+    path = "/api/connections/{id}/storage",
+    tag = "monitoring",
+    params(
+        ("id" = String, Path, description = "连接 ID"),
+        StorageQuery
+    ),
+    responses(
+        (status = 200, description = "排序后当前页的表存储统计", body = ApiResponse<PaginatedData<TableStorage>>),
+        (status = 400, description = "排序字段无效"),
+        (status = 404, description = "连接未找到")
+    )
+)]
+pub async fn get_storage_stats(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<StorageQuery>,
+) -> Result<Json<ApiResponse<PaginatedData<TableStorage>>>, AppError> {
+    let service = StorageService::new(state.pool_manager);
+    let data = service.tables(&id, &query).await?;
+    Ok(Json(ApiResponse::ok_with_service(data, "connection-service")))
+}
+
+// ============================================================
+// 审计日志接口
+// ============================================================
+
+/// 查询审计日志（需要管理员令牌）
+#[utoipa::path(
+    get,
+    path = "/api/audit",
+    tag = "audit",
+    params(
+        AuditQuery,
+        ("X-Admin-Token" = String, Header, description = "管理员令牌")
+    ),
+    responses(
+        (status = 200, description = "审计条目，新的在前", body = ApiResponse<Vec<AuditEntry>>),
+        (status = 401, description = "缺少管理员令牌"),
+        (status = 403, description = "管理员令牌无效或管理操作未启用")
+    )
+)]
+pub async fn list_audit_entries(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<AuditQuery>,
+) -> Result<Json<ApiResponse<Vec<AuditEntry>>>, AppError> {
+    authorize_admin(&headers, state.config.admin_token.as_deref())?;
+    let data = state.audit.recent(&query).await?;
+    Ok(Json(ApiResponse::ok_with_service(data, "connection-service")))
+}
+
+// ============================================================
+// 密钥管理接口
+// ============================================================
+
+/// 轮换主密钥并重新加密所有保存的密码（需要管理员令牌，记录审计日志）
+#[utoipa::path(
+    post,
+    path = "/api/secrets/rotate",
+    tag = "secrets",
+    params(
+        ("X-Admin-Token" = String, Header, description = "管理员令牌")
+    ),
+    request_body = RotateKeyRequest,
+    responses(
+        (status = 200, description = "主密钥已轮换", body = ApiResponse<KeyRotationResult>),
+        (status = 401, description = "缺少管理员令牌"),
+        (status = 403, description = "管理员令牌无效或管理操作未启用"),
+        (status = 409, description = "主密钥来自环境变量，无法在线轮换")
+    )
+)]
+pub async fn rotate_master_key(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    request_id: Option<Extension<RequestId>>,
+    req: Option<Json<RotateKeyRequest>>,
+) -> Result<Json<ApiResponse<KeyRotationResult>>, AppError> {
+    authorize_admin(&headers, state.config.admin_token.as_deref())?;
+    let req = req.map(|Json(req)| req).unwrap_or_default();
+    let request_id = request_id.map(|Extension(r)| r.0);
+    let service = SecretService::new(state.keyring, state.pool_manager, state.audit);
+    let data = service.rotate(req, request_id).await?;
+    Ok(Json(ApiResponse::ok_with_service(data, "connection-service")))
+}
+
+// ============================================================
+// 元数据搜索接口
+// ============================================================
+
+/// 跨连接搜索表、视图、列和索引
+#[utoipa::path(
+    get,
+    path = "/api/search",
+    tag = "search",
+    params(SearchQuery),
+    responses(
+        (status = 200, description = "按相关度排序的命中结果", body = ApiResponse<SearchResult>),
+        (status = 400, description = "对象类型无效"),
+        (status = 422, description = "请求参数无效")
+    )
+)]
+pub async fn search_metadata(
+    State(state): State<AppState>,
+    Query(query): Query<SearchQuery>,
+) -> Result<Json<ApiResponse<SearchResult>>, AppError> {
+    let data = state.search_index.search(&query).await?;
+    Ok(Json(ApiResponse::ok_with_service(data, "connection-service")))
+}
+
+/// 立即重建所有连接的搜索索引
+#[utoipa::path(
+    post,
+    path = "/api/search/refresh",
+    tag = "search",
+    responses(
+        (status = 200, description = "重建后的索引状态", body = ApiResponse<SearchIndexStatus>)
+    )
+)]
+pub async fn refresh_search_index(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<SearchIndexStatus>>, AppError> {
+    let data = state.search_index.refresh_all(&state.pool_manager).await;
+    Ok(Json(ApiResponse::ok_with_service(data, "connection-service")))
+}
+
+// ============================================================
+// Schema 快照接口
+// ============================================================
+
+/// 列出连接的 schema 快照
+#[utoipa::path(
+    get,
+    path = "/api/connections/{id}/snapshots",
+    tag = "snapshots",
+    params(
+        ("id" = String, Path, description = "连接 ID")
+    ),
+    responses(
+        (status = 200, description = "快照列表（版本升序）", body = ApiResponse<Vec<SnapshotSummary>>)
+    )
+)]
+pub async fn list_snapshots(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<ApiResponse<Vec<SnapshotSummary>>>, AppError> {
+    let service = SnapshotService::new(state.pool_manager, state.snapshots);
+    let data = service.list(&id).await?;
+    Ok(Json(ApiResponse::ok_with_service(data, "connection-service")))
+}
+
+/// 立即保存 schema 快照
+#[utoipa::path(
+    post,
+    path = "/api/connections/{id}/snapshots",
+    tag = "snapshots",
+    params(
+        ("id" = String, Path, description = "连接 ID")
+    ),
+    request_body = TakeSnapshotRequest,
+    responses(
+        (status = 200, description = "新快照及与上一版本的差异", body = ApiResponse<SnapshotResult>),
+        (status = 404, description = "连接未找到")
+    )
+)]
+pub async fn take_snapshot(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    req: Option<Json<TakeSnapshotRequest>>,
+) -> Result<Json<ApiResponse<SnapshotResult>>, AppError> {
+    let req = req.map(|Json(req)| req).unwrap_or_default();
+    let service = SnapshotService::new(state.pool_manager, state.snapshots);
+    let data = service.take(&id, req.schema, SnapshotTrigger::Manual).await?;
+    Ok(Json(ApiResponse::ok_with_service(data, "connection-service")))
+}
+
+/// 获取指定版本的完整快照
+#[utoipa::path(
+    get,
+    path = "/api/connections/{id}/snapshots/{version}",
+    tag = "snapshots",
+    params(
+        ("id" = String, Path, description = "连接 ID"),
+        ("version" = u32, Path, description = "快照版本")
+    ),
+    responses(
+        (status = 200, description = "快照内容", body = ApiResponse<SchemaSnapshot>),
+        (status = 404, description = "快照未找到")
+    )
+)]
+pub async fn get_snapshot(
+    State(state): State<AppState>,
+    Path((id, version)): Path<(String, u32)>,
+) -> Result<Json<ApiResponse<SchemaSnapshot>>, AppError> {
+    let service = SnapshotService::new(state.pool_manager, state.snapshots);
+    let data = service.get(&id, version).await?;
+    Ok(Json(ApiResponse::ok_with_service(data, "connection-service")))
+}
+
+/// 获取相邻快照之间的结构变更时间线
+#[utoipa::path(
+    get,
+    path = "/api/connections/{id}/snapshots/timeline",
+    tag = "snapshots",
+    params(
+        ("id" = String, Path, description = "连接 ID")
+    ),
+    responses(
+        (status = 200, description = "有变化的相邻快照对", body = ApiResponse<Vec<SnapshotChange>>)
+    )
+)]
+pub async fn get_snapshot_timeline(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<ApiResponse<Vec<SnapshotChange>>>, AppError> {
+    let service = SnapshotService::new(state.pool_manager, state.snapshots);
+    let data = service.timeline(&id).await?;
+    Ok(Json(ApiResponse::ok_with_service(data, "connection-service")))
+}
+
+/// 获取定时快照配置
+#[utoipa::path(
+    get,
+    path = "/api/connections/{id}/snapshots/schedule",
+    tag = "snapshots",
+    params(
+        ("id" = String, Path, description = "连接 ID")
+    ),
+    responses(
+        (status = 200, description = "定时快照配置及上次执行结果", body = ApiResponse<SnapshotSchedule>),
+        (status = 404, description = "未配置定时快照")
+    )
+)]
+pub async fn get_snapshot_schedule(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<ApiResponse<SnapshotSchedule>>, AppError> {
+    let service = SnapshotService::new(state.pool_manager, state.snapshots);
+    let data = service.schedule(&id).await?;
+    Ok(Json(ApiResponse::ok_with_service(data, "connection-service")))
+}
+
+/// 设置定时快照
+#[utoipa::path(
+    put,
+    path = "/api/connections/{id}/snapshots/schedule",
+    tag = "snapshots",
+    params(
+        ("id" = String, Path, description = "连接 ID")
+    ),
+    request_body = SnapshotScheduleRequest,
+    responses(
+        (status = 200, description = "已保存的定时快照配置", body = ApiResponse<SnapshotSchedule>),
+        (status = 400, description = "参数校验失败"),
+        (status = 404, description = "连接未找到")
+    )
+)]
+pub async fn set_snapshot_schedule(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(req): Json<SnapshotScheduleRequest>,
+) -> Result<Json<ApiResponse<SnapshotSchedule>>, AppError> {
+    let service = SnapshotService::new(state.pool_manager, state.snapshots);
+    let data = service.set_schedule(&id, req).await?;
+    Ok(Json(ApiResponse::ok_with_service(data, "connection-service")))
+}
+
+/// 删除定时快照
+#[utoipa::path(
+    delete,
+    path = "/api/connections/{id}/snapshots/schedule",
+    tag = "snapshots",
+    params(
+        ("id" = String, Path, description = "连接 ID")
+    ),
+    responses(
+        (status = 200, description = "定时快照已删除", body = ApiResponse<bool>),
+        (status = 404, description = "未配置定时快照")
+    )
+)]
+pub async fn delete_snapshot_schedule(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<ApiResponse<bool>>, AppError> {
+    let service = SnapshotService::new(state.pool_manager, state.snapshots);
+    service.remove_schedule(&id).await?;
+    Ok(Json(ApiResponse::ok_with_service(true, "connection-service")))
 }
 
 // ============================================================
@@ -217,7 +1253,7 @@ pub struct PoolInfo {
 // ============================================================
 
 use crate::service::{ConnectionServiceTrait, MockConnectionService};
-use common::models::connection::DbType;
+use common::models::connection::{ConnectionStatus, DbType};
 
 /// Trait 演示响应
 #[derive(Serialize, ToSchema)]
@@ -283,7 +1319,19 @@ pub async fn demo_trait_mock() -> Json<ApiResponse<TraitDemoResponse>> {
             username: Some("mock_user".to_string()),
             database: Some("mock_db".to_string()),
             file_path: None,
+            read_only: false,
+            folder: None,
+            tags: vec![],
+            environment: None,
+            color: None,
             created_at: "2026-01-01T00:00:00Z".to_string(),
+            updated_at: None,
+            options: Default::default(),
+            pool: Default::default(),
+            tls: None,
+            ssh_tunnel: None,
+            status: ConnectionStatus::Connected,
+            last_error: None,
         },
         ConnectionItem {
             id: "mock-002".to_string(),
@@ -294,7 +1342,19 @@ pub async fn demo_trait_mock() -> Json<ApiResponse<TraitDemoResponse>> {
             username: Some("mock_admin".to_string()),
             database: Some("mock_postgres".to_string()),
             file_path: None,
+            read_only: false,
+            folder: None,
+            tags: vec![],
+            environment: None,
+            color: None,
             created_at: "2026-01-02T00:00:00Z".to_string(),
+            updated_at: None,
+            options: Default::default(),
+            pool: Default::default(),
+            tls: None,
+            ssh_tunnel: None,
+            status: ConnectionStatus::Connected,
+            last_error: None,
         },
     ];
     
@@ -343,8 +1403,20 @@ pub async fn demo_trait_generic(
             port: None,
             username: None,
             database: None,
+            read_only: false,
+            folder: None,
+            tags: vec![],
+            environment: None,
+            color: None,
             file_path: Some("/tmp/mock.db".to_string()),
             created_at: "2026-01-01T00:00:00Z".to_string(),
+            updated_at: None,
+            options: Default::default(),
+            pool: Default::default(),
+            tls: None,
+            ssh_tunnel: None,
+            status: ConnectionStatus::Connected,
+            last_error: None,
         },
     ]);
     let mock_count = get_connection_count(&mock_service).await;
@@ -0,0 +1,249 @@
+//! 连接健康监控
+//!
+//! 后台任务定期探测每个已打开的连接池（`SELECT 1` / `PING`）：
+//! - 每个连接池单独调度，间隔为 `HEALTH_CHECK_INTERVAL` 加 ±20% 随机抖动，避免同时探测所有数据库
+//! - 探测在后台任务中执行，卡在连接超时或 SSH 握手上的重建不会拖住其他连接池；
+//!   同一连接池上一次探测未结束时不再发起新的探测
+//! - 探测失败时重建连接池；重建失败按指数退避重试，直到探测或重建成功
+//! - 连接池耗尽说明数据库忙而不是不可达，不重建
+//! - SSH 隧道改用了新的本地端口时直接重建，不探测原端口（原端口已被其他进程占用）
+//! - 探测不算作使用，不影响空闲回收；已回收的连接池不再探测
+//!
+//! 探测结果和状态变化记录在连接池管理器的健康历史中。
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use common::errors::AppError;
+use ring::rand::{SecureRandom, SystemRandom};
+use tokio::task::{Id, JoinError, JoinSet};
+use tokio::time::Instant;
+use tracing::warn;
+
+use crate::pool_manager::PoolManager;
+
+/// 检查是否有到期探测的间隔
+const MONITOR_TICK: Duration = Duration::from_secs(1);
+/// 探测间隔的随机抖动比例
+const JITTER: f64 = 0.2;
+/// 重建失败后的首次重试间隔
+const BACKOFF_MIN: Duration = Duration::from_secs(2);
+/// 重建失败后的最长重试间隔
+const BACKOFF_MAX: Duration = Duration::from_secs(5 * 60);
+
+/// 一个连接池的探测计划
+struct Schedule {
+    next: Instant,
+    /// 连续重建失败次数
+    failures: u32,
+}
+
+/// 连接池健康监控
+pub struct HealthMonitor {
+    interval: Duration,
+    schedule: HashMap<String, Schedule>,
+    rng: SystemRandom,
+    /// 进行中的探测，返回连接池是否可用
+    checks: JoinSet<bool>,
+    /// 进行中的探测任务对应的连接 ID
+    in_flight: HashMap<Id, String>,
+}
+
+impl HealthMonitor {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            schedule: HashMap::new(),
+            rng: SystemRandom::new(),
+            checks: JoinSet::new(),
+            in_flight: HashMap::new(),
+        }
+    }
+
+    /// 收集已结束的探测，并在后台探测所有到期且没有探测进行中的连接池，返回新发起的探测数量
+    ///
+    /// 新打开的连接池在一个抖动间隔后首次探测。
+    pub async fn run_due(&mut self, pool_manager: &Arc<PoolManager>) -> usize {
+        while let Some(result) = self.checks.try_join_next_with_id() {
+            self.finish(result);
+        }
+
+        let now = Instant::now();
+        let open: HashSet<String> = pool_manager.open_pool_ids().await.into_iter().collect();
+        self.schedule.retain(|id, _| open.contains(id));
+
+        let mut count = 0;
+        for id in open {
+            let schedule = self.schedule.entry(id.clone()).or_insert_with(|| Schedule {
+                next: now + jittered(&self.rng, self.interval),
+                failures: 0,
+            });
+            if schedule.next > now || self.in_flight.values().any(|running| *running == id) {
+                continue;
+            }
+            let pool_manager = pool_manager.clone();
+            let task_id = {
+                let id = id.clone();
+                self.checks.spawn(async move { check(&pool_manager, &id).await }).id()
+            };
+            self.in_flight.insert(task_id, id);
+            count += 1;
+        }
+        count
+    }
+
+    /// 按探测结果安排下一次探测；任务异常结束时按失败处理
+    fn finish(&mut self, result: Result<(Id, bool), JoinError>) {
+        let (task_id, healthy) = match result {
+            Ok(result) => result,
+            Err(e) => (e.id(), false),
+        };
+        let Some(id) = self.in_flight.remove(&task_id) else { return };
+        let Some(schedule) = self.schedule.get_mut(&id) else { return };
+        schedule.failures = if healthy { 0 } else { schedule.failures + 1 };
+        let delay = match schedule.failures {
+            0 => self.interval,
+            n => backoff(n),
+        };
+        schedule.next = Instant::now() + jittered(&self.rng, delay);
+    }
+}
+
+/// 探测一个连接池，失败时重建，返回连接池是否可用
+async fn check(pool_manager: &PoolManager, id: &str) -> bool {
+    if pool_manager.tunnel_moved(id).await {
+        warn!(connection_id = %id, "SSH 隧道的本地端口已变更，重建连接池");
+        return rebuild(pool_manager, id).await;
+    }
+    let error = match pool_manager.probe_open(id).await {
+        // 连接池已被回收或删除
+        None => return true,
+        Some(Ok(_)) | Some(Err(AppError::PoolExhausted(_))) => return true,
+        Some(Err(e)) => e,
+    };
+    warn!(connection_id = %id, error = %error, "健康探测失败，重建连接池");
+    rebuild(pool_manager, id).await
+}
+
+/// 重建连接池，返回是否成功
+async fn rebuild(pool_manager: &PoolManager, id: &str) -> bool {
+    match pool_manager.rebuild(id).await {
+        Ok(_) => true,
+        Err(e) => {
+            warn!(connection_id = %id, error = %e, "重建连接池失败");
+            false
+        }
+    }
+}
+
+/// 第 `failures` 次重建失败后的重试间隔
+fn backoff(failures: u32) -> Duration {
+    BACKOFF_MIN.saturating_mul(1 << failures.saturating_sub(1).min(16)).min(BACKOFF_MAX)
+}
+
+/// 给间隔加上 ±[`JITTER`] 的随机抖动
+fn jittered(rng: &SystemRandom, interval: Duration) -> Duration {
+    let mut bytes = [0u8; 4];
+    if rng.fill(&mut bytes).is_err() {
+        return interval;
+    }
+    let unit = u32::from_le_bytes(bytes) as f64 / u32::MAX as f64;
+    interval.mul_f64(1.0 + JITTER * (2.0 * unit - 1.0))
+}
+
+/// 启动连接池健康监控任务，间隔为 0 时不启动
+pub fn spawn_monitor(pool_manager: Arc<PoolManager>, interval: Duration) {
+    if interval.is_zero() {
+        return;
+    }
+    tokio::spawn(async move {
+        let mut monitor = HealthMonitor::new(interval);
+        let mut ticker = tokio::time::interval(MONITOR_TICK);
+        loop {
+            ticker.tick().await;
+            monitor.run_due(&pool_manager).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::config::AppConfig;
+    use common::models::connection::{ConnectionConfig, ConnectionStatus, DbType};
+    use common::models::HealthState;
+
+    use crate::test_support;
+
+    /// 发起到期的探测并等待全部结束，返回发起的数量
+    async fn run_to_completion(monitor: &mut HealthMonitor, pool_manager: &Arc<PoolManager>) -> usize {
+        let count = monitor.run_due(pool_manager).await;
+        while let Some(result) = monitor.checks.join_next_with_id().await {
+            monitor.finish(result);
+        }
+        count
+    }
+
+    #[test]
+    fn test_backoff_and_jitter_bounds() {
+        assert_eq!(backoff(1), BACKOFF_MIN);
+        assert_eq!(backoff(3), BACKOFF_MIN * 4);
+        assert_eq!(backoff(40), BACKOFF_MAX);
+
+        let rng = SystemRandom::new();
+        let interval = Duration::from_secs(30);
+        for _ in 0..100 {
+            let delay = jittered(&rng, interval);
+            assert!(delay >= interval.mul_f64(1.0 - JITTER) && delay <= interval.mul_f64(1.0 + JITTER));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_pool_is_rebuilt_with_backoff() {
+        let dir = std::env::temp_dir().join(format!("health-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("a.db");
+        let manager = Arc::new(PoolManager::new(AppConfig { connect_timeout_secs: 1, ..AppConfig::load() }));
+        let lite = ConnectionConfig {
+            file_path: Some(file.to_string_lossy().to_string()),
+            ..test_support::connection("lite", DbType::SQLite)
+        };
+        manager.add_connection(lite).await.unwrap();
+        manager.probe("lite").await.unwrap();
+
+        let mut monitor = HealthMonitor::new(Duration::ZERO);
+        // 首次发现连接池时按抖动间隔排期，间隔为 0 时立即探测
+        assert_eq!(run_to_completion(&mut monitor, &manager).await, 1);
+        assert_eq!(monitor.schedule["lite"].failures, 0);
+
+        // 数据库文件被删除后，探测失败且无法重建
+        manager.get_pool("lite").await.unwrap().close().await;
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(run_to_completion(&mut monitor, &manager).await, 1);
+        assert_eq!(monitor.schedule["lite"].failures, 1);
+        assert!(monitor.schedule["lite"].next > Instant::now() + BACKOFF_MIN.mul_f64(1.0 - JITTER) / 2);
+        let health = manager.health("lite").await.unwrap();
+        assert_eq!(health.state, Some(HealthState::Down));
+        assert!(health.consecutive_failures >= 2);
+        assert_eq!(manager.status("lite").await.0, ConnectionStatus::Degraded);
+
+        // 退避期间不再探测；数据库恢复后重建连接池
+        assert_eq!(run_to_completion(&mut monitor, &manager).await, 0);
+        std::fs::create_dir_all(&dir).unwrap();
+        monitor.schedule.get_mut("lite").unwrap().next = Instant::now();
+        assert_eq!(run_to_completion(&mut monitor, &manager).await, 1);
+        assert_eq!(monitor.schedule["lite"].failures, 0);
+
+        let health = manager.health("lite").await.unwrap();
+        assert_eq!(health.state, Some(HealthState::Up));
+        assert_eq!((health.rebuilds, health.consecutive_failures), (1, 0));
+        let states: Vec<HealthState> = health.transitions.iter().map(|t| t.state).collect();
+        assert_eq!(states, [HealthState::Up, HealthState::Down, HealthState::Up]);
+        assert!(health.transitions[1].error.is_some());
+        assert_eq!(health.latency.samples, 2);
+        assert!(health.latency.p50_ms <= health.latency.p99_ms);
+        assert_eq!(manager.status("lite").await.0, ConnectionStatus::Connected);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
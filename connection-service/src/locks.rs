@@ -0,0 +1,292 @@
+//! 锁等待分析服务
+//!
+//! 读取服务器当前的锁和阻塞关系，构建“阻塞者 → 等待者”树：
+//! - PostgreSQL：`pg_locks` 关联 `pg_stat_activity`，阻塞关系来自 `pg_blocking_pids`
+//! - MySQL 8：`performance_schema.data_locks` / `data_lock_waits`，线程 ID 映射到 PROCESSLIST ID
+//!
+//! 互相阻塞的会话（死锁）以环中最小的 pid 为根，环中每个会话只出现一次。
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::Arc;
+
+use common::errors::{AppError, AppResult};
+use common::models::activity::{BlockingNode, LockItem, LockReport, SessionActivity};
+use sqlx::{MySqlPool, PgPool, Row};
+
+use crate::activity::{mysql_sessions, postgres_sessions};
+use crate::pool_manager::{DatabasePool, PoolManager};
+
+/// 锁等待分析服务
+pub struct LockService {
+    pool_manager: Arc<PoolManager>,
+}
+
+impl LockService {
+    /// 创建新的锁分析服务实例
+    pub fn new(pool_manager: Arc<PoolManager>) -> Self {
+        Self { pool_manager }
+    }
+
+    /// 获取当前锁和阻塞树
+    pub async fn report(&self, id: &str) -> AppResult<LockReport> {
+        let pool = self.pool_manager.pool(id).await?;
+        let (sessions, locks, edges) = match pool {
+            DatabasePool::Postgres(pool) => {
+                let sessions = postgres_sessions(&pool, true).await?;
+                let (locks, edges) = postgres_locks(&pool).await?;
+                (sessions, locks, edges)
+            }
+            DatabasePool::MySQL(pool) => {
+                let (locks, edges) = mysql_locks(&pool).await?;
+                (mysql_sessions(&pool, true).await?, locks, edges)
+            }
+            _ => {
+                return Err(AppError::UnsupportedDatabaseType(
+                    "锁分析仅支持 MySQL 8 和 PostgreSQL 连接".into(),
+                ))
+            }
+        };
+        let current: Vec<i64> = sessions.iter().filter(|s| s.current).map(|s| s.pid).collect();
+        let locks: Vec<LockItem> = locks.into_iter().filter(|l| !current.contains(&l.pid)).collect();
+        Ok(build_report(sessions, locks, &edges))
+    }
+}
+
+/// 读取 PostgreSQL 的锁和 (等待者, 阻塞者) 关系
+async fn postgres_locks(pool: &PgPool) -> AppResult<(Vec<LockItem>, Vec<(i64, i64)>)> {
+    let rows = sqlx::query(
+        "SELECT l.pid::int8 AS pid, l.locktype::text AS locktype, l.mode::text AS mode, l.granted, \
+                CASE WHEN c.relname IS NOT NULL THEN n.nspname || '.' || c.relname END AS relation \
+         FROM pg_locks l \
+         JOIN pg_stat_activity a ON a.pid = l.pid AND a.backend_type = 'client backend' \
+         LEFT JOIN pg_class c ON c.oid = l.relation \
+         LEFT JOIN pg_namespace n ON n.oid = c.relnamespace \
+         WHERE l.locktype <> 'virtualxid' AND l.pid <> pg_backend_pid() \
+         ORDER BY l.granted, l.pid, relation",
+    )
+    .fetch_all(pool)
+    .await?;
+    let locks = rows
+        .iter()
+        .map(|row| {
+            Ok(LockItem {
+                pid: row.try_get("pid")?,
+                lock_type: row.try_get("locktype")?,
+                mode: row.try_get("mode")?,
+                granted: row.try_get("granted")?,
+                relation: row.try_get("relation")?,
+            })
+        })
+        .collect::<AppResult<Vec<_>>>()?;
+
+    let edges = sqlx::query_as::<_, (i64, i64)>(
+        "SELECT a.pid::int8, b.pid::int8 \
+         FROM pg_stat_activity a, unnest(pg_blocking_pids(a.pid)) AS b(pid) \
+         WHERE a.wait_event_type = 'Lock'",
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok((locks, edges))
+}
+
+/// 读取 MySQL 8 的锁和 (等待者, 阻塞者) 关系
+async fn mysql_locks(pool: &MySqlPool) -> AppResult<(Vec<LockItem>, Vec<(i64, i64)>)> {
+    let available: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM information_schema.TABLES \
+         WHERE TABLE_SCHEMA = 'performance_schema' AND TABLE_NAME = 'data_lock_waits'",
+    )
+    .fetch_one(pool)
+    .await?;
+    if available == 0 {
+        return Err(AppError::UnsupportedDatabaseType(
+            "锁分析需要 MySQL 8.0 及以上版本的 performance_schema.data_lock_waits".into(),
+        ));
+    }
+
+    let rows = sqlx::query(
+        "SELECT CAST(t.PROCESSLIST_ID AS SIGNED) AS pid, CAST(l.LOCK_TYPE AS CHAR) AS lock_type, \
+                CAST(l.LOCK_MODE AS CHAR) AS lock_mode, CAST(l.LOCK_STATUS = 'GRANTED' AS SIGNED) AS granted, \
+                CAST(CONCAT(l.OBJECT_SCHEMA, '.', l.OBJECT_NAME) AS CHAR) AS relation \
+         FROM performance_schema.data_locks l \
+         JOIN performance_schema.threads t ON t.THREAD_ID = l.THREAD_ID \
+         WHERE t.PROCESSLIST_ID IS NOT NULL \
+         ORDER BY granted, pid, relation",
+    )
+    .fetch_all(pool)
+    .await?;
+    let locks = rows
+        .iter()
+        .map(|row| {
+            Ok(LockItem {
+                pid: row.try_get("pid")?,
+                lock_type: row.try_get("lock_type")?,
+                mode: row.try_get("lock_mode")?,
+                granted: row.try_get::<i64, _>("granted")? != 0,
+                relation: row.try_get("relation")?,
+            })
+        })
+        .collect::<AppResult<Vec<_>>>()?;
+
+    let edges = sqlx::query_as::<_, (i64, i64)>(
+        "SELECT DISTINCT CAST(rt.PROCESSLIST_ID AS SIGNED), CAST(bt.PROCESSLIST_ID AS SIGNED) \
+         FROM performance_schema.data_lock_waits w \
+         JOIN performance_schema.threads rt ON rt.THREAD_ID = w.REQUESTING_THREAD_ID \
+         JOIN performance_schema.threads bt ON bt.THREAD_ID = w.BLOCKING_THREAD_ID \
+         WHERE rt.PROCESSLIST_ID IS NOT NULL AND bt.PROCESSLIST_ID IS NOT NULL",
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok((locks, edges))
+}
+
+/// 由会话、锁和 (等待者, 阻塞者) 关系构建报告
+fn build_report(sessions: Vec<SessionActivity>, locks: Vec<LockItem>, edges: &[(i64, i64)]) -> LockReport {
+    let sessions: HashMap<i64, SessionActivity> = sessions.into_iter().map(|s| (s.pid, s)).collect();
+    let mut waiters: BTreeMap<i64, BTreeSet<i64>> = BTreeMap::new();
+    let mut blockers: BTreeMap<i64, BTreeSet<i64>> = BTreeMap::new();
+    for &(waiter, blocker) in edges {
+        if waiter != blocker {
+            waiters.entry(blocker).or_default().insert(waiter);
+            blockers.entry(waiter).or_default().insert(blocker);
+        }
+    }
+    let deadlocked: BTreeSet<i64> = blockers.keys().copied().filter(|&pid| in_cycle(pid, &blockers)).collect();
+
+    // 根：阻塞他人但自身不在等待的会话，以及每个无外部根的环中最小的 pid
+    let mut roots: Vec<i64> = waiters.keys().copied().filter(|pid| !blockers.contains_key(pid)).collect();
+    let mut covered = BTreeSet::new();
+    for &root in &roots {
+        reachable(root, &waiters, &mut covered);
+    }
+    for &pid in &deadlocked {
+        if !covered.contains(&pid) {
+            roots.push(pid);
+            reachable(pid, &waiters, &mut covered);
+        }
+    }
+
+    let tree = Tree { sessions: &sessions, locks: &locks, waiters: &waiters, blockers: &blockers, deadlocked: &deadlocked };
+    LockReport {
+        blocking: roots.into_iter().map(|pid| tree.node(pid, &mut Vec::new())).collect(),
+        waiting_sessions: blockers.len(),
+        deadlocked: deadlocked.into_iter().collect(),
+        locks,
+    }
+}
+
+/// 会话是否处于阻塞环中
+fn in_cycle(start: i64, blockers: &BTreeMap<i64, BTreeSet<i64>>) -> bool {
+    let mut stack: Vec<i64> = blockers.get(&start).into_iter().flatten().copied().collect();
+    let mut seen = BTreeSet::new();
+    while let Some(pid) = stack.pop() {
+        if pid == start {
+            return true;
+        }
+        if seen.insert(pid) {
+            stack.extend(blockers.get(&pid).into_iter().flatten());
+        }
+    }
+    false
+}
+
+fn reachable(pid: i64, waiters: &BTreeMap<i64, BTreeSet<i64>>, seen: &mut BTreeSet<i64>) {
+    if seen.insert(pid) {
+        for &waiter in waiters.get(&pid).into_iter().flatten() {
+            reachable(waiter, waiters, seen);
+        }
+    }
+}
+
+struct Tree<'a> {
+    sessions: &'a HashMap<i64, SessionActivity>,
+    locks: &'a [LockItem],
+    waiters: &'a BTreeMap<i64, BTreeSet<i64>>,
+    blockers: &'a BTreeMap<i64, BTreeSet<i64>>,
+    deadlocked: &'a BTreeSet<i64>,
+}
+
+impl Tree<'_> {
+    fn node(&self, pid: i64, path: &mut Vec<i64>) -> BlockingNode {
+        let session = self.sessions.get(&pid);
+        let waiting = self.blockers.contains_key(&pid);
+        let waiting_for = self
+            .locks
+            .iter()
+            .find(|l| l.pid == pid && !l.granted)
+            .map(|l| match &l.relation {
+                Some(relation) => format!("{} on {}", l.mode, relation),
+                None => format!("{} on {}", l.mode, l.lock_type),
+            });
+        path.push(pid);
+        let mut blocked = Vec::new();
+        for &waiter in self.waiters.get(&pid).into_iter().flatten() {
+            if !path.contains(&waiter) {
+                blocked.push(self.node(waiter, path));
+            }
+        }
+        path.pop();
+        BlockingNode {
+            pid,
+            user: session.and_then(|s| s.user.clone()),
+            state: session.and_then(|s| s.state.clone()),
+            query: session.and_then(|s| s.query.clone()),
+            duration_ms: session.and_then(|s| s.duration_ms),
+            wait_ms: session.filter(|_| waiting).and_then(|s| s.duration_ms),
+            waiting_for,
+            deadlocked: self.deadlocked.contains(&pid),
+            blocked,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(pid: i64) -> SessionActivity {
+        SessionActivity {
+            pid,
+            user: Some("app".into()),
+            database: None,
+            client: None,
+            application: None,
+            state: Some("active".into()),
+            query: Some(format!("UPDATE t SET v = {}", pid)),
+            started_at: None,
+            duration_ms: Some(pid * 1000),
+            wait_event: None,
+            current: false,
+        }
+    }
+
+    fn pids(nodes: &[BlockingNode]) -> Vec<(i64, Vec<i64>)> {
+        nodes.iter().map(|n| (n.pid, n.blocked.iter().map(|b| b.pid).collect())).collect()
+    }
+
+    #[test]
+    fn test_chains_and_deadlocks() {
+        let sessions = (1..=6).map(session).collect();
+        let locks = vec![LockItem {
+            pid: 2,
+            lock_type: "relation".into(),
+            mode: "RowExclusiveLock".into(),
+            granted: false,
+            relation: Some("public.orders".into()),
+        }];
+        // 1 阻塞 2 和 3，2 阻塞 4；5 和 6 互相阻塞
+        let edges = [(2, 1), (3, 1), (4, 2), (5, 6), (6, 5)];
+        let report = build_report(sessions, locks, &edges);
+
+        assert_eq!(pids(&report.blocking), vec![(1, vec![2, 3]), (5, vec![6])]);
+        let root = &report.blocking[0];
+        assert_eq!(root.wait_ms, None);
+        assert_eq!(root.blocked[0].wait_ms, Some(2000));
+        assert_eq!(root.blocked[0].waiting_for.as_deref(), Some("RowExclusiveLock on public.orders"));
+        assert_eq!(pids(&root.blocked[0].blocked), vec![(4, vec![])]);
+
+        // 环中的会话只出现一次
+        assert!(report.blocking[1].blocked[0].blocked.is_empty());
+        assert_eq!(report.deadlocked, vec![5, 6]);
+        assert_eq!(report.waiting_sessions, 5);
+    }
+}
@@ -5,11 +5,39 @@
 //! - 连接池管理
 //! - 连接测试
 
+mod activity;
+mod audit;
+mod bundle;
+mod connection_store;
 mod pool_manager;
 mod routes;
 mod service;
 mod state;
 mod handlers;
+mod health_monitor;
+mod locks;
+mod completion;
+mod metadata;
+mod profile;
+mod schema_cache;
+mod dialect;
+mod diagnostics;
+mod ddl;
+mod er_diagram;
+mod schema_diff;
+mod search_index;
+mod secrets;
+mod snapshot;
+mod ssh_tunnel;
+mod sql_builder;
+mod storage;
+mod table_data;
+#[cfg(test)]
+mod test_support;
+mod tls;
+mod tunnel;
+
+use std::time::Duration;
 
 use axum::{middleware, routing::get, Json, Router};
 use common::config::AppConfig;
@@ -36,10 +64,49 @@ const DEFAULT_PORT: u16 = 8081;
         handlers::list_connections,
         handlers::create_connection,
         handlers::get_connection,
+        handlers::update_connection,
+        handlers::replace_connection,
+        handlers::export_dsn,
         handlers::delete_connection,
         handlers::test_connection,
+        handlers::dry_run_connection,
+        handlers::export_connections,
+        handlers::import_connections,
+        handlers::get_pool_stats,
+        handlers::list_pool_stats,
+        handlers::get_connection_health,
+        handlers::list_connection_health,
         handlers::health_check,
         handlers::get_pool_info,
+        handlers::list_databases,
+        handlers::list_schemas,
+        handlers::list_tables,
+        handlers::get_table,
+        handlers::get_catalog,
+        handlers::get_table_ddl,
+        handlers::diff_schemas,
+        handlers::get_er_diagram,
+        handlers::get_table_rows,
+        handlers::apply_row_changes,
+        handlers::profile_table,
+        handlers::get_profile_job,
+        handlers::complete_sql,
+        handlers::refresh_schema_cache,
+        handlers::get_activity,
+        handlers::kill_session,
+        handlers::get_locks,
+        handlers::get_storage_stats,
+        handlers::list_audit_entries,
+        handlers::rotate_master_key,
+        handlers::search_metadata,
+        handlers::refresh_search_index,
+        handlers::list_snapshots,
+        handlers::take_snapshot,
+        handlers::get_snapshot,
+        handlers::get_snapshot_timeline,
+        handlers::get_snapshot_schedule,
+        handlers::set_snapshot_schedule,
+        handlers::delete_snapshot_schedule,
         // Trait 演示接口
         handlers::demo_trait_real,
         handlers::demo_trait_mock,
@@ -48,8 +115,106 @@ const DEFAULT_PORT: u16 = 8081;
     components(schemas(
         common::models::ConnectionConfig,
         common::models::ConnectionItem,
+        common::models::ConnectionStatus,
+        common::models::Environment,
+        common::models::ExportBundleRequest,
+        common::models::ConnectionBundle,
+        common::models::BundleEncryption,
+        common::models::ImportBundleRequest,
+        common::models::ImportFormat,
+        common::models::ConflictMode,
+        common::models::ImportReport,
+        common::models::ImportEntryResult,
+        common::models::ImportOutcome,
         common::models::CreateConnectionRequest,
+        common::models::ConnectionDiagnostics,
+        common::models::DiagnosticStage,
+        common::models::DiagnosticStep,
+        common::models::StepStatus,
+        common::models::PoolStats,
+        common::models::AcquireStats,
+        common::models::TunnelStatus,
+        common::models::TunnelState,
+        common::models::HealthState,
+        common::models::HealthTransition,
+        common::models::LatencyStats,
+        common::models::ConnectionHealth,
+        common::models::HealthOverview,
+        common::models::UpdateConnectionRequest,
+        common::models::ConnectionDsn,
         common::models::DbType,
+        common::models::SchemaItem,
+        common::models::TableKind,
+        common::models::TableItem,
+        common::models::ColumnItem,
+        common::models::PrimaryKeyItem,
+        common::models::IndexItem,
+        common::models::ForeignKeyItem,
+        common::models::TableDetail,
+        common::models::TableDdl,
+        common::models::TypeMapping,
+        common::models::SchemaDiffRequest,
+        common::models::SchemaDiff,
+        common::models::ChangeKind,
+        common::models::TableDiff,
+        common::models::ColumnDiff,
+        common::models::PrimaryKeyDiff,
+        common::models::IndexDiff,
+        common::models::ForeignKeyDiff,
+        common::models::MigrationStep,
+        common::models::ErFormat,
+        common::models::ErColumn,
+        common::models::ErNode,
+        common::models::ErEdge,
+        common::models::ErDiagram,
+        common::models::SnapshotTrigger,
+        common::models::SchemaSnapshot,
+        common::models::SnapshotSummary,
+        common::models::TakeSnapshotRequest,
+        common::models::SnapshotResult,
+        common::models::SnapshotScheduleRequest,
+        common::models::SnapshotSchedule,
+        common::models::SnapshotChange,
+        common::models::FilterOperator,
+        common::models::RowFilter,
+        common::models::CountMode,
+        common::models::RowChangeKind,
+        common::models::RowChange,
+        common::models::RowChangesRequest,
+        common::models::RowChangeResult,
+        common::models::RowChangesResult,
+        common::models::DistinctMode,
+        common::models::ProfileRequest,
+        common::models::ProfileColumnKind,
+        common::models::ValueFrequency,
+        common::models::HistogramBucket,
+        common::models::ColumnProfile,
+        common::models::TableProfile,
+        common::models::ProfileJobStatus,
+        common::models::ProfileJob,
+        common::models::CompletionRequest,
+        common::models::CompletionKind,
+        common::models::CompletionItem,
+        common::models::CompletionResult,
+        common::models::SchemaCacheInfo,
+        common::models::SessionActivity,
+        common::models::KillMode,
+        common::models::KillSessionRequest,
+        common::models::KillSessionResult,
+        common::models::LockItem,
+        common::models::BlockingNode,
+        common::models::LockReport,
+        common::models::IndexStorage,
+        common::models::TableStorage,
+        common::models::AuditOutcome,
+        common::models::AuditEntry,
+        common::models::RotateKeyRequest,
+        common::models::KeyRotationResult,
+        common::models::SearchObjectType,
+        common::models::SearchHit,
+        common::models::SearchResult,
+        common::models::IndexedConnection,
+        common::models::SearchIndexStatus,
         handlers::ConnectionTestResult,
         handlers::HealthResponse,
         handlers::PoolInfo,
@@ -57,6 +222,14 @@ const DEFAULT_PORT: u16 = 8081;
     )),
     tags(
         (name = "connections", description = "连接管理端点"),
+        (name = "metadata", description = "元数据端点"),
+        (name = "data", description = "表数据端点"),
+        (name = "sql", description = "SQL 编辑辅助端点"),
+        (name = "search", description = "元数据搜索端点"),
+        (name = "monitoring", description = "服务器监控端点"),
+        (name = "audit", description = "审计日志端点"),
+        (name = "secrets", description = "密钥管理端点"),
+        (name = "snapshots", description = "Schema 快照端点"),
         (name = "health", description = "健康检查端点"),
         (name = "demo", description = "Trait 演示端点")
     )
@@ -82,7 +255,28 @@ async fn main() {
         .unwrap_or(DEFAULT_PORT);
 
     // 创建应用状态
-    let state = AppState::new(config.clone());
+    let keyring = secrets::Keyring::load(&config).await.expect("加载主密钥失败");
+    let state = AppState::new(config.clone(), keyring);
+
+    // 恢复已保存的连接，连接池在首次使用时打开
+    // 密码无法用已配置的密钥解密时拒绝启动
+    let restored = state.pool_manager.restore().await.expect("加载保存的连接失败");
+    info!(count = restored, "已加载保存的连接");
+
+    // 启动空闲连接池回收任务
+    pool_manager::spawn_evictor(state.pool_manager.clone());
+
+    // 启动连接池健康监控任务
+    health_monitor::spawn_monitor(
+        state.pool_manager.clone(),
+        Duration::from_secs(config.health_check_interval_secs),
+    );
+
+    // 启动定时快照调度器
+    snapshot::spawn_scheduler(state.pool_manager.clone(), state.snapshots.clone());
+
+    // 启动元数据搜索索引任务
+    search_index::spawn_indexer(state.pool_manager.clone(), state.search_index.clone());
 
     // 创建路由
     let app = create_router(state);
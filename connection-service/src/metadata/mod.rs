@@ -0,0 +1,281 @@
+//! 元数据读取服务
+//!
+//! 按连接读取数据库目录信息（数据库/schema、表、列、索引、主键、外键），
+//! 统一转换为 `common::models::metadata` 中与数据库无关的模型：
+//! - MySQL / PostgreSQL：读取 `information_schema` 及系统目录
+//! - SQLite：读取 `sqlite_master` 及 `PRAGMA`
+
+mod mysql;
+mod postgres;
+mod sqlite;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common::errors::{AppError, AppResult};
+use common::models::metadata::{
+    ColumnItem, ForeignKeyItem, IndexItem, PrimaryKeyItem, SchemaItem, TableDetail, TableItem,
+};
+
+use crate::pool_manager::{DatabasePool, PoolManager};
+
+/// 按表名归属的目录行（表名, 条目）
+type TableRows<T> = Vec<(String, T)>;
+
+/// 元数据服务
+pub struct MetadataService {
+    pool_manager: Arc<PoolManager>,
+}
+
+impl MetadataService {
+    /// 创建新的元数据服务实例
+    pub fn new(pool_manager: Arc<PoolManager>) -> Self {
+        Self { pool_manager }
+    }
+
+    /// 列出服务器上的数据库
+    pub async fn databases(&self, id: &str) -> AppResult<Vec<SchemaItem>> {
+        match self.pool_manager.pool(id).await? {
+            DatabasePool::MySQL(pool) => mysql::schemas(&pool).await,
+            DatabasePool::Postgres(pool) => postgres::databases(&pool).await,
+            DatabasePool::SQLite(pool) => sqlite::schemas(&pool).await,
+            _ => Err(unsupported()),
+        }
+    }
+
+    /// 列出当前数据库中的 schema（MySQL 中与数据库相同）
+    pub async fn schemas(&self, id: &str) -> AppResult<Vec<SchemaItem>> {
+        match self.pool_manager.pool(id).await? {
+            DatabasePool::MySQL(pool) => mysql::schemas(&pool).await,
+            DatabasePool::Postgres(pool) => postgres::schemas(&pool).await,
+            DatabasePool::SQLite(pool) => sqlite::schemas(&pool).await,
+            _ => Err(unsupported()),
+        }
+    }
+
+    /// 获取连接当前（默认）schema 的名称
+    pub async fn current_schema(&self, id: &str) -> AppResult<String> {
+        self.schemas(id)
+            .await?
+            .into_iter()
+            .find(|s| s.is_current)
+            .map(|s| s.name)
+            .ok_or_else(|| AppError::InvalidInput(format!("连接 {} 没有默认 schema，请显式指定", id)))
+    }
+
+    /// 列出 schema 中的表和视图
+    pub async fn tables(&self, id: &str, schema: Option<&str>) -> AppResult<Vec<TableItem>> {
+        let pool = self.pool_manager.pool(id).await?;
+        list_tables(&pool, schema, None).await
+    }
+
+    /// 获取单个表的完整结构
+    pub async fn table(&self, id: &str, schema: Option<&str>, table: &str) -> AppResult<TableDetail> {
+        let pool = self.pool_manager.pool(id).await?;
+        describe(&pool, schema, Some(table))
+            .await?
+            .pop()
+            .ok_or_else(|| AppError::NotFound(format!("table {}", table)))
+    }
+
+    /// 获取 schema 中所有表的完整结构
+    pub async fn schema_details(&self, id: &str, schema: Option<&str>) -> AppResult<Vec<TableDetail>> {
+        let pool = self.pool_manager.pool(id).await?;
+        describe(&pool, schema, None).await
+    }
+}
+
+fn unsupported() -> AppError {
+    AppError::UnsupportedDatabaseType("元数据仅支持 MySQL、PostgreSQL 和 SQLite 连接".into())
+}
+
+async fn list_tables(
+    pool: &DatabasePool,
+    schema: Option<&str>,
+    table: Option<&str>,
+) -> AppResult<Vec<TableItem>> {
+    match pool {
+        DatabasePool::MySQL(pool) => mysql::tables(pool, schema, table).await,
+        DatabasePool::Postgres(pool) => postgres::tables(pool, schema, table).await,
+        DatabasePool::SQLite(pool) => sqlite::tables(pool, schema, table).await,
+        _ => Err(unsupported()),
+    }
+}
+
+/// 读取表、列、索引、外键并按表组装；`table` 为空时读取整个 schema
+async fn describe(
+    pool: &DatabasePool,
+    schema: Option<&str>,
+    table: Option<&str>,
+) -> AppResult<Vec<TableDetail>> {
+    let tables = list_tables(pool, schema, table).await?;
+    if tables.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let (columns, indexes, foreign_keys, primary_keys) = match pool {
+        DatabasePool::MySQL(pool) => {
+            let indexes = mysql::indexes(pool, schema, table).await?;
+            let primary_keys = primary_keys_from_indexes(&indexes);
+            (
+                mysql::columns(pool, schema, table).await?,
+                indexes,
+                mysql::foreign_keys(pool, schema, table).await?,
+                primary_keys,
+            )
+        }
+        DatabasePool::Postgres(pool) => {
+            let indexes = postgres::indexes(pool, schema, table).await?;
+            let primary_keys = primary_keys_from_indexes(&indexes);
+            (
+                postgres::columns(pool, schema, table).await?,
+                indexes,
+                postgres::foreign_keys(pool, schema, table).await?,
+                primary_keys,
+            )
+        }
+        DatabasePool::SQLite(pool) => {
+            let (columns, primary_keys) = sqlite::columns(pool, schema, table).await?;
+            (
+                columns,
+                sqlite::indexes(pool, schema, table).await?,
+                sqlite::foreign_keys(pool, schema, table).await?,
+                primary_keys,
+            )
+        }
+        _ => return Err(unsupported()),
+    };
+
+    Ok(assemble(tables, columns, indexes, foreign_keys, primary_keys))
+}
+
+/// 从主键索引推导主键（MySQL / PostgreSQL 的主键总是由索引支撑）
+fn primary_keys_from_indexes(indexes: &TableRows<IndexItem>) -> TableRows<PrimaryKeyItem> {
+    indexes
+        .iter()
+        .filter(|(_, index)| index.primary)
+        .map(|(table, index)| {
+            (
+                table.clone(),
+                PrimaryKeyItem {
+                    name: Some(index.name.clone()),
+                    columns: index.columns.clone(),
+                },
+            )
+        })
+        .collect()
+}
+
+fn assemble(
+    tables: Vec<TableItem>,
+    columns: TableRows<ColumnItem>,
+    indexes: TableRows<IndexItem>,
+    foreign_keys: TableRows<ForeignKeyItem>,
+    primary_keys: TableRows<PrimaryKeyItem>,
+) -> Vec<TableDetail> {
+    let mut columns = group(columns);
+    let mut indexes = group(indexes);
+    let mut foreign_keys = group(foreign_keys);
+    let mut primary_keys: HashMap<String, PrimaryKeyItem> = primary_keys.into_iter().collect();
+
+    tables
+        .into_iter()
+        .map(|table| {
+            let primary_key = primary_keys.remove(&table.name);
+            let mut columns = columns.remove(&table.name).unwrap_or_default();
+            if let Some(pk) = &primary_key {
+                for column in columns.iter_mut() {
+                    column.is_primary_key = pk.columns.contains(&column.name);
+                }
+            }
+            TableDetail {
+                indexes: indexes.remove(&table.name).unwrap_or_default(),
+                foreign_keys: foreign_keys.remove(&table.name).unwrap_or_default(),
+                primary_key,
+                columns,
+                table,
+            }
+        })
+        .collect()
+}
+
+fn group<T>(rows: TableRows<T>) -> HashMap<String, Vec<T>> {
+    let mut grouped: HashMap<String, Vec<T>> = HashMap::new();
+    for (table, item) in rows {
+        grouped.entry(table).or_default().push(item);
+    }
+    grouped
+}
+
+/// 将外键规则统一为大写 SQL 关键字
+fn normalize_rule(rule: &str) -> String {
+    rule.trim().to_uppercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::models::metadata::TableKind;
+
+    use crate::test_support;
+
+    async fn sqlite_manager() -> (Arc<PoolManager>, String) {
+        let (manager, pool) = test_support::sqlite_manager(test_support::sqlite_connection("meta")).await;
+        for sql in [
+            "CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, email TEXT NOT NULL UNIQUE, name TEXT DEFAULT 'anon')",
+            "CREATE TABLE orders (id INTEGER PRIMARY KEY, user_id INTEGER NOT NULL REFERENCES users ON DELETE CASCADE, status TEXT)",
+            "CREATE INDEX idx_orders_status ON orders (status, user_id)",
+            "CREATE VIEW v_orders AS SELECT o.id, u.email FROM orders o JOIN users u ON u.id = o.user_id",
+        ] {
+            sqlx::query(sql).execute(&pool).await.unwrap();
+        }
+        (manager, "meta".to_string())
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_tables_and_views() {
+        let (manager, id) = sqlite_manager().await;
+        let service = MetadataService::new(manager);
+
+        let tables = service.tables(&id, None).await.unwrap();
+        let names: Vec<_> = tables.iter().map(|t| (t.name.as_str(), t.kind)).collect();
+        assert_eq!(
+            names,
+            vec![
+                ("orders", TableKind::Table),
+                ("users", TableKind::Table),
+                ("v_orders", TableKind::View),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_table_detail() {
+        let (manager, id) = sqlite_manager().await;
+        let service = MetadataService::new(manager);
+
+        let users = service.table(&id, None, "users").await.unwrap();
+        assert_eq!(users.primary_key.as_ref().unwrap().columns, vec!["id"]);
+        let idcol = users.column("id").unwrap();
+        assert!(idcol.is_primary_key && idcol.auto_increment);
+        let email = users.column("email").unwrap();
+        assert!(!email.nullable);
+        assert_eq!(users.column("name").unwrap().default_value.as_deref(), Some("'anon'"));
+        assert!(users.indexes.iter().any(|i| i.unique && i.columns == vec!["email"]));
+
+        let orders = service.table(&id, None, "orders").await.unwrap();
+        let fk = &orders.foreign_keys[0];
+        assert_eq!(fk.columns, vec!["user_id"]);
+        assert_eq!(fk.referenced_table, "users");
+        // 省略被引用列的外键引用 users 的主键
+        assert_eq!(fk.referenced_columns, vec!["id"]);
+        assert_eq!(fk.on_delete.as_deref(), Some("CASCADE"));
+        let index = orders.indexes.iter().find(|i| i.name == "idx_orders_status").unwrap();
+        assert_eq!(index.columns, vec!["status", "user_id"]);
+
+        assert!(matches!(
+            service.table(&id, None, "missing").await,
+            Err(AppError::NotFound(_))
+        ));
+    }
+}
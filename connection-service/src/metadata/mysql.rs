@@ -0,0 +1,238 @@
+//! MySQL 元数据读取（information_schema）
+//!
+//! information_schema 的部分列在 MySQL 8 中以二进制排序规则返回，
+//! 因此统一 `CAST(... AS CHAR)` 后再解码。
+
+use common::errors::AppResult;
+use common::models::metadata::{ColumnItem, ForeignKeyItem, IndexItem, SchemaItem, TableItem, TableKind};
+use sqlx::{MySqlPool, Row};
+
+use super::{normalize_rule, TableRows};
+
+pub(super) async fn schemas(pool: &MySqlPool) -> AppResult<Vec<SchemaItem>> {
+    let rows = sqlx::query(
+        "SELECT CAST(SCHEMA_NAME AS CHAR) AS name, CAST(SCHEMA_NAME = DATABASE() AS SIGNED) AS is_current \
+         FROM information_schema.SCHEMATA ORDER BY SCHEMA_NAME",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    rows.iter()
+        .map(|row| {
+            Ok(SchemaItem {
+                name: row.try_get("name")?,
+                is_current: row.try_get::<Option<i64>, _>("is_current")?.unwrap_or(0) != 0,
+            })
+        })
+        .collect()
+}
+
+pub(super) async fn tables(
+    pool: &MySqlPool,
+    schema: Option<&str>,
+    table: Option<&str>,
+) -> AppResult<Vec<TableItem>> {
+    let rows = sqlx::query(
+        "SELECT CAST(TABLE_SCHEMA AS CHAR) AS table_schema, CAST(TABLE_NAME AS CHAR) AS table_name, \
+                CAST(TABLE_TYPE AS CHAR) AS table_type, CAST(TABLE_ROWS AS SIGNED) AS table_rows, \
+                CAST(TABLE_COMMENT AS CHAR) AS table_comment \
+         FROM information_schema.TABLES \
+         WHERE TABLE_SCHEMA = COALESCE(?, DATABASE()) AND (? IS NULL OR TABLE_NAME = ?) \
+         ORDER BY TABLE_NAME",
+    )
+    .bind(schema)
+    .bind(table)
+    .bind(table)
+    .fetch_all(pool)
+    .await?;
+
+    rows.iter()
+        .map(|row| {
+            let table_type: String = row.try_get("table_type")?;
+            let kind = if table_type.contains("VIEW") {
+                TableKind::View
+            } else {
+                TableKind::Table
+            };
+            Ok(TableItem {
+                schema: row.try_get("table_schema")?,
+                name: row.try_get("table_name")?,
+                row_estimate: match kind {
+                    TableKind::Table => row.try_get("table_rows")?,
+                    _ => None,
+                },
+                comment: non_empty(row.try_get("table_comment")?),
+                kind,
+            })
+        })
+        .collect()
+}
+
+pub(super) async fn columns(
+    pool: &MySqlPool,
+    schema: Option<&str>,
+    table: Option<&str>,
+) -> AppResult<TableRows<ColumnItem>> {
+    let rows = sqlx::query(
+        "SELECT CAST(TABLE_NAME AS CHAR) AS table_name, CAST(COLUMN_NAME AS CHAR) AS column_name, \
+                CAST(ORDINAL_POSITION AS SIGNED) AS ordinal, CAST(COLUMN_TYPE AS CHAR) AS column_type, \
+                CAST(IS_NULLABLE AS CHAR) AS is_nullable, CAST(COLUMN_DEFAULT AS CHAR) AS column_default, \
+                CAST(EXTRA AS CHAR) AS extra, CAST(COLUMN_COMMENT AS CHAR) AS column_comment \
+         FROM information_schema.COLUMNS \
+         WHERE TABLE_SCHEMA = COALESCE(?, DATABASE()) AND (? IS NULL OR TABLE_NAME = ?) \
+         ORDER BY TABLE_NAME, ORDINAL_POSITION",
+    )
+    .bind(schema)
+    .bind(table)
+    .bind(table)
+    .fetch_all(pool)
+    .await?;
+
+    rows.iter()
+        .map(|row| {
+            let extra: Option<String> = row.try_get("extra")?;
+            let nullable: String = row.try_get("is_nullable")?;
+            let column_type: String = row.try_get("column_type")?;
+            let default_value: Option<String> = row.try_get("column_default")?;
+            Ok((
+                row.try_get("table_name")?,
+                ColumnItem {
+                    name: row.try_get("column_name")?,
+                    ordinal: row.try_get::<i64, _>("ordinal")? as u32,
+                    default_value: default_value
+                        .map(|d| default_expression(d, &column_type, extra.as_deref().unwrap_or(""))),
+                    data_type: column_type,
+                    nullable: nullable == "YES",
+                    is_primary_key: false,
+                    auto_increment: extra
+                        .map(|e| e.to_lowercase().contains("auto_increment"))
+                        .unwrap_or(false),
+                    comment: non_empty(row.try_get("column_comment")?),
+                },
+            ))
+        })
+        .collect()
+}
+
+/// 将 `COLUMN_DEFAULT` 转换为 SQL 表达式
+///
+/// information_schema 中字符串默认值不带引号，表达式默认值（8.0.13+）在 EXTRA 中标记为
+/// `DEFAULT_GENERATED`。
+fn default_expression(default: String, column_type: &str, extra: &str) -> String {
+    let lower = default.to_lowercase();
+    let numeric = column_type.contains("int")
+        || ["decimal", "float", "double", "bit"].iter().any(|t| column_type.starts_with(t));
+    if extra.contains("DEFAULT_GENERATED")
+        || lower.starts_with("current_timestamp")
+        || default.starts_with('\'')
+        || (numeric && default.parse::<f64>().is_ok())
+    {
+        default
+    } else {
+        format!("'{}'", default.replace('\\', "\\\\").replace('\'', "''"))
+    }
+}
+
+pub(super) async fn indexes(
+    pool: &MySqlPool,
+    schema: Option<&str>,
+    table: Option<&str>,
+) -> AppResult<TableRows<IndexItem>> {
+    let rows = sqlx::query(
+        "SELECT CAST(TABLE_NAME AS CHAR) AS table_name, CAST(INDEX_NAME AS CHAR) AS index_name, \
+                CAST(COLUMN_NAME AS CHAR) AS column_name, CAST(NON_UNIQUE AS SIGNED) AS non_unique, \
+                CAST(INDEX_TYPE AS CHAR) AS index_type \
+         FROM information_schema.STATISTICS \
+         WHERE TABLE_SCHEMA = COALESCE(?, DATABASE()) AND (? IS NULL OR TABLE_NAME = ?) \
+         ORDER BY TABLE_NAME, INDEX_NAME, SEQ_IN_INDEX",
+    )
+    .bind(schema)
+    .bind(table)
+    .bind(table)
+    .fetch_all(pool)
+    .await?;
+
+    let mut result: TableRows<IndexItem> = Vec::new();
+    for row in rows {
+        let table_name: String = row.try_get("table_name")?;
+        let name: String = row.try_get("index_name")?;
+        // 函数索引（MySQL 8）没有列名
+        let column = row
+            .try_get::<Option<String>, _>("column_name")?
+            .unwrap_or_else(|| "(expression)".to_string());
+
+        match result.last_mut() {
+            Some((t, index)) if *t == table_name && index.name == name => index.columns.push(column),
+            _ => result.push((
+                table_name,
+                IndexItem {
+                    primary: name == "PRIMARY",
+                    unique: row.try_get::<i64, _>("non_unique")? == 0,
+                    index_type: row.try_get("index_type")?,
+                    columns: vec![column],
+                    name,
+                },
+            )),
+        }
+    }
+    Ok(result)
+}
+
+pub(super) async fn foreign_keys(
+    pool: &MySqlPool,
+    schema: Option<&str>,
+    table: Option<&str>,
+) -> AppResult<TableRows<ForeignKeyItem>> {
+    let rows = sqlx::query(
+        "SELECT CAST(k.TABLE_NAME AS CHAR) AS table_name, CAST(k.CONSTRAINT_NAME AS CHAR) AS constraint_name, \
+                CAST(k.COLUMN_NAME AS CHAR) AS column_name, \
+                CAST(k.REFERENCED_TABLE_SCHEMA AS CHAR) AS referenced_schema, \
+                CAST(k.REFERENCED_TABLE_NAME AS CHAR) AS referenced_table, \
+                CAST(k.REFERENCED_COLUMN_NAME AS CHAR) AS referenced_column, \
+                CAST(r.UPDATE_RULE AS CHAR) AS update_rule, CAST(r.DELETE_RULE AS CHAR) AS delete_rule \
+         FROM information_schema.KEY_COLUMN_USAGE k \
+         JOIN information_schema.REFERENTIAL_CONSTRAINTS r \
+           ON r.CONSTRAINT_SCHEMA = k.CONSTRAINT_SCHEMA AND r.CONSTRAINT_NAME = k.CONSTRAINT_NAME \
+          AND r.TABLE_NAME = k.TABLE_NAME \
+         WHERE k.TABLE_SCHEMA = COALESCE(?, DATABASE()) AND (? IS NULL OR k.TABLE_NAME = ?) \
+           AND k.REFERENCED_TABLE_NAME IS NOT NULL \
+         ORDER BY k.TABLE_NAME, k.CONSTRAINT_NAME, k.ORDINAL_POSITION",
+    )
+    .bind(schema)
+    .bind(table)
+    .bind(table)
+    .fetch_all(pool)
+    .await?;
+
+    let mut result: TableRows<ForeignKeyItem> = Vec::new();
+    for row in rows {
+        let table_name: String = row.try_get("table_name")?;
+        let name: String = row.try_get("constraint_name")?;
+        let column: String = row.try_get("column_name")?;
+        let referenced_column: String = row.try_get("referenced_column")?;
+
+        match result.last_mut() {
+            Some((t, fk)) if *t == table_name && fk.name == name => {
+                fk.columns.push(column);
+                fk.referenced_columns.push(referenced_column);
+            }
+            _ => result.push((
+                table_name,
+                ForeignKeyItem {
+                    name,
+                    columns: vec![column],
+                    referenced_schema: row.try_get("referenced_schema")?,
+                    referenced_table: row.try_get("referenced_table")?,
+                    referenced_columns: vec![referenced_column],
+                    on_update: row.try_get::<Option<String>, _>("update_rule")?.map(|r| normalize_rule(&r)),
+                    on_delete: row.try_get::<Option<String>, _>("delete_rule")?.map(|r| normalize_rule(&r)),
+                },
+            )),
+        }
+    }
+    Ok(result)
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|v| !v.is_empty())
+}
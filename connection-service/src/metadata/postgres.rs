@@ -0,0 +1,231 @@
+//! PostgreSQL 元数据读取
+//!
+//! 表和列来自 pg_catalog（information_schema 不提供行数估计、索引和完整类型），
+//! 所有名称列统一转换为 `text` 以便解码。
+
+use common::errors::AppResult;
+use common::models::metadata::{ColumnItem, ForeignKeyItem, IndexItem, SchemaItem, TableItem, TableKind};
+use sqlx::{PgPool, Row};
+
+use super::TableRows;
+
+pub(super) async fn databases(pool: &PgPool) -> AppResult<Vec<SchemaItem>> {
+    let rows = sqlx::query(
+        "SELECT datname::text AS name, datname = current_database() AS is_current \
+         FROM pg_database WHERE NOT datistemplate ORDER BY datname",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    rows.iter()
+        .map(|row| {
+            Ok(SchemaItem {
+                name: row.try_get("name")?,
+                is_current: row.try_get("is_current")?,
+            })
+        })
+        .collect()
+}
+
+pub(super) async fn schemas(pool: &PgPool) -> AppResult<Vec<SchemaItem>> {
+    let rows = sqlx::query(
+        "SELECT nspname::text AS name, nspname = current_schema() AS is_current \
+         FROM pg_namespace \
+         WHERE nspname NOT LIKE 'pg\\_%' AND nspname <> 'information_schema' \
+         ORDER BY nspname",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    rows.iter()
+        .map(|row| {
+            Ok(SchemaItem {
+                name: row.try_get("name")?,
+                is_current: row.try_get::<Option<bool>, _>("is_current")?.unwrap_or(false),
+            })
+        })
+        .collect()
+}
+
+pub(super) async fn tables(
+    pool: &PgPool,
+    schema: Option<&str>,
+    table: Option<&str>,
+) -> AppResult<Vec<TableItem>> {
+    let rows = sqlx::query(
+        "SELECT n.nspname::text AS table_schema, c.relname::text AS table_name, c.relkind::text AS relkind, \
+                CASE WHEN c.reltuples < 0 THEN NULL ELSE c.reltuples::int8 END AS row_estimate, \
+                obj_description(c.oid, 'pg_class') AS table_comment \
+         FROM pg_class c JOIN pg_namespace n ON n.oid = c.relnamespace \
+         WHERE n.nspname = COALESCE($1::text, current_schema()) AND ($2::text IS NULL OR c.relname = $2) \
+           AND c.relkind IN ('r', 'p', 'v', 'm', 'f') \
+         ORDER BY c.relname",
+    )
+    .bind(schema)
+    .bind(table)
+    .fetch_all(pool)
+    .await?;
+
+    rows.iter()
+        .map(|row| {
+            let relkind: String = row.try_get("relkind")?;
+            let kind = match relkind.as_str() {
+                "v" => TableKind::View,
+                "m" => TableKind::MaterializedView,
+                _ => TableKind::Table,
+            };
+            Ok(TableItem {
+                schema: row.try_get("table_schema")?,
+                name: row.try_get("table_name")?,
+                row_estimate: match kind {
+                    TableKind::View => None,
+                    _ => row.try_get("row_estimate")?,
+                },
+                comment: row.try_get("table_comment")?,
+                kind,
+            })
+        })
+        .collect()
+}
+
+pub(super) async fn columns(
+    pool: &PgPool,
+    schema: Option<&str>,
+    table: Option<&str>,
+) -> AppResult<TableRows<ColumnItem>> {
+    let rows = sqlx::query(
+        "SELECT c.relname::text AS table_name, a.attname::text AS column_name, a.attnum::int4 AS ordinal, \
+                format_type(a.atttypid, a.atttypmod) AS data_type, NOT a.attnotnull AS nullable, \
+                pg_get_expr(d.adbin, d.adrelid) AS column_default, \
+                (a.attidentity <> '' OR COALESCE(pg_get_expr(d.adbin, d.adrelid), '') LIKE 'nextval(%') AS auto_increment, \
+                col_description(c.oid, a.attnum) AS column_comment \
+         FROM pg_attribute a \
+         JOIN pg_class c ON c.oid = a.attrelid \
+         JOIN pg_namespace n ON n.oid = c.relnamespace \
+         LEFT JOIN pg_attrdef d ON d.adrelid = a.attrelid AND d.adnum = a.attnum \
+         WHERE n.nspname = COALESCE($1::text, current_schema()) AND ($2::text IS NULL OR c.relname = $2) \
+           AND c.relkind IN ('r', 'p', 'v', 'm', 'f') AND a.attnum > 0 AND NOT a.attisdropped \
+         ORDER BY c.relname, a.attnum",
+    )
+    .bind(schema)
+    .bind(table)
+    .fetch_all(pool)
+    .await?;
+
+    rows.iter()
+        .map(|row| {
+            Ok((
+                row.try_get("table_name")?,
+                ColumnItem {
+                    name: row.try_get("column_name")?,
+                    ordinal: row.try_get::<i32, _>("ordinal")? as u32,
+                    data_type: row.try_get("data_type")?,
+                    nullable: row.try_get("nullable")?,
+                    default_value: row.try_get("column_default")?,
+                    is_primary_key: false,
+                    auto_increment: row.try_get("auto_increment")?,
+                    comment: row.try_get("column_comment")?,
+                },
+            ))
+        })
+        .collect()
+}
+
+pub(super) async fn indexes(
+    pool: &PgPool,
+    schema: Option<&str>,
+    table: Option<&str>,
+) -> AppResult<TableRows<IndexItem>> {
+    let rows = sqlx::query(
+        "SELECT t.relname::text AS table_name, i.relname::text AS index_name, \
+                ix.indisunique AS is_unique, ix.indisprimary AS is_primary, am.amname::text AS index_type, \
+                ARRAY(SELECT pg_get_indexdef(ix.indexrelid, k, true) \
+                      FROM generate_series(1, ix.indnatts) AS k ORDER BY k) AS columns \
+         FROM pg_index ix \
+         JOIN pg_class i ON i.oid = ix.indexrelid \
+         JOIN pg_class t ON t.oid = ix.indrelid \
+         JOIN pg_namespace n ON n.oid = t.relnamespace \
+         JOIN pg_am am ON am.oid = i.relam \
+         WHERE n.nspname = COALESCE($1::text, current_schema()) AND ($2::text IS NULL OR t.relname = $2) \
+         ORDER BY t.relname, i.relname",
+    )
+    .bind(schema)
+    .bind(table)
+    .fetch_all(pool)
+    .await?;
+
+    rows.iter()
+        .map(|row| {
+            Ok((
+                row.try_get("table_name")?,
+                IndexItem {
+                    name: row.try_get("index_name")?,
+                    columns: row.try_get("columns")?,
+                    unique: row.try_get("is_unique")?,
+                    primary: row.try_get("is_primary")?,
+                    index_type: row.try_get("index_type")?,
+                },
+            ))
+        })
+        .collect()
+}
+
+pub(super) async fn foreign_keys(
+    pool: &PgPool,
+    schema: Option<&str>,
+    table: Option<&str>,
+) -> AppResult<TableRows<ForeignKeyItem>> {
+    let rows = sqlx::query(
+        "SELECT t.relname::text AS table_name, con.conname::text AS constraint_name, \
+                ARRAY(SELECT a.attname::text FROM unnest(con.conkey) WITH ORDINALITY AS k(attnum, ord) \
+                      JOIN pg_attribute a ON a.attrelid = con.conrelid AND a.attnum = k.attnum \
+                      ORDER BY k.ord) AS columns, \
+                rn.nspname::text AS referenced_schema, rt.relname::text AS referenced_table, \
+                ARRAY(SELECT a.attname::text FROM unnest(con.confkey) WITH ORDINALITY AS k(attnum, ord) \
+                      JOIN pg_attribute a ON a.attrelid = con.confrelid AND a.attnum = k.attnum \
+                      ORDER BY k.ord) AS referenced_columns, \
+                con.confupdtype::text AS update_rule, con.confdeltype::text AS delete_rule \
+         FROM pg_constraint con \
+         JOIN pg_class t ON t.oid = con.conrelid \
+         JOIN pg_namespace n ON n.oid = t.relnamespace \
+         JOIN pg_class rt ON rt.oid = con.confrelid \
+         JOIN pg_namespace rn ON rn.oid = rt.relnamespace \
+         WHERE con.contype = 'f' AND n.nspname = COALESCE($1::text, current_schema()) \
+           AND ($2::text IS NULL OR t.relname = $2) \
+         ORDER BY t.relname, con.conname",
+    )
+    .bind(schema)
+    .bind(table)
+    .fetch_all(pool)
+    .await?;
+
+    rows.iter()
+        .map(|row| {
+            let update_rule: String = row.try_get("update_rule")?;
+            let delete_rule: String = row.try_get("delete_rule")?;
+            Ok((
+                row.try_get("table_name")?,
+                ForeignKeyItem {
+                    name: row.try_get("constraint_name")?,
+                    columns: row.try_get("columns")?,
+                    referenced_schema: row.try_get("referenced_schema")?,
+                    referenced_table: row.try_get("referenced_table")?,
+                    referenced_columns: row.try_get("referenced_columns")?,
+                    on_update: Some(action_name(&update_rule).to_string()),
+                    on_delete: Some(action_name(&delete_rule).to_string()),
+                },
+            ))
+        })
+        .collect()
+}
+
+/// 将 pg_constraint 的动作代码转换为 SQL 关键字
+fn action_name(code: &str) -> &'static str {
+    match code {
+        "r" => "RESTRICT",
+        "c" => "CASCADE",
+        "n" => "SET NULL",
+        "d" => "SET DEFAULT",
+        _ => "NO ACTION",
+    }
+}
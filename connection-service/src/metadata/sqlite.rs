@@ -0,0 +1,236 @@
+//! SQLite 元数据读取（sqlite_master + PRAGMA 表值函数）
+
+use common::errors::AppResult;
+use common::models::metadata::{
+    ColumnItem, ForeignKeyItem, IndexItem, PrimaryKeyItem, SchemaItem, TableItem, TableKind,
+};
+use sqlx::{Row, SqlitePool};
+
+use super::{normalize_rule, TableRows};
+
+const DEFAULT_SCHEMA: &str = "main";
+
+pub(super) async fn schemas(pool: &SqlitePool) -> AppResult<Vec<SchemaItem>> {
+    let rows = sqlx::query("SELECT name FROM pragma_database_list ORDER BY seq")
+        .fetch_all(pool)
+        .await?;
+
+    rows.iter()
+        .map(|row| {
+            let name: String = row.try_get("name")?;
+            Ok(SchemaItem {
+                is_current: name == DEFAULT_SCHEMA,
+                name,
+            })
+        })
+        .collect()
+}
+
+pub(super) async fn tables(
+    pool: &SqlitePool,
+    schema: Option<&str>,
+    table: Option<&str>,
+) -> AppResult<Vec<TableItem>> {
+    let schema = schema.unwrap_or(DEFAULT_SCHEMA);
+    let sql = format!(
+        "SELECT name, type FROM {}.sqlite_master \
+         WHERE type IN ('table', 'view') AND name NOT LIKE 'sqlite\\_%' ESCAPE '\\' \
+           AND (?1 IS NULL OR name = ?1) \
+         ORDER BY name",
+        quote_ident(schema)
+    );
+    let rows = sqlx::query(&sql).bind(table).fetch_all(pool).await?;
+
+    rows.iter()
+        .map(|row| {
+            let kind: String = row.try_get("type")?;
+            Ok(TableItem {
+                schema: schema.to_string(),
+                name: row.try_get("name")?,
+                kind: if kind == "view" { TableKind::View } else { TableKind::Table },
+                row_estimate: None,
+                comment: None,
+            })
+        })
+        .collect()
+}
+
+/// 读取列定义，同时根据 `pk` 序号推导主键（INTEGER PRIMARY KEY 没有对应索引）
+pub(super) async fn columns(
+    pool: &SqlitePool,
+    schema: Option<&str>,
+    table: Option<&str>,
+) -> AppResult<(TableRows<ColumnItem>, TableRows<PrimaryKeyItem>)> {
+    let schema = schema.unwrap_or(DEFAULT_SCHEMA);
+    let sql = format!(
+        "SELECT m.name AS table_name, p.cid, p.name, p.type, p.\"notnull\", p.dflt_value, p.pk \
+         FROM {}.sqlite_master m JOIN pragma_table_info(m.name, ?1) p \
+         WHERE m.type IN ('table', 'view') AND m.name NOT LIKE 'sqlite\\_%' ESCAPE '\\' \
+           AND (?2 IS NULL OR m.name = ?2) \
+         ORDER BY m.name, p.cid",
+        quote_ident(schema)
+    );
+    let rows = sqlx::query(&sql).bind(schema).bind(table).fetch_all(pool).await?;
+
+    let mut columns: TableRows<ColumnItem> = Vec::with_capacity(rows.len());
+    let mut key_columns: Vec<(String, i64, String)> = Vec::new();
+    for row in rows {
+        let table_name: String = row.try_get("table_name")?;
+        let name: String = row.try_get("name")?;
+        let pk: i64 = row.try_get("pk")?;
+        if pk > 0 {
+            key_columns.push((table_name.clone(), pk, name.clone()));
+        }
+        columns.push((
+            table_name,
+            ColumnItem {
+                ordinal: row.try_get::<i64, _>("cid")? as u32 + 1,
+                data_type: row.try_get("type")?,
+                nullable: row.try_get::<i64, _>("notnull")? == 0 && pk == 0,
+                default_value: row.try_get("dflt_value")?,
+                is_primary_key: pk > 0,
+                auto_increment: false,
+                comment: None,
+                name,
+            },
+        ));
+    }
+
+    key_columns.sort();
+    let mut primary_keys: TableRows<PrimaryKeyItem> = Vec::new();
+    for (table_name, _, column) in key_columns {
+        match primary_keys.last_mut() {
+            Some((t, pk)) if *t == table_name => pk.columns.push(column),
+            _ => primary_keys.push((table_name, PrimaryKeyItem { name: None, columns: vec![column] })),
+        }
+    }
+
+    // 单列 INTEGER PRIMARY KEY 是 rowid 别名，插入时自动分配
+    for (table_name, column) in columns.iter_mut() {
+        column.auto_increment = column.is_primary_key
+            && column.data_type.eq_ignore_ascii_case("INTEGER")
+            && primary_keys
+                .iter()
+                .any(|(t, pk)| t == table_name && pk.columns.len() == 1);
+    }
+
+    Ok((columns, primary_keys))
+}
+
+pub(super) async fn indexes(
+    pool: &SqlitePool,
+    schema: Option<&str>,
+    table: Option<&str>,
+) -> AppResult<TableRows<IndexItem>> {
+    let schema = schema.unwrap_or(DEFAULT_SCHEMA);
+    let sql = format!(
+        "SELECT m.name AS table_name, il.name AS index_name, il.\"unique\" AS is_unique, \
+                il.origin, ii.name AS column_name \
+         FROM {}.sqlite_master m \
+         JOIN pragma_index_list(m.name, ?1) il \
+         JOIN pragma_index_xinfo(il.name, ?1) ii \
+         WHERE m.type = 'table' AND ii.key = 1 AND (?2 IS NULL OR m.name = ?2) \
+         ORDER BY m.name, il.name, ii.seqno",
+        quote_ident(schema)
+    );
+    let rows = sqlx::query(&sql).bind(schema).bind(table).fetch_all(pool).await?;
+
+    let mut result: TableRows<IndexItem> = Vec::new();
+    for row in rows {
+        let table_name: String = row.try_get("table_name")?;
+        let name: String = row.try_get("index_name")?;
+        // 表达式索引没有列名
+        let column = row
+            .try_get::<Option<String>, _>("column_name")?
+            .unwrap_or_else(|| "(expression)".to_string());
+
+        match result.last_mut() {
+            Some((t, index)) if *t == table_name && index.name == name => index.columns.push(column),
+            _ => {
+                let origin: String = row.try_get("origin")?;
+                result.push((
+                    table_name,
+                    IndexItem {
+                        unique: row.try_get::<i64, _>("is_unique")? != 0,
+                        primary: origin == "pk",
+                        index_type: None,
+                        columns: vec![column],
+                        name,
+                    },
+                ))
+            }
+        }
+    }
+    Ok(result)
+}
+
+pub(super) async fn foreign_keys(
+    pool: &SqlitePool,
+    schema: Option<&str>,
+    table: Option<&str>,
+) -> AppResult<TableRows<ForeignKeyItem>> {
+    let schema = schema.unwrap_or(DEFAULT_SCHEMA);
+    // 省略被引用列时引用的是对方主键，按外键内序号取主键的对应列
+    let sql = format!(
+        "SELECT m.name AS table_name, f.id, f.\"table\" AS referenced_table, f.\"from\" AS column_name, \
+                COALESCE(f.\"to\", (SELECT p.name FROM pragma_table_info(f.\"table\", ?1) p WHERE p.pk = f.seq + 1)) \
+                    AS referenced_column, \
+                f.on_update, f.on_delete \
+         FROM {}.sqlite_master m JOIN pragma_foreign_key_list(m.name, ?1) f \
+         WHERE m.type = 'table' AND (?2 IS NULL OR m.name = ?2) \
+         ORDER BY m.name, f.id, f.seq",
+        quote_ident(schema)
+    );
+    let rows = sqlx::query(&sql).bind(schema).bind(table).fetch_all(pool).await?;
+
+    let mut result: TableRows<ForeignKeyItem> = Vec::new();
+    // 每个外键的被引用列是否都已确定；被引用表没有对应主键列时无法确定
+    let mut resolved: Vec<bool> = Vec::new();
+    let mut last_id = -1;
+    for row in rows {
+        let table_name: String = row.try_get("table_name")?;
+        let id: i64 = row.try_get("id")?;
+        let column: String = row.try_get("column_name")?;
+        let referenced_column: Option<String> = row.try_get("referenced_column")?;
+
+        match result.last_mut() {
+            Some((t, fk)) if *t == table_name && id == last_id => {
+                fk.columns.push(column);
+                match (referenced_column, resolved.last_mut()) {
+                    (Some(referenced_column), _) => fk.referenced_columns.push(referenced_column),
+                    (None, Some(resolved)) => *resolved = false,
+                    (None, None) => {}
+                }
+            }
+            _ => {
+                let on_update: String = row.try_get("on_update")?;
+                let on_delete: String = row.try_get("on_delete")?;
+                result.push((
+                    table_name.clone(),
+                    ForeignKeyItem {
+                        // SQLite 外键没有名称，按表名和序号生成
+                        name: format!("fk_{}_{}", table_name, id),
+                        columns: vec![column],
+                        referenced_schema: Some(schema.to_string()),
+                        referenced_table: row.try_get("referenced_table")?,
+                        referenced_columns: referenced_column.iter().cloned().collect(),
+                        on_update: Some(normalize_rule(&on_update)),
+                        on_delete: Some(normalize_rule(&on_delete)),
+                    },
+                ));
+                resolved.push(referenced_column.is_some());
+            }
+        }
+        last_id = id;
+    }
+    for ((_, fk), resolved) in result.iter_mut().zip(resolved) {
+        if !resolved {
+            fk.referenced_columns.clear();
+        }
+    }
+    Ok(result)
+}
+
+fn quote_ident(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
@@ -1,17 +1,64 @@
 //! Database connection pool manager.
 //!
 //! Manages connection pools for different database types (MySQL, PostgreSQL, SQLite, Redis).
+//!
+//! Connection configurations are persisted through [`ConnectionStore`] when one is
+//! attached. A connection whose database cannot be reached stays registered as
+//! disconnected and its pool is opened again on next use.
+//!
+//! Pools are opened lazily on first use and shut down by [`spawn_evictor`] once
+//! unused for the connection's eviction period. Network I/O such as opening or
+//! closing a pool never happens while the manager's maps are locked; concurrent
+//! first uses of one connection wait on a per-connection lock and share a pool.
+//!
+//! TLS settings are applied through the sqlx connect options for MySQL and
+//! PostgreSQL. Redis connections with TLS go through a local [`Tunnel`] that
+//! lives as long as the pool, and so does the [`SshTunnel`] of a connection
+//! behind a bastion host.
+//!
+//! Every connect and probe feeds the connection's health history: up/down
+//! transitions and a window of probe round-trip times. The health monitor
+//! probes open pools in the background and rebuilds failed ones through
+//! [`PoolManager::rebuild`].
 
-use std::collections::HashMap;
-use std::time::Duration;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use common::config::AppConfig;
 use common::errors::{AppError, AppResult};
-use common::models::connection::{ConnectionConfig, DbType};
-use redis::aio::ConnectionManager as RedisConnectionManager;
-use sqlx::{mysql::MySqlPoolOptions, postgres::PgPoolOptions, sqlite::SqlitePoolOptions};
-use sqlx::{MySqlPool, PgPool, SqlitePool};
-use tokio::sync::RwLock;
+use chrono::{DateTime, Utc};
+use common::models::connection::{ConnectionConfig, ConnectionStatus, DbType, PoolOptions, TlsMode};
+use common::models::pool::{
+    AcquireStats, ConnectionHealth, HealthOverview, HealthState, HealthTransition, LatencyStats, PoolStats,
+    TunnelState, TunnelStatus,
+};
+use common::utils::dsn::{self, REDIS_TLS_OPTION};
+use redis::aio::{ConnectionManager as RedisConnectionManager, ConnectionManagerConfig};
+use sqlx::{mysql::MySqlPoolOptions, postgres::PgPoolOptions};
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::{Database, MySqlPool, PgPool, SqlitePool};
+use tokio::sync::{Mutex, RwLock};
+use tracing::info;
+
+use crate::connection_store::{self, ConnectionStore};
+use crate::dialect::Dialect;
+use crate::ssh_tunnel::SshTunnel;
+use crate::tls;
+use crate::tunnel::{Tunnel, Upstream};
+
+/// Longest time a replaced pool waits for in-flight queries before closing.
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(60);
+const DRAIN_POLL: Duration = Duration::from_millis(200);
+/// Interval between checks for pools to evict.
+const EVICT_TICK: Duration = Duration::from_secs(15);
+/// Defaults for idle connection timeout and connection lifetime, as in sqlx.
+const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+const DEFAULT_MAX_LIFETIME: Duration = Duration::from_secs(30 * 60);
+/// State changes kept per connection.
+const TRANSITION_LIMIT: usize = 50;
+/// Probe round-trip times kept per connection for the latency percentiles.
+const LATENCY_WINDOW: usize = 100;
 
 /// Connection pool wrapper for different database types.
 #[derive(Clone)]
@@ -28,53 +75,651 @@ pub enum DatabasePool {
     Unsupported,
 }
 
+impl DatabasePool {
+    /// Returns the SQL dialect spoken by this pool.
+    ///
+    /// Fails for Redis and unsupported pools, which have no SQL dialect.
+    pub fn dialect(&self) -> AppResult<Dialect> {
+        match self {
+            Self::MySQL(_) => Ok(Dialect::MySql),
+            Self::Postgres(_) => Ok(Dialect::Postgres),
+            Self::SQLite(_) => Ok(Dialect::Sqlite),
+            _ => Err(AppError::UnsupportedDatabaseType(
+                "only MySQL, PostgreSQL and SQLite connections have a SQL dialect".into(),
+            )),
+        }
+    }
+
+    /// Runs a trivial probe (`SELECT 1` or `PING`) and returns its total time.
+    pub async fn ping(&self) -> AppResult<Duration> {
+        self.probe().await.map(|probe| probe.acquire_wait + probe.round_trip)
+    }
+
+    /// Acquires a connection and runs a trivial probe (`SELECT 1` or `PING`).
+    ///
+    /// Fails with [`AppError::PoolExhausted`] when no connection frees up
+    /// within the acquire timeout.
+    pub async fn probe(&self) -> AppResult<Probe> {
+        let started = Instant::now();
+        let acquire_wait = match self {
+            DatabasePool::MySQL(pool) => {
+                let mut conn = pool.acquire().await?;
+                let acquired = started.elapsed();
+                sqlx::query("SELECT 1").execute(&mut *conn).await?;
+                acquired
+            }
+            DatabasePool::Postgres(pool) => {
+                let mut conn = pool.acquire().await?;
+                let acquired = started.elapsed();
+                sqlx::query("SELECT 1").execute(&mut *conn).await?;
+                acquired
+            }
+            DatabasePool::SQLite(pool) => {
+                let mut conn = pool.acquire().await?;
+                let acquired = started.elapsed();
+                sqlx::query("SELECT 1").execute(&mut *conn).await?;
+                acquired
+            }
+            DatabasePool::Redis(manager) => {
+                // Multiplexed: there is no connection to wait for.
+                let mut conn = manager.clone();
+                redis::cmd("PING").query_async::<String>(&mut conn).await?;
+                Duration::ZERO
+            }
+            DatabasePool::Unsupported => {
+                return Err(AppError::UnsupportedDatabaseType("Connection type not supported yet".into()));
+            }
+        };
+        Ok(Probe { acquire_wait, round_trip: started.elapsed() - acquire_wait })
+    }
+
+    /// Returns the open, idle, minimum and maximum connection counts.
+    pub fn usage(&self) -> PoolUsage {
+        let sqlx_usage = |size: u32, idle: usize, min: u32, max: u32| PoolUsage { size, idle: idle as u32, min, max };
+        match self {
+            Self::MySQL(p) => sqlx_usage(p.size(), p.num_idle(), p.options().get_min_connections(), p.options().get_max_connections()),
+            Self::Postgres(p) => sqlx_usage(p.size(), p.num_idle(), p.options().get_min_connections(), p.options().get_max_connections()),
+            Self::SQLite(p) => sqlx_usage(p.size(), p.num_idle(), p.options().get_min_connections(), p.options().get_max_connections()),
+            // One multiplexed connection that is never checked out.
+            Self::Redis(_) => PoolUsage { size: 1, idle: 1, min: 1, max: 1 },
+            Self::Unsupported => PoolUsage::default(),
+        }
+    }
+
+    /// Whether any connection is checked out of the pool.
+    pub fn in_use(&self) -> bool {
+        let busy = |size: u32, idle: usize| size as usize > idle;
+        match self {
+            Self::MySQL(p) => busy(p.size(), p.num_idle()),
+            Self::Postgres(p) => busy(p.size(), p.num_idle()),
+            Self::SQLite(p) => busy(p.size(), p.num_idle()),
+            // Redis connections are multiplexed and never checked out.
+            Self::Redis(_) | Self::Unsupported => false,
+        }
+    }
+
+    /// Closes the pool once its checked-out connections are returned.
+    pub async fn close(self) {
+        match self {
+            Self::MySQL(p) => p.close().await,
+            Self::Postgres(p) => p.close().await,
+            Self::SQLite(p) => p.close().await,
+            // Redis connections are multiplexed and close when dropped.
+            Self::Redis(_) | Self::Unsupported => {}
+        }
+    }
+}
+
+/// Timing of a probe.
+#[derive(Debug, Clone, Copy)]
+pub struct Probe {
+    /// Time spent waiting for a free connection.
+    pub acquire_wait: Duration,
+    /// Round-trip time of the probe query.
+    pub round_trip: Duration,
+}
+
+/// Connection counts of a pool.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PoolUsage {
+    pub size: u32,
+    pub idle: u32,
+    pub min: u32,
+    pub max: u32,
+}
+
+/// Connect and probe history of a connection, kept while its pool is closed.
+#[derive(Debug, Default)]
+struct Health {
+    last_success: Option<DateTime<Utc>>,
+    last_probe: Option<DateTime<Utc>>,
+    last_probe_rtt: Option<Duration>,
+    last_error: Option<(String, DateTime<Utc>)>,
+    acquires: u64,
+    acquire_timeouts: u64,
+    total_wait: Duration,
+    max_wait: Duration,
+    last_wait: Option<Duration>,
+    /// Current state and when it was entered.
+    state: Option<(HealthState, DateTime<Utc>)>,
+    /// State changes, oldest first, at most [`TRANSITION_LIMIT`].
+    transitions: VecDeque<HealthTransition>,
+    consecutive_failures: u32,
+    rebuilds: u32,
+    /// Round-trip times of the latest successful probes, at most [`LATENCY_WINDOW`].
+    latencies: VecDeque<Duration>,
+}
+
+impl Health {
+    fn succeed(&mut self) {
+        self.last_success = Some(Utc::now());
+        self.consecutive_failures = 0;
+        self.enter(HealthState::Up, None);
+    }
+
+    /// Records an error; an exhausted pool is busy, not down.
+    fn fail(&mut self, error: &AppError) {
+        self.last_error = Some((error.to_string(), Utc::now()));
+        if !matches!(error, AppError::PoolExhausted(_)) {
+            self.consecutive_failures += 1;
+            self.enter(HealthState::Down, Some(error.to_string()));
+        }
+    }
+
+    /// Switches to `state`, recording the change.
+    fn enter(&mut self, state: HealthState, error: Option<String>) {
+        if self.state.is_some_and(|(current, _)| current == state) {
+            return;
+        }
+        let now = Utc::now();
+        self.state = Some((state, now));
+        if self.transitions.len() == TRANSITION_LIMIT {
+            self.transitions.pop_front();
+        }
+        self.transitions.push_back(HealthTransition { state, at: now.to_rfc3339(), error });
+    }
+
+    /// Records a probe and its outcome.
+    fn record_probe(&mut self, result: &AppResult<Probe>) {
+        self.last_probe = Some(Utc::now());
+        match result {
+            Ok(probe) => {
+                self.acquires += 1;
+                self.total_wait += probe.acquire_wait;
+                self.max_wait = self.max_wait.max(probe.acquire_wait);
+                self.last_wait = Some(probe.acquire_wait);
+                self.last_probe_rtt = Some(probe.round_trip);
+                if self.latencies.len() == LATENCY_WINDOW {
+                    self.latencies.pop_front();
+                }
+                self.latencies.push_back(probe.round_trip);
+                self.succeed();
+            }
+            Err(e) => {
+                if matches!(e, AppError::PoolExhausted(_)) {
+                    self.acquires += 1;
+                    self.acquire_timeouts += 1;
+                }
+                self.fail(e);
+            }
+        }
+    }
+
+    /// Whether the last error is more recent than the last success.
+    fn failing(&self) -> bool {
+        match (&self.last_error, self.last_success) {
+            (Some((_, failed)), Some(succeeded)) => *failed > succeeded,
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+
+    fn acquire_stats(&self) -> AcquireStats {
+        let ms = |d: Duration| d.as_millis() as u64;
+        let measured = self.acquires - self.acquire_timeouts;
+        AcquireStats {
+            count: self.acquires,
+            timeouts: self.acquire_timeouts,
+            last_wait_ms: self.last_wait.map(ms),
+            avg_wait_ms: (measured > 0).then(|| ms(self.total_wait) / measured),
+            max_wait_ms: (measured > 0).then(|| ms(self.max_wait)),
+        }
+    }
+
+    /// Nearest-rank percentiles of the probe round-trip window.
+    fn latency_stats(&self) -> LatencyStats {
+        let mut sorted: Vec<u64> = self.latencies.iter().map(|d| d.as_millis() as u64).collect();
+        sorted.sort_unstable();
+        let percentile = |p: usize| {
+            let rank = (sorted.len() * p).div_ceil(100).max(1);
+            sorted.get(rank - 1).copied()
+        };
+        LatencyStats {
+            samples: sorted.len() as u32,
+            p50_ms: percentile(50),
+            p95_ms: percentile(95),
+            p99_ms: percentile(99),
+            max_ms: sorted.last().copied(),
+        }
+    }
+}
+
+/// A newly opened pool and the tunnels it connects through, if any.
+pub struct PoolHandle {
+    pub pool: DatabasePool,
+    tunnel: Option<Tunnel>,
+    ssh: Option<SshTunnel>,
+}
+
+impl PoolHandle {
+    fn new(pool: DatabasePool) -> Self {
+        Self { pool, tunnel: None, ssh: None }
+    }
+
+    /// TLS version and cipher negotiated by the tunnel, if the pool uses one.
+    pub fn tunnel_tls(&self) -> Option<&str> {
+        self.tunnel.as_ref().and_then(|t| t.negotiated())
+    }
+
+    /// Closes the pool, then stops its tunnels.
+    pub async fn close(self) {
+        self.pool.close().await;
+        drop(self.tunnel);
+        drop(self.ssh);
+    }
+}
+
+/// An open pool and the last time it was handed out.
+struct PoolEntry {
+    pool: DatabasePool,
+    tunnel: Option<Tunnel>,
+    ssh: Option<SshTunnel>,
+    last_used: std::sync::Mutex<Instant>,
+}
+
+impl PoolEntry {
+    fn new(handle: PoolHandle) -> Self {
+        Self {
+            pool: handle.pool,
+            tunnel: handle.tunnel,
+            ssh: handle.ssh,
+            last_used: std::sync::Mutex::new(Instant::now()),
+        }
+    }
+
+    fn into_handle(self) -> PoolHandle {
+        PoolHandle { pool: self.pool, tunnel: self.tunnel, ssh: self.ssh }
+    }
+
+    /// Hands out the pool and records the use.
+    fn take(&self) -> DatabasePool {
+        *self.last_used.lock().unwrap_or_else(|e| e.into_inner()) = Instant::now();
+        self.pool.clone()
+    }
+
+    fn idle_for(&self) -> Duration {
+        self.last_used.lock().unwrap_or_else(|e| e.into_inner()).elapsed()
+    }
+}
+
+/// Pool settings of a connection with the service defaults filled in.
+struct PoolSettings {
+    min_connections: u32,
+    max_connections: u32,
+    acquire_timeout: Duration,
+    idle_timeout: Option<Duration>,
+    max_lifetime: Option<Duration>,
+}
+
+impl PoolSettings {
+    /// Applies the settings to a sqlx pool builder.
+    fn apply<DB: Database>(&self, options: sqlx::pool::PoolOptions<DB>) -> sqlx::pool::PoolOptions<DB> {
+        options
+            .min_connections(self.min_connections)
+            .max_connections(self.max_connections)
+            .acquire_timeout(self.acquire_timeout)
+            .idle_timeout(self.idle_timeout)
+            .max_lifetime(self.max_lifetime)
+    }
+}
+
+/// Converts a number of seconds where 0 means "never".
+fn optional_secs(secs: u64) -> Option<Duration> {
+    (secs > 0).then(|| Duration::from_secs(secs))
+}
+
 /// Manages database connection pools.
 ///
 /// Maintains a collection of connection pools, one for each active database connection.
 /// Supports MySQL, PostgreSQL, SQLite, and Redis.
 pub struct PoolManager {
     config: AppConfig,
-    /// Connection pools indexed by connection ID.
-    pools: RwLock<HashMap<String, DatabasePool>>,
+    /// Open connection pools indexed by connection ID.
+    pools: RwLock<HashMap<String, PoolEntry>>,
+    /// Per-connection locks that serialize opening a pool.
+    opening: Mutex<HashMap<String, Arc<Mutex<()>>>>,
     /// Connection configurations indexed by connection ID.
     configs: RwLock<HashMap<String, ConnectionConfig>>,
+    /// Connect and probe history indexed by connection ID.
+    health: RwLock<HashMap<String, Health>>,
+    /// Persistent store; `None` keeps connections in memory only.
+    store: Option<ConnectionStore>,
+    /// Serializes store writes so the newest state always lands last.
+    persist_lock: Mutex<()>,
 }
 
 impl PoolManager {
-    /// Creates a new pool manager.
+    /// Creates a new pool manager that keeps connections in memory only.
     pub fn new(config: AppConfig) -> Self {
         Self {
             config,
             pools: RwLock::new(HashMap::new()),
+            opening: Mutex::new(HashMap::new()),
             configs: RwLock::new(HashMap::new()),
+            health: RwLock::new(HashMap::new()),
+            store: None,
+            persist_lock: Mutex::new(()),
+        }
+    }
+
+    /// Creates a pool manager that persists connections to `store`.
+    pub fn with_store(config: AppConfig, store: ConnectionStore) -> Self {
+        Self { store: Some(store), ..Self::new(config) }
+    }
+
+    /// Reloads persisted connections.
+    ///
+    /// Every stored connection is registered without opening its pool; pools
+    /// are opened on first use. Returns the number of restored connections.
+    pub async fn restore(&self) -> AppResult<usize> {
+        let Some(store) = &self.store else { return Ok(0) };
+        let loaded = store.load().await?;
+        let count = loaded.configs.len();
+        {
+            let mut registered = self.configs.write().await;
+            for config in loaded.configs {
+                registered.insert(config.id.clone(), config);
+            }
+        }
+        if loaded.stale {
+            self.persist().await?;
+            info!("已用当前主密钥重新加密保存的密码");
         }
+        Ok(count)
     }
 
     /// Adds a new database connection.
+    ///
+    /// The pool is opened before the connection is registered, so an
+    /// unreachable database is rejected.
     pub async fn add_connection(&self, config: ConnectionConfig) -> AppResult<()> {
         let id = config.id.clone();
-        let timeout = Duration::from_secs(self.config.connect_timeout_secs);
-        let max_connections = self.config.max_connections;
+        let handle = self.open_pool(&config).await?;
+        self.pools.write().await.insert(id.clone(), PoolEntry::new(handle));
+        self.configs.write().await.insert(id.clone(), config);
+        if let Err(e) = self.persist().await {
+            let entry = self.pools.write().await.remove(&id);
+            self.configs.write().await.remove(&id);
+            if let Some(entry) = entry {
+                entry.into_handle().close().await;
+            }
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Registers imported connections without opening their pools.
+    ///
+    /// Connections with an existing ID replace the registered ones, whose
+    /// pools are closed once their in-flight queries have finished. Nothing
+    /// is registered if the store cannot be written.
+    pub async fn import_connections(&self, configs: Vec<ConnectionConfig>) -> AppResult<()> {
+        let previous: Vec<(String, Option<ConnectionConfig>)> = {
+            let mut registered = self.configs.write().await;
+            configs
+                .into_iter()
+                .map(|config| (config.id.clone(), registered.insert(config.id.clone(), config)))
+                .collect()
+        };
+        if let Err(e) = self.persist().await {
+            let mut registered = self.configs.write().await;
+            for (id, config) in previous {
+                match config {
+                    Some(config) => registered.insert(id, config),
+                    None => registered.remove(&id),
+                };
+            }
+            return Err(e);
+        }
+        for (id, _) in previous.iter().filter(|(_, replaced)| replaced.is_some()) {
+            self.health.write().await.remove(id);
+            if let Some(entry) = self.pools.write().await.remove(id) {
+                tokio::spawn(drain(entry.into_handle()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Replaces the configuration of a registered connection.
+    ///
+    /// When the endpoint, credentials, TLS, tunnel or pool settings change, a pool is opened with the new
+    /// settings first, so a failure leaves the connection untouched. The old
+    /// pool is swapped out atomically and closed once its in-flight queries
+    /// have finished.
+    pub async fn update_connection(&self, config: ConnectionConfig) -> AppResult<()> {
+        let id = config.id.clone();
+        let current = self
+            .get_connection(&id)
+            .await
+            .ok_or_else(|| AppError::ConnectionNotFound(id.clone()))?;
+        let reconnect = !same_endpoint(&current, &config);
+        let handle = if reconnect { Some(self.open_pool(&config).await?) } else { None };
+
+        let replaced = {
+            let mut configs = self.configs.write().await;
+            let mut pools = self.pools.write().await;
+            if !configs.contains_key(&id) {
+                return Err(AppError::ConnectionNotFound(id));
+            }
+            configs.insert(id.clone(), config);
+            handle.map(|handle| pools.insert(id.clone(), PoolEntry::new(handle)))
+        };
+        if reconnect {
+            let mut health = Health::default();
+            health.succeed();
+            self.health.write().await.insert(id.clone(), health);
+        }
+        if let Err(e) = self.persist().await {
+            // Put the previous configuration and pool back.
+            self.configs.write().await.insert(id.clone(), current);
+            if let Some(previous) = replaced {
+                let mut pools = self.pools.write().await;
+                let new_pool = match previous {
+                    Some(previous) => pools.insert(id, previous),
+                    None => pools.remove(&id),
+                };
+                drop(pools);
+                if let Some(new_pool) = new_pool {
+                    tokio::spawn(drain(new_pool.into_handle()));
+                }
+            }
+            return Err(e);
+        }
+        if let Some(Some(previous)) = replaced {
+            tokio::spawn(drain(previous.into_handle()));
+        }
+        Ok(())
+    }
+
+    /// Opens the pool of a registered connection unless it is already open.
+    ///
+    /// Concurrent callers for the same connection wait for a single attempt.
+    /// A failure is remembered as the connection's last error.
+    pub async fn connect(&self, id: &str) -> AppResult<DatabasePool> {
+        let lock = self.opening.lock().await.entry(id.to_string()).or_default().clone();
+        let _opening = lock.lock().await;
+        if let Some(entry) = self.pools.read().await.get(id) {
+            return Ok(entry.take());
+        }
+        let config = self
+            .get_connection(id)
+            .await
+            .ok_or_else(|| AppError::ConnectionNotFound(id.to_string()))?;
+        let handle = match self.open_pool(&config).await {
+            Ok(handle) => handle,
+            Err(e) => {
+                self.health.write().await.entry(id.to_string()).or_default().fail(&e);
+                return Err(e);
+            }
+        };
+        self.health.write().await.entry(id.to_string()).or_default().succeed();
+
+        // The connection may have been removed or updated while connecting.
+        let unchanged = self.configs.read().await.get(id).is_some_and(|c| same_endpoint(c, &config));
+        let pool = handle.pool.clone();
+        let (current, unused) = {
+            let mut pools = self.pools.write().await;
+            match pools.get(id) {
+                Some(entry) => (Some(entry.take()), Some(handle)),
+                None if unchanged => {
+                    pools.insert(id.to_string(), PoolEntry::new(handle));
+                    (None, None)
+                }
+                None => (None, Some(handle)),
+            }
+        };
+        if let Some(unused) = unused {
+            unused.close().await;
+        }
+        match current {
+            Some(current) => Ok(current),
+            None if !unchanged => Err(AppError::ConnectionNotFound(id.to_string())),
+            None => {
+                info!(connection_id = %id, "已打开连接池");
+                Ok(pool)
+            }
+        }
+    }
+
+    /// Shuts down pools that have been unused for their eviction period.
+    ///
+    /// Pools with checked-out connections are kept. Returns the number of
+    /// pools shut down.
+    pub async fn evict_idle(&self) -> usize {
+        let limits: HashMap<String, Duration> = self
+            .configs
+            .read()
+            .await
+            .values()
+            .filter_map(|c| self.evict_after(&c.pool).map(|d| (c.id.clone(), d)))
+            .collect();
+        let evicted: Vec<(String, PoolEntry)> = {
+            let mut pools = self.pools.write().await;
+            let ids: Vec<String> = pools
+                .iter()
+                .filter(|(id, entry)| {
+                    limits.get(*id).is_some_and(|limit| entry.idle_for() >= *limit) && !entry.pool.in_use()
+                })
+                .map(|(id, _)| id.clone())
+                .collect();
+            ids.into_iter().filter_map(|id| pools.remove(&id).map(|entry| (id, entry))).collect()
+        };
+        let count = evicted.len();
+        for (id, entry) in evicted {
+            entry.into_handle().close().await;
+            info!(connection_id = %id, "连接池空闲，已关闭");
+        }
+        count
+    }
+
+    /// Timeout for opening a connection.
+    pub fn connect_timeout(&self) -> Duration {
+        Duration::from_secs(self.config.connect_timeout_secs)
+    }
+
+    /// Eviction period of a connection, `None` if its pool is never shut down.
+    fn evict_after(&self, options: &PoolOptions) -> Option<Duration> {
+        optional_secs(options.evict_after_secs.unwrap_or(self.config.pool_evict_after_secs))
+    }
+
+    /// Resolves a connection's pool settings against the service defaults.
+    fn pool_settings(&self, options: &PoolOptions) -> PoolSettings {
+        let max_connections = options.max_connections.unwrap_or(self.config.max_connections).max(1);
+        PoolSettings {
+            min_connections: options.min_connections.unwrap_or(0).min(max_connections),
+            max_connections,
+            acquire_timeout: options.acquire_timeout_secs.map(Duration::from_secs).unwrap_or(self.connect_timeout()),
+            idle_timeout: options.idle_timeout_secs.map_or(Some(DEFAULT_IDLE_TIMEOUT), optional_secs),
+            max_lifetime: options.max_lifetime_secs.map_or(Some(DEFAULT_MAX_LIFETIME), optional_secs),
+        }
+    }
+
+    /// Opens a pool for `config` without registering it.
+    pub async fn open_pool(&self, config: &ConnectionConfig) -> AppResult<PoolHandle> {
+        let ssh = self.open_tunnel(config).await?;
+        self.open_pool_through(config, ssh).await
+    }
+
+    /// Opens the SSH tunnel of a connection, if it has one.
+    pub async fn open_tunnel(&self, config: &ConnectionConfig) -> AppResult<Option<SshTunnel>> {
+        let Some(options) = &config.ssh_tunnel else { return Ok(None) };
+        if config.db_type == DbType::SQLite {
+            return Err(AppError::Validation("ssh_tunnel: SQLite connections cannot use an SSH tunnel".into()));
+        }
+        let host = config
+            .host
+            .as_deref()
+            .ok_or_else(|| AppError::Validation(format!("{} requires host", config.db_type)))?;
+        let port = config
+            .port
+            .or(config.db_type.default_port())
+            .ok_or_else(|| AppError::Validation(format!("{} requires port", config.db_type)))?;
+        let tunnel = SshTunnel::open(options, host, port, self.connect_timeout(), &self.config.data_dir).await?;
+        Ok(Some(tunnel))
+    }
+
+    /// Opens a pool, connecting through the local end of `ssh` when given.
+    ///
+    /// The tunnel is closed together with the pool.
+    pub async fn open_pool_through(&self, config: &ConnectionConfig, ssh: Option<SshTunnel>) -> AppResult<PoolHandle> {
+        let routed;
+        let target = match &ssh {
+            Some(ssh) => {
+                let local = ssh.local_addr();
+                routed = ConnectionConfig { host: Some(local.ip().to_string()), port: Some(local.port()), ..config.clone() };
+                &routed
+            }
+            None => config,
+        };
+        let mut handle = self.open_direct(target, config.host.as_deref()).await?;
+        handle.ssh = ssh;
+        Ok(handle)
+    }
+
+    /// Opens a pool connecting to the host and port of `config`.
+    ///
+    /// `server_host` is the database's own host name, which Redis verifies
+    /// its TLS certificate against when `config` points at a tunnel.
+    async fn open_direct(&self, config: &ConnectionConfig, server_host: Option<&str>) -> AppResult<PoolHandle> {
+        let settings = self.pool_settings(&config.pool);
 
         let pool = match &config.db_type {
-            DbType::MySQL => {
-                let url = self.build_mysql_url(&config)?;
-                let pool = MySqlPoolOptions::new()
-                    .max_connections(max_connections)
-                    .acquire_timeout(timeout)
-                    .connect(&url)
+            DbType::MySQL | DbType::MariaDB => {
+                let options = tls::mysql_options(&driver_url(config)?, config.tls.as_ref()).await?;
+                let pool = settings
+                    .apply(MySqlPoolOptions::new())
+                    .connect_with(options)
                     .await
-                    .map_err(|e| AppError::DatabaseConnection(e.to_string()))?;
+                    .map_err(tls::connect_error)?;
                 DatabasePool::MySQL(pool)
             }
             DbType::Postgres => {
-                let url = self.build_postgres_url(&config)?;
-                let pool = PgPoolOptions::new()
-                    .max_connections(max_connections)
-                    .acquire_timeout(timeout)
-                    .connect(&url)
+                let options = tls::pg_options(&driver_url(config)?, config.tls.as_ref()).await?;
+                let pool = settings
+                    .apply(PgPoolOptions::new())
+                    .connect_with(options)
                     .await
-                    .map_err(|e| AppError::DatabaseConnection(e.to_string()))?;
+                    .map_err(tls::connect_error)?;
                 DatabasePool::Postgres(pool)
             }
             DbType::SQLite => {
@@ -82,85 +727,302 @@ impl PoolManager {
                     .file_path
                     .as_deref()
                     .ok_or_else(|| AppError::Validation("SQLite requires file_path".into()))?;
-                let url = format!("sqlite:{}?mode=rwc", path);
-                let pool = SqlitePoolOptions::new()
+                let options = SqliteConnectOptions::new().filename(path).create_if_missing(true);
+                let pool = settings
+                    .apply(SqlitePoolOptions::new())
+                    .min_connections(settings.min_connections.min(1))
                     .max_connections(1) // SQLite is single-writer
-                    .connect(&url)
+                    .connect_with(options)
                     .await
                     .map_err(|e| AppError::DatabaseConnection(e.to_string()))?;
                 DatabasePool::SQLite(pool)
             }
-            DbType::Redis => {
-                let url = self.build_redis_url(&config)?;
-                let client = redis::Client::open(url)
-                    .map_err(|e| AppError::RedisConnection(e.to_string()))?;
-                let manager = RedisConnectionManager::new(client)
-                    .await
-                    .map_err(|e| AppError::RedisConnection(e.to_string()))?;
-                DatabasePool::Redis(manager)
-            }
+            DbType::Redis => return self.open_redis(config, server_host, &settings).await,
             _ => {
                 // For now, return Unsupported for new database types
                 DatabasePool::Unsupported
             }
         };
 
-        self.pools.write().await.insert(id.clone(), pool);
-        self.configs.write().await.insert(id, config);
-        Ok(())
+        Ok(PoolHandle::new(pool))
+    }
+
+    /// Opens a Redis connection manager, through a TLS tunnel when configured.
+    ///
+    /// In `prefer` mode a server that does not speak TLS is used in plaintext.
+    async fn open_redis(
+        &self,
+        config: &ConnectionConfig,
+        server_host: Option<&str>,
+        settings: &PoolSettings,
+    ) -> AppResult<PoolHandle> {
+        let mut target = config.clone();
+        target.options.remove(REDIS_TLS_OPTION);
+        let mut tunnel = None;
+        if let Some(options) = tls::redis_tls(config) {
+            let host = config
+                .host
+                .as_deref()
+                .ok_or_else(|| AppError::Validation("Redis requires host".into()))?;
+            let (connector, server_name) = tls::connector(server_host.unwrap_or(host), &options).await?;
+            let upstream = Upstream { connector, server_name };
+            let port = config.port.or(DbType::Redis.default_port()).unwrap_or(6379);
+            match Tunnel::open(host, port, Some(upstream), self.connect_timeout()).await {
+                Ok(opened) => {
+                    target.host = Some(opened.local_addr().ip().to_string());
+                    target.port = Some(opened.local_addr().port());
+                    tunnel = Some(opened);
+                }
+                Err(e) if options.mode == TlsMode::Prefer => {
+                    info!(connection_id = %config.id, error = %e, "Redis 不支持 TLS，使用明文连接");
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        let client = redis::Client::open(driver_url(&target)?)
+            .map_err(|e| AppError::RedisConnection(e.to_string()))?;
+        let manager_config = ConnectionManagerConfig::new().set_connection_timeout(settings.acquire_timeout);
+        let manager = RedisConnectionManager::new_with_config(client, manager_config)
+            .await
+            .map_err(|e| AppError::RedisConnection(e.to_string()))?;
+        Ok(PoolHandle { pool: DatabasePool::Redis(manager), tunnel, ssh: None })
     }
 
-    /// Tests a database connection.
+    /// Writes all connection configurations to the store, if any.
+    async fn persist(&self) -> AppResult<()> {
+        let Some(store) = &self.store else { return Ok(()) };
+        let _guard = self.persist_lock.lock().await;
+        let configs = self.list_connections().await;
+        store.save(&configs).await
+    }
+
+    /// Re-encrypts every stored secret with the active master key.
+    ///
+    /// Returns the number of secrets sealed in the store: passwords, secret
+    /// driver options, TLS client keys and SSH credentials.
+    pub async fn reencrypt_secrets(&self) -> AppResult<usize> {
+        self.persist().await?;
+        Ok(self.configs.read().await.values().map(connection_store::secret_count).sum())
+    }
+
+    /// Tests a database connection, reconnecting it first if disconnected.
     pub async fn test_connection(&self, id: &str) -> AppResult<Duration> {
-        let pools = self.pools.read().await;
-        let pool = pools
-            .get(id)
-            .ok_or_else(|| AppError::ConnectionNotFound(id.to_string()))?;
+        self.probe(id).await.map(|probe| probe.acquire_wait + probe.round_trip)
+    }
 
-        let start = std::time::Instant::now();
+    /// Probes a connection, opening its pool if necessary, and records the
+    /// outcome in its pool statistics.
+    pub async fn probe(&self, id: &str) -> AppResult<Probe> {
+        let pool = self.pool(id).await?;
+        self.probe_pool(id, &pool).await
+    }
 
-        match pool {
-            DatabasePool::MySQL(pool) => {
-                sqlx::query("SELECT 1")
-                    .execute(pool)
-                    .await
-                    .map_err(|e| AppError::DatabaseQuery(e.to_string()))?;
+    /// Probes the open pool of a connection without opening it or counting
+    /// as a use. Returns `None` when no pool is open.
+    pub async fn probe_open(&self, id: &str) -> Option<AppResult<Probe>> {
+        let pool = self.get_pool(id).await?;
+        Some(self.probe_pool(id, &pool).await)
+    }
+
+    async fn probe_pool(&self, id: &str, pool: &DatabasePool) -> AppResult<Probe> {
+        let result = match pool.probe().await {
+            // sqlx also times out when it cannot open a connection to add to
+            // the pool; a pool with room to grow is unreachable, not exhausted.
+            Err(AppError::PoolExhausted(_)) if pool.usage().size < pool.usage().max => {
+                Err(AppError::DatabaseConnection("在获取连接超时内无法建立新连接".into()))
             }
-            DatabasePool::Postgres(pool) => {
-                sqlx::query("SELECT 1")
-                    .execute(pool)
-                    .await
-                    .map_err(|e| AppError::DatabaseQuery(e.to_string()))?;
+            result => result,
+        };
+        if self.configs.read().await.contains_key(id) {
+            self.health.write().await.entry(id.to_string()).or_default().record_probe(&result);
+        }
+        result
+    }
+
+    /// Replaces the open pool of a connection with a newly opened one.
+    ///
+    /// The old pool is closed once its in-flight queries have finished and
+    /// keeps serving if the new pool cannot be opened. Returns `false` when
+    /// no pool was open or the connection changed meanwhile, in which case
+    /// nothing is replaced.
+    pub async fn rebuild(&self, id: &str) -> AppResult<bool> {
+        let lock = self.opening.lock().await.entry(id.to_string()).or_default().clone();
+        let _opening = lock.lock().await;
+        let config = self
+            .get_connection(id)
+            .await
+            .ok_or_else(|| AppError::ConnectionNotFound(id.to_string()))?;
+        if !self.pools.read().await.contains_key(id) {
+            return Ok(false);
+        }
+        let handle = match self.open_pool(&config).await {
+            Ok(handle) => handle,
+            Err(e) => {
+                self.health.write().await.entry(id.to_string()).or_default().fail(&e);
+                return Err(e);
             }
-            DatabasePool::SQLite(pool) => {
-                sqlx::query("SELECT 1")
-                    .execute(pool)
-                    .await
-                    .map_err(|e| AppError::DatabaseQuery(e.to_string()))?;
+        };
+
+        let unchanged = self.configs.read().await.get(id).is_some_and(|c| same_endpoint(c, &config));
+        let replaced = {
+            let mut pools = self.pools.write().await;
+            match pools.remove(id) {
+                Some(previous) if unchanged => {
+                    let entry = PoolEntry::new(handle);
+                    // Rebuilding is not a use; keep the pool's eviction clock.
+                    *entry.last_used.lock().unwrap_or_else(|e| e.into_inner()) =
+                        *previous.last_used.lock().unwrap_or_else(|e| e.into_inner());
+                    pools.insert(id.to_string(), entry);
+                    Ok(previous)
+                }
+                previous => {
+                    if let Some(previous) = previous {
+                        pools.insert(id.to_string(), previous);
+                    }
+                    Err(handle)
+                }
             }
-            DatabasePool::Redis(manager) => {
-                let mut conn = manager.clone();
-                redis::cmd("PING")
-                    .query_async::<String>(&mut conn)
-                    .await
-                    .map_err(|e| AppError::RedisOperation(e.to_string()))?;
+        };
+        match replaced {
+            Ok(previous) => {
+                tokio::spawn(drain(previous.into_handle()));
+                let mut health = self.health.write().await;
+                let health = health.entry(id.to_string()).or_default();
+                health.rebuilds += 1;
+                health.succeed();
+                info!(connection_id = %id, "已重建连接池");
+                Ok(true)
             }
-            DatabasePool::Unsupported => {
-                return Err(AppError::UnsupportedDatabaseType("Connection type not supported yet".into()));
+            Err(unused) => {
+                unused.close().await;
+                Ok(false)
+            }
+        }
+    }
+
+    /// IDs of the connections whose pool is open.
+    pub async fn open_pool_ids(&self) -> Vec<String> {
+        self.pools.read().await.keys().cloned().collect()
+    }
+
+    /// Gets the health history of a connection.
+    pub async fn health(&self, id: &str) -> AppResult<ConnectionHealth> {
+        let config = self
+            .get_connection(id)
+            .await
+            .ok_or_else(|| AppError::ConnectionNotFound(id.to_string()))?;
+        Ok(self.health_of(config).await)
+    }
+
+    /// Gets the health history of all connections, ordered by name.
+    pub async fn health_overview(&self) -> HealthOverview {
+        let mut configs = self.list_connections().await;
+        configs.sort_by(|a, b| (&a.name, &a.id).cmp(&(&b.name, &b.id)));
+        let mut overview = HealthOverview::default();
+        for config in configs {
+            let health = self.health_of(config).await;
+            match health.state {
+                Some(HealthState::Up) => overview.up += 1,
+                Some(HealthState::Down) => overview.down += 1,
+                None => overview.unknown += 1,
             }
+            overview.connections.push(health);
+        }
+        overview
+    }
+
+    async fn health_of(&self, config: ConnectionConfig) -> ConnectionHealth {
+        let (status, _) = self.status(&config.id).await;
+        let health = self.health.read().await;
+        let health = health.get(&config.id);
+        ConnectionHealth {
+            id: config.id,
+            name: config.name,
+            status,
+            state: health.and_then(|h| h.state).map(|(state, _)| state),
+            since: health.and_then(|h| h.state).map(|(_, since)| since.to_rfc3339()),
+            consecutive_failures: health.map_or(0, |h| h.consecutive_failures),
+            rebuilds: health.map_or(0, |h| h.rebuilds),
+            latency: health.map(Health::latency_stats).unwrap_or_default(),
+            transitions: health.map(|h| h.transitions.iter().rev().cloned().collect()).unwrap_or_default(),
         }
+    }
+
+    /// Gets the pool statistics of a connection.
+    pub async fn stats(&self, id: &str) -> AppResult<PoolStats> {
+        let config = self
+            .get_connection(id)
+            .await
+            .ok_or_else(|| AppError::ConnectionNotFound(id.to_string()))?;
+        Ok(self.stats_of(config).await)
+    }
 
-        Ok(start.elapsed())
+    /// Gets the pool statistics of all connections, ordered by name.
+    pub async fn all_stats(&self) -> Vec<PoolStats> {
+        let mut configs = self.list_connections().await;
+        configs.sort_by(|a, b| (&a.name, &a.id).cmp(&(&b.name, &b.id)));
+        let mut stats = Vec::with_capacity(configs.len());
+        for config in configs {
+            stats.push(self.stats_of(config).await);
+        }
+        stats
+    }
+
+    async fn stats_of(&self, config: ConnectionConfig) -> PoolStats {
+        let (status, _) = self.status(&config.id).await;
+        let tunnel = self.tunnel_status(&config.id).await;
+        let usage = match self.get_pool(&config.id).await {
+            Some(pool) => pool.usage(),
+            None => {
+                let settings = self.pool_settings(&config.pool);
+                PoolUsage { min: settings.min_connections, max: settings.max_connections, ..PoolUsage::default() }
+            }
+        };
+        let health = self.health.read().await;
+        let health = health.get(&config.id);
+        let time = |t: &DateTime<Utc>| t.to_rfc3339();
+        PoolStats {
+            id: config.id,
+            name: config.name,
+            db_type: config.db_type,
+            status,
+            size: usage.size,
+            idle: usage.idle,
+            in_use: usage.size.saturating_sub(usage.idle),
+            min_connections: usage.min,
+            max_connections: usage.max,
+            acquire: health.map(Health::acquire_stats).unwrap_or_default(),
+            last_success_at: health.and_then(|h| h.last_success.as_ref()).map(time),
+            last_probe_at: health.and_then(|h| h.last_probe.as_ref()).map(time),
+            last_probe_ms: health.and_then(|h| h.last_probe_rtt).map(|d| d.as_millis() as u64),
+            last_error: health.and_then(|h| h.last_error.as_ref()).map(|(e, _)| e.clone()),
+            last_error_at: health.and_then(|h| h.last_error.as_ref()).map(|(_, t)| time(t)),
+            tunnel,
+        }
     }
 
     /// Removes a database connection.
+    ///
+    /// The pool is only torn down once the removal has been persisted; if the
+    /// store cannot be written the connection is kept.
     pub async fn remove_connection(&self, id: &str) -> AppResult<()> {
-        self.pools.write().await.remove(id);
-        self.configs
+        let config = self
+            .configs
             .write()
             .await
             .remove(id)
             .ok_or_else(|| AppError::ConnectionNotFound(id.to_string()))?;
+        if let Err(e) = self.persist().await {
+            self.configs.write().await.insert(id.to_string(), config);
+            return Err(e);
+        }
+        let entry = self.pools.write().await.remove(id);
+        self.health.write().await.remove(id);
+        self.opening.lock().await.remove(id);
+        if let Some(entry) = entry {
+            tokio::spawn(drain(entry.into_handle()));
+        }
         Ok(())
     }
 
@@ -174,9 +1036,55 @@ impl PoolManager {
         self.configs.read().await.get(id).cloned()
     }
 
-    /// Gets a connection pool by ID.
+    /// Gets an open connection pool by ID.
+    ///
+    /// Returns `None` when no pool is open and does not count as a use that
+    /// keeps the pool from being evicted; use [`Self::pool`] to open it on demand.
     pub async fn get_pool(&self, id: &str) -> Option<DatabasePool> {
-        self.pools.read().await.get(id).cloned()
+        self.pools.read().await.get(id).map(|entry| entry.pool.clone())
+    }
+
+    /// Gets a connection pool by ID, opening it if necessary.
+    pub async fn pool(&self, id: &str) -> AppResult<DatabasePool> {
+        if let Some(entry) = self.pools.read().await.get(id) {
+            return Ok(entry.take());
+        }
+        self.connect(id).await
+    }
+
+    /// Gets the pool status of a connection and, unless connected, its last error.
+    ///
+    /// An open pool whose SSH tunnel is down, or has moved to another local
+    /// port, is degraded.
+    pub async fn status(&self, id: &str) -> (ConnectionStatus, Option<String>) {
+        let open = self.pools.read().await.contains_key(id);
+        let tunnel_error = if self.tunnel_moved(id).await {
+            Some("SSH 隧道的本地端口已变更，等待重建连接池".to_string())
+        } else {
+            self.tunnel_status(id).await.filter(|t| t.state != TunnelState::Up).map(|t| {
+                format!("SSH 隧道 {} 已断开: {}", t.bastion, t.last_error.unwrap_or_default())
+            })
+        };
+        let health = self.health.read().await;
+        let failing = health.get(id).filter(|h| h.failing());
+        let error = failing.and_then(|h| h.last_error.as_ref()).map(|(e, _)| e.clone());
+        match (open, tunnel_error.or(error)) {
+            (true, None) => (ConnectionStatus::Connected, None),
+            (true, Some(error)) => (ConnectionStatus::Degraded, Some(error)),
+            (false, Some(error)) => (ConnectionStatus::Disconnected, Some(error)),
+            (false, None) => (ConnectionStatus::Idle, None),
+        }
+    }
+
+    /// Gets the status of the SSH tunnel of an open pool.
+    pub async fn tunnel_status(&self, id: &str) -> Option<TunnelStatus> {
+        self.pools.read().await.get(id).and_then(|entry| entry.ssh.as_ref()).map(SshTunnel::status)
+    }
+
+    /// Checks if the SSH tunnel of an open pool has moved to another local
+    /// port, in which case the pool must be rebuilt to reach the database.
+    pub async fn tunnel_moved(&self, id: &str) -> bool {
+        self.pools.read().await.get(id).and_then(|entry| entry.ssh.as_ref()).is_some_and(SshTunnel::port_changed)
     }
 
     /// Checks if a connection exists.
@@ -188,52 +1096,248 @@ impl PoolManager {
     pub async fn connection_count(&self) -> usize {
         self.configs.read().await.len()
     }
+}
 
-    // ============== URL Builders ==============
+/// Renders the URL handed to the driver.
+///
+/// Credentials are percent-encoded; MySQL and PostgreSQL fall back to the
+/// `root` / `postgres` user and the `postgres` database when unset.
+fn driver_url(config: &ConnectionConfig) -> AppResult<String> {
+    let mut config = config.clone();
+    match config.db_type {
+        DbType::MySQL | DbType::MariaDB => {
+            config.username.get_or_insert_with(|| "root".into());
+        }
+        DbType::Postgres => {
+            config.username.get_or_insert_with(|| "postgres".into());
+            config.database.get_or_insert_with(|| "postgres".into());
+        }
+        _ => {}
+    }
+    dsn::render(&config, false)
+}
 
-    fn build_mysql_url(&self, config: &ConnectionConfig) -> AppResult<String> {
-        let host = config
-            .host
-            .as_deref()
-            .ok_or_else(|| AppError::Validation("MySQL requires host".into()))?;
-        let port = config.port.unwrap_or(3306);
-        let username = config.username.as_deref().unwrap_or("root");
-        let password = config.password.as_deref().unwrap_or("");
-        let database = config.database.as_deref().unwrap_or("");
+/// Whether two configurations reach the same database with the same
+/// credentials, TLS, tunnel and pool settings.
+fn same_endpoint(a: &ConnectionConfig, b: &ConnectionConfig) -> bool {
+    a.db_type == b.db_type
+        && a.host == b.host
+        && a.port == b.port
+        && a.username == b.username
+        && a.password == b.password
+        && a.database == b.database
+        && a.file_path == b.file_path
+        && a.options == b.options
+        && a.tls == b.tls
+        && a.ssh_tunnel == b.ssh_tunnel
+        && a.pool == b.pool
+}
 
-        Ok(format!(
-            "mysql://{}:{}@{}:{}/{}",
-            username, password, host, port, database
-        ))
+/// Closes a pool that was swapped out, once its checked-out connections are back.
+///
+/// Queries that hold a connection keep running; the pool is closed when it
+/// has been idle or after [`DRAIN_TIMEOUT`].
+async fn drain(handle: PoolHandle) {
+    let started = Instant::now();
+    while handle.pool.in_use() && started.elapsed() < DRAIN_TIMEOUT {
+        tokio::time::sleep(DRAIN_POLL).await;
     }
+    handle.close().await;
+}
 
-    fn build_postgres_url(&self, config: &ConnectionConfig) -> AppResult<String> {
-        let host = config
-            .host
-            .as_deref()
-            .ok_or_else(|| AppError::Validation("PostgreSQL requires host".into()))?;
-        let port = config.port.unwrap_or(5432);
-        let username = config.username.as_deref().unwrap_or("postgres");
-        let password = config.password.as_deref().unwrap_or("");
-        let database = config.database.as_deref().unwrap_or("postgres");
+/// Starts the background task that shuts down idle pools.
+pub fn spawn_evictor(pool_manager: Arc<PoolManager>) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(EVICT_TICK);
+        loop {
+            ticker.tick().await;
+            pool_manager.evict_idle().await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secrets::Keyring;
+    use crate::test_support::connection;
+    use common::models::connection::UpdateConnectionRequest;
+
+    #[tokio::test]
+    async fn test_restore_keeps_unreachable_connection() {
+        let dir = std::env::temp_dir().join(format!("pools-{}", uuid::Uuid::new_v4()));
+        let data_dir = dir.to_str().unwrap();
+        let lite = ConnectionConfig {
+            file_path: Some(dir.join("lite.db").to_string_lossy().to_string()),
+            ..connection("lite", DbType::SQLite)
+        };
+        let down = ConnectionConfig {
+            host: Some("127.0.0.1".into()),
+            port: Some(1),
+            password: Some("pw".into()),
+            ..connection("down", DbType::Postgres)
+        };
+        let config = AppConfig {
+            connect_timeout_secs: 1,
+            data_dir: data_dir.to_string(),
+            master_key: None,
+            master_key_file: None,
+            ..AppConfig::load()
+        };
+        let keyring = Arc::new(Keyring::load(&config).await.unwrap());
+        let store = || ConnectionStore::open(data_dir, keyring.clone());
+        store().save(&[lite, down]).await.unwrap();
+
+        let manager = Arc::new(PoolManager::with_store(config, store()));
+        assert_eq!(manager.restore().await.unwrap(), 2);
+        assert_eq!(manager.connection_count().await, 2);
+        // 连接池在首次使用时才打开
+        assert_eq!(manager.status("lite").await, (ConnectionStatus::Idle, None));
+        assert!(manager.get_pool("lite").await.is_none());
+
+        assert!(manager.pool("lite").await.is_ok());
+        assert_eq!(manager.status("lite").await, (ConnectionStatus::Connected, None));
+        assert!(manager.pool("down").await.is_err());
+        let (status, error) = manager.status("down").await;
+        assert_eq!(status, ConnectionStatus::Disconnected);
+        assert!(error.is_some());
+        assert_eq!(manager.get_connection("down").await.unwrap().password.as_deref(), Some("pw"));
 
-        Ok(format!(
-            "postgres://{}:{}@{}:{}/{}",
-            username, password, host, port, database
-        ))
+        // 存储无法写入时删除失败，连接和已打开的连接池保持不变
+        let file = dir.join("connections.json");
+        std::fs::remove_file(&file).unwrap();
+        std::fs::create_dir_all(file.join("blocked")).unwrap();
+        assert!(manager.remove_connection("lite").await.is_err());
+        assert!(manager.get_connection("lite").await.is_some());
+        assert!(manager.get_pool("lite").await.is_some());
+        std::fs::remove_dir_all(&file).unwrap();
+
+        manager.remove_connection("down").await.unwrap();
+        assert_eq!(store().load().await.unwrap().configs.len(), 1);
+        let _ = std::fs::remove_dir_all(dir);
     }
 
-    fn build_redis_url(&self, config: &ConnectionConfig) -> AppResult<String> {
-        let host = config
-            .host
-            .as_deref()
-            .ok_or_else(|| AppError::Validation("Redis requires host".into()))?;
-        let port = config.port.unwrap_or(6379);
+    #[tokio::test]
+    async fn test_update_swaps_pool_after_drain() {
+        let dir = std::env::temp_dir().join(format!("pools-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = |name: &str| Some(dir.join(name).to_string_lossy().to_string());
+        let manager = PoolManager::new(AppConfig { connect_timeout_secs: 1, ..AppConfig::load() });
+        manager
+            .add_connection(ConnectionConfig { file_path: file("a.db"), ..connection("lite", DbType::SQLite) })
+            .await
+            .unwrap();
+        let DatabasePool::SQLite(old) = manager.get_pool("lite").await.unwrap() else { unreachable!() };
+        let in_flight = old.acquire().await.unwrap();
 
-        if let Some(password) = &config.password {
-            Ok(format!("redis://:{}@{}:{}", password, host, port))
-        } else {
-            Ok(format!("redis://{}:{}", host, port))
+        // 端点不变时不重建连接池
+        let rename: UpdateConnectionRequest = serde_json::from_str(r#"{"name": "renamed", "host": null}"#).unwrap();
+        manager.update_connection(rename.apply(manager.get_connection("lite").await.unwrap())).await.unwrap();
+        assert_eq!(manager.get_connection("lite").await.unwrap().name, "renamed");
+        let DatabasePool::SQLite(same) = manager.get_pool("lite").await.unwrap() else { unreachable!() };
+        assert_eq!(same.connect_options().get_filename(), old.connect_options().get_filename());
+
+        // 新配置无法连接时保持原样
+        let broken = ConnectionConfig {
+            db_type: DbType::Postgres,
+            host: Some("127.0.0.1".into()),
+            port: Some(1),
+            ..manager.get_connection("lite").await.unwrap()
+        };
+        assert!(manager.update_connection(broken).await.is_err());
+        assert_eq!(manager.get_connection("lite").await.unwrap().db_type, DbType::SQLite);
+
+        let moved = ConnectionConfig { file_path: file("b.db"), ..manager.get_connection("lite").await.unwrap() };
+        manager.update_connection(moved).await.unwrap();
+        let DatabasePool::SQLite(new) = manager.get_pool("lite").await.unwrap() else { unreachable!() };
+        assert!(new.connect_options().get_filename().ends_with("b.db"));
+
+        // 旧连接池等进行中的查询结束后才关闭
+        tokio::time::sleep(DRAIN_POLL * 2).await;
+        assert!(!old.is_closed());
+        drop(in_flight);
+        for _ in 0..20 {
+            if old.is_closed() {
+                break;
+            }
+            tokio::time::sleep(DRAIN_POLL).await;
+        }
+        assert!(old.is_closed());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn test_idle_pool_is_evicted_and_reopened() {
+        let dir = std::env::temp_dir().join(format!("pools-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let manager = Arc::new(PoolManager::new(AppConfig { pool_evict_after_secs: 0, ..AppConfig::load() }));
+        let lite = ConnectionConfig {
+            file_path: Some(dir.join("a.db").to_string_lossy().to_string()),
+            pool: PoolOptions { evict_after_secs: Some(1), ..Default::default() },
+            ..connection("lite", DbType::SQLite)
+        };
+        manager.configs.write().await.insert("lite".into(), lite);
+
+        // 并发首次使用只打开一个连接池
+        let tasks: Vec<_> = (0..4)
+            .map(|_| {
+                let manager = manager.clone();
+                tokio::spawn(async move { manager.pool("lite").await.unwrap() })
+            })
+            .collect();
+        let mut opened = Vec::new();
+        for task in tasks {
+            let DatabasePool::SQLite(pool) = task.await.unwrap() else { unreachable!() };
+            opened.push(pool);
         }
+
+        // 使用中的连接池不回收
+        let in_flight = opened[0].acquire().await.unwrap();
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+        assert_eq!(manager.evict_idle().await, 0);
+        drop(in_flight);
+        // 连接异步归还到池中
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(manager.evict_idle().await, 1);
+        assert!(opened.iter().all(|pool| pool.is_closed()));
+        assert_eq!(manager.status("lite").await, (ConnectionStatus::Idle, None));
+
+        assert!(manager.pool("lite").await.is_ok());
+        assert_eq!(manager.status("lite").await.0, ConnectionStatus::Connected);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn test_exhausted_pool_reports_degraded() {
+        let dir = std::env::temp_dir().join(format!("pools-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let manager = PoolManager::new(AppConfig::load());
+        let lite = ConnectionConfig {
+            file_path: Some(dir.join("a.db").to_string_lossy().to_string()),
+            pool: PoolOptions { acquire_timeout_secs: Some(1), ..Default::default() },
+            ..connection("lite", DbType::SQLite)
+        };
+        manager.add_connection(lite).await.unwrap();
+        manager.probe("lite").await.unwrap();
+
+        let DatabasePool::SQLite(pool) = manager.get_pool("lite").await.unwrap() else { unreachable!() };
+        let held = pool.acquire().await.unwrap();
+        let err = manager.probe("lite").await.unwrap_err();
+        assert!(matches!(err, AppError::PoolExhausted(_)));
+        assert_eq!(err.response_code(), common::response::code::DB_POOL_EXHAUSTED);
+
+        let stats = manager.stats("lite").await.unwrap();
+        assert_eq!(stats.status, ConnectionStatus::Degraded);
+        assert_eq!((stats.size, stats.in_use, stats.max_connections), (1, 1, 1));
+        assert_eq!((stats.acquire.count, stats.acquire.timeouts), (2, 1));
+        assert!(stats.last_error.is_some());
+
+        drop(held);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        manager.probe("lite").await.unwrap();
+        let stats = manager.stats("lite").await.unwrap();
+        assert_eq!(stats.status, ConnectionStatus::Connected);
+        assert!(stats.last_probe_ms.is_some());
+        let _ = std::fs::remove_dir_all(dir);
     }
 }
@@ -0,0 +1,631 @@
+//! 列数据画像服务
+//!
+//! 统计表中每列的数据特征：
+//! - 空值数量与比例、不同值数量（精确或基于统计信息估算）
+//! - 最小/最大值、数值列平均值、文本列长度
+//! - 高频值 Top-N，数值和日期列的等宽直方图
+//!
+//! 可只统计抽样的行。大表（估算行数达到阈值且未抽样）作为后台任务运行，
+//! 通过任务 ID 查询结果。
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
+
+use chrono::{DateTime, Utc};
+use common::errors::{AppError, AppResult};
+use common::models::metadata::{ColumnItem, TableDetail, TableKind};
+use common::models::profile::{
+    ColumnProfile, DistinctMode, HistogramBucket, ProfileColumnKind, ProfileJob, ProfileJobStatus,
+    ProfileRequest, TableProfile, ValueFrequency,
+};
+use serde_json::{Number, Value};
+use tokio::sync::RwLock;
+use validator::Validate;
+
+use crate::dialect::Dialect;
+use crate::metadata::MetadataService;
+use crate::pool_manager::{DatabasePool, PoolManager};
+use crate::sql_builder::{select_expr, SqlBuilder, SqlParam, ValueKind};
+
+/// 默认高频值数量
+const DEFAULT_TOP_N: u32 = 10;
+
+/// 默认直方图桶数
+const DEFAULT_BUCKETS: u32 = 10;
+
+/// 估算行数达到该值且未抽样时视为大表：后台运行，默认估算不同值数量
+const LARGE_TABLE_ROWS: i64 = 1_000_000;
+
+/// 内存中保留的最多任务数，超出时淘汰最早结束的任务
+const MAX_JOBS: usize = 100;
+
+// ============== 任务存储 ==============
+
+/// 画像任务存储（内存）
+#[derive(Default)]
+pub struct ProfileJobStore {
+    jobs: RwLock<HashMap<String, ProfileJob>>,
+}
+
+impl ProfileJobStore {
+    /// 保存任务，超出上限时淘汰最早结束的任务
+    async fn insert(&self, job: ProfileJob) {
+        let mut jobs = self.jobs.write().await;
+        jobs.insert(job.id.clone(), job);
+        while jobs.len() > MAX_JOBS {
+            let oldest = jobs
+                .values()
+                .filter(|j| j.status != ProfileJobStatus::Running)
+                .min_by(|a, b| a.created_at.cmp(&b.created_at))
+                .map(|j| j.id.clone());
+            match oldest {
+                Some(id) => jobs.remove(&id),
+                None => break,
+            };
+        }
+    }
+
+    /// 记录后台任务的结果
+    async fn finish(&self, id: &str, result: AppResult<TableProfile>) {
+        if let Some(job) = self.jobs.write().await.get_mut(id) {
+            job.finished_at = Some(Utc::now().to_rfc3339());
+            match result {
+                Ok(profile) => {
+                    job.status = ProfileJobStatus::Completed;
+                    job.result = Some(profile);
+                }
+                Err(e) => {
+                    job.status = ProfileJobStatus::Failed;
+                    job.error = Some(e.to_string());
+                }
+            }
+        }
+    }
+
+    /// 获取连接下的任务
+    async fn get(&self, connection_id: &str, id: &str) -> AppResult<ProfileJob> {
+        self.jobs
+            .read()
+            .await
+            .get(id)
+            .filter(|job| job.connection_id == connection_id)
+            .cloned()
+            .ok_or_else(|| AppError::NotFound(format!("profile job {}", id)))
+    }
+}
+
+// ============== 服务 ==============
+
+/// 列数据画像服务
+pub struct ProfileService {
+    pool_manager: Arc<PoolManager>,
+    jobs: Arc<ProfileJobStore>,
+}
+
+impl ProfileService {
+    /// 创建新的画像服务实例
+    pub fn new(pool_manager: Arc<PoolManager>, jobs: Arc<ProfileJobStore>) -> Self {
+        Self { pool_manager, jobs }
+    }
+
+    /// 开始画像：小表直接返回结果，大表或要求后台运行时返回运行中的任务
+    pub async fn start(&self, id: &str, table: &str, req: ProfileRequest) -> AppResult<ProfileJob> {
+        req.validate()?;
+        let pool = self.pool_manager.pool(id).await?;
+        let dialect = pool.dialect()?;
+        let detail = MetadataService::new(self.pool_manager.clone())
+            .table(id, req.schema.as_deref(), table)
+            .await?;
+
+        let columns = match &req.columns {
+            Some(names) if !names.is_empty() => names
+                .iter()
+                .map(|name| {
+                    detail.column(name).cloned().ok_or_else(|| {
+                        AppError::InvalidInput(format!("表 {} 中不存在列 {}", detail.table.name, name))
+                    })
+                })
+                .collect::<AppResult<Vec<_>>>()?,
+            _ => detail.columns.clone(),
+        };
+        let large = req.sample_rows.is_none() && detail.table.row_estimate.unwrap_or(0) >= LARGE_TABLE_ROWS;
+        let options = Options {
+            columns,
+            sample_rows: req.sample_rows,
+            top_n: req.top_n.unwrap_or(DEFAULT_TOP_N),
+            buckets: req.histogram_buckets.unwrap_or(DEFAULT_BUCKETS),
+            distinct: req
+                .distinct
+                .unwrap_or(if large { DistinctMode::Approximate } else { DistinctMode::Exact }),
+        };
+        let background = large || req.background.unwrap_or(false);
+
+        let mut job = ProfileJob {
+            id: uuid::Uuid::new_v4().to_string(),
+            connection_id: id.to_string(),
+            schema: detail.table.schema.clone(),
+            table: detail.table.name.clone(),
+            status: ProfileJobStatus::Running,
+            background,
+            created_at: Utc::now().to_rfc3339(),
+            finished_at: None,
+            error: None,
+            result: None,
+        };
+        if !background {
+            let profile = profile_table(&pool, dialect, &detail, &options).await?;
+            job.status = ProfileJobStatus::Completed;
+            job.finished_at = Some(Utc::now().to_rfc3339());
+            job.result = Some(profile);
+            self.jobs.insert(job.clone()).await;
+            return Ok(job);
+        }
+
+        self.jobs.insert(job.clone()).await;
+        let jobs = self.jobs.clone();
+        let job_id = job.id.clone();
+        tokio::spawn(async move {
+            let result = profile_table(&pool, dialect, &detail, &options).await;
+            if let Err(e) = &result {
+                tracing::warn!(job = %job_id, table = %detail.table.name, error = %e, "后台列画像失败");
+            }
+            jobs.finish(&job_id, result).await;
+        });
+        tracing::info!(id = %id, job = %job.id, table = %job.table, "后台列画像已开始");
+        Ok(job)
+    }
+
+    /// 查询画像任务
+    pub async fn job(&self, id: &str, job_id: &str) -> AppResult<ProfileJob> {
+        self.jobs.get(id, job_id).await
+    }
+}
+
+// ============== 统计 ==============
+
+/// 画像参数
+struct Options {
+    columns: Vec<ColumnItem>,
+    sample_rows: Option<u64>,
+    top_n: u32,
+    buckets: u32,
+    distinct: DistinctMode,
+}
+
+/// 统计整张表（或抽样）
+async fn profile_table(
+    pool: &DatabasePool,
+    dialect: Dialect,
+    detail: &TableDetail,
+    options: &Options,
+) -> AppResult<TableProfile> {
+    let started = Instant::now();
+    let source = source_sql(dialect, detail, options.sample_rows);
+
+    let mut count = SqlBuilder::new(dialect);
+    count.push("SELECT COUNT(*) FROM ").push(&source);
+    let row_count = count.fetch_count(pool).await?.max(0) as u64;
+
+    // 统计信息描述的是整张表，抽样时不适用
+    let estimates = match (options.distinct, options.sample_rows) {
+        (DistinctMode::Approximate, None) => catalog_distinct(pool, dialect, detail).await?,
+        _ => HashMap::new(),
+    };
+
+    let mut columns = Vec::with_capacity(options.columns.len());
+    for column in &options.columns {
+        let estimate = estimates.get(&column.name).copied();
+        columns.push(profile_column(pool, dialect, &source, column, row_count, estimate, options).await?);
+    }
+
+    Ok(TableProfile {
+        schema: detail.table.schema.clone(),
+        table: detail.table.name.clone(),
+        row_count,
+        sampled: options.sample_rows.is_some(),
+        columns,
+        profiled_at: Utc::now().to_rfc3339(),
+        elapsed_ms: started.elapsed().as_millis() as u64,
+    })
+}
+
+/// 统计的数据来源：整张表，或抽样子查询
+///
+/// PostgreSQL 的表按估算行数使用 `TABLESAMPLE BERNOULLI` 随机抽样；
+/// 其他情况取存储顺序上的前 N 行。
+fn source_sql(dialect: Dialect, detail: &TableDetail, sample_rows: Option<u64>) -> String {
+    let table_sql = dialect.qualified_table(Some(&detail.table.schema), &detail.table.name);
+    let Some(limit) = sample_rows else {
+        return table_sql;
+    };
+    match (dialect, detail.table.kind, detail.table.row_estimate) {
+        (Dialect::Postgres, TableKind::Table, Some(estimate)) if estimate > limit as i64 => {
+            // 多抽 10%，避免抽样不足 N 行
+            let percent = (limit as f64 * 110.0 / estimate as f64).min(100.0);
+            format!(
+                "(SELECT * FROM {} TABLESAMPLE BERNOULLI ({:.6}) LIMIT {}) AS s",
+                table_sql, percent, limit
+            )
+        }
+        _ => format!("(SELECT * FROM {} LIMIT {}) AS s", table_sql, limit),
+    }
+}
+
+/// 从统计信息读取各列的不同值数量估算
+async fn catalog_distinct(pool: &DatabasePool, dialect: Dialect, detail: &TableDetail) -> AppResult<HashMap<String, u64>> {
+    let mut builder = SqlBuilder::new(dialect);
+    let columns = [("name".to_string(), ValueKind::Text), ("n".to_string(), ValueKind::Number)];
+    match dialect {
+        // n_distinct 为负数时表示占总行数的比例
+        Dialect::Postgres => {
+            builder
+                .push("SELECT attname::text, n_distinct::text FROM pg_stats WHERE schemaname = ")
+                .push_param(SqlParam::Text(detail.table.schema.clone()), None)
+                .push(" AND tablename = ")
+                .push_param(SqlParam::Text(detail.table.name.clone()), None);
+        }
+        // 索引首列的基数即该列的不同值数量
+        Dialect::MySql => {
+            builder
+                .push("SELECT COLUMN_NAME, CAST(MAX(CARDINALITY) AS CHAR) FROM information_schema.STATISTICS WHERE TABLE_SCHEMA = ")
+                .push_param(SqlParam::Text(detail.table.schema.clone()), None)
+                .push(" AND TABLE_NAME = ")
+                .push_param(SqlParam::Text(detail.table.name.clone()), None)
+                .push(" AND SEQ_IN_INDEX = 1 GROUP BY COLUMN_NAME");
+        }
+        Dialect::Sqlite => return Ok(HashMap::new()),
+    }
+    let rows = builder.fetch_rows(pool, &columns).await?;
+    let total = detail.table.row_estimate.unwrap_or(0).max(0) as f64;
+    Ok(rows
+        .iter()
+        .filter_map(|row| {
+            let name = row.get("name")?.as_str()?.to_string();
+            let n = row.get("n")?.as_f64()?;
+            let distinct = if n < 0.0 { -n * total } else { n };
+            Some((name, distinct.round() as u64))
+        })
+        .collect())
+}
+
+/// 统计单列
+async fn profile_column(
+    pool: &DatabasePool,
+    dialect: Dialect,
+    source: &str,
+    column: &ColumnItem,
+    row_count: u64,
+    distinct_estimate: Option<u64>,
+    options: &Options,
+) -> AppResult<ColumnProfile> {
+    let value_kind = ValueKind::of(dialect, &column.data_type);
+    let kind = profile_kind(value_kind, &column.data_type);
+    let ident = dialect.quote_ident(&column.name);
+    // PostgreSQL 中 json 等类型没有相等运算符，按文本比较
+    let value = match (dialect, kind) {
+        (Dialect::Postgres, ProfileColumnKind::Other) => format!("{}::text", ident),
+        _ => ident.clone(),
+    };
+    let epoch = match kind {
+        ProfileColumnKind::Temporal => epoch_expr(dialect, &ident, &column.data_type),
+        _ => None,
+    };
+
+    // 聚合：(别名, 表达式, 解码方式)
+    let mut aggregates: Vec<(&str, String, ValueKind)> = vec![("non_null", format!("COUNT({})", value), ValueKind::Integer)];
+    if distinct_estimate.is_none() {
+        aggregates.push(("distinct_count", format!("COUNT(DISTINCT {})", value), ValueKind::Integer));
+    }
+    let min_max_kind = match kind {
+        ProfileColumnKind::Numeric => Some(ValueKind::Number),
+        ProfileColumnKind::Text | ProfileColumnKind::Temporal => Some(ValueKind::Text),
+        _ => None,
+    };
+    if let Some(decode) = min_max_kind {
+        aggregates.push(("min", text_expr(dialect, &format!("MIN({})", ident)), decode));
+        aggregates.push(("max", text_expr(dialect, &format!("MAX({})", ident)), decode));
+    }
+    if kind == ProfileColumnKind::Numeric {
+        aggregates.push(("avg", text_expr(dialect, &format!("AVG({})", ident)), ValueKind::Number));
+    }
+    if kind == ProfileColumnKind::Text {
+        let length = match dialect {
+            Dialect::MySql => format!("CHAR_LENGTH({})", ident),
+            Dialect::Postgres => format!("LENGTH({}::text)", ident),
+            Dialect::Sqlite => format!("LENGTH({})", ident),
+        };
+        aggregates.push(("min_length", text_expr(dialect, &format!("MIN({})", length)), ValueKind::Number));
+        aggregates.push(("max_length", text_expr(dialect, &format!("MAX({})", length)), ValueKind::Number));
+        aggregates.push(("avg_length", text_expr(dialect, &format!("AVG({})", length)), ValueKind::Number));
+    }
+    if let Some(epoch) = &epoch {
+        aggregates.push(("min_epoch", text_expr(dialect, &format!("MIN({})", epoch)), ValueKind::Number));
+        aggregates.push(("max_epoch", text_expr(dialect, &format!("MAX({})", epoch)), ValueKind::Number));
+    }
+
+    let mut builder = SqlBuilder::new(dialect);
+    let select: Vec<String> = aggregates
+        .iter()
+        .map(|(alias, expr, _)| format!("{} AS {}", expr, alias))
+        .collect();
+    builder.push("SELECT ").push(&select.join(", ")).push(" FROM ").push(source);
+    let decode: Vec<(String, ValueKind)> = aggregates
+        .iter()
+        .map(|(alias, _, kind)| (alias.to_string(), *kind))
+        .collect();
+    let stats = builder.fetch_rows(pool, &decode).await?.pop().unwrap_or(Value::Null);
+    let number = |key: &str| stats.get(key).and_then(Value::as_f64);
+    let present = |key: &str| stats.get(key).filter(|v| !v.is_null()).cloned();
+
+    let non_null = number("non_null").unwrap_or(0.0) as u64;
+    let null_count = row_count.saturating_sub(non_null);
+    let top_values = top_values(pool, dialect, source, &column.name, value_kind, options.top_n).await?;
+    let histogram = match (kind, &epoch) {
+        (ProfileColumnKind::Numeric, _) => match (number("min"), number("max")) {
+            (Some(min), Some(max)) => {
+                let value = match dialect {
+                    Dialect::Postgres => format!("CAST({} AS double precision)", ident),
+                    _ => ident.clone(),
+                };
+                let counts = histogram(pool, dialect, source, &ident, &value, min, max, non_null, options.buckets).await?;
+                Some(buckets(min, max, counts, |v| Number::from_f64(v).map(Value::Number).unwrap_or(Value::Null)))
+            }
+            _ => None,
+        },
+        (ProfileColumnKind::Temporal, Some(epoch)) => match (number("min_epoch"), number("max_epoch")) {
+            (Some(min), Some(max)) => {
+                let counts = histogram(pool, dialect, source, &ident, epoch, min, max, non_null, options.buckets).await?;
+                Some(buckets(min, max, counts, epoch_to_json))
+            }
+            _ => None,
+        },
+        _ => None,
+    };
+
+    Ok(ColumnProfile {
+        name: column.name.clone(),
+        data_type: column.data_type.clone(),
+        kind,
+        null_count,
+        null_ratio: if row_count == 0 { 0.0 } else { null_count as f64 / row_count as f64 },
+        distinct_count: distinct_estimate.or_else(|| number("distinct_count").map(|n| n as u64)),
+        distinct_approximate: distinct_estimate.is_some(),
+        min: present("min"),
+        max: present("max"),
+        avg: number("avg"),
+        min_length: number("min_length").map(|n| n as u64),
+        max_length: number("max_length").map(|n| n as u64),
+        avg_length: number("avg_length"),
+        top_values,
+        histogram,
+    })
+}
+
+/// 高频值，频次降序
+async fn top_values(
+    pool: &DatabasePool,
+    dialect: Dialect,
+    source: &str,
+    column: &str,
+    kind: ValueKind,
+    limit: u32,
+) -> AppResult<Vec<ValueFrequency>> {
+    let mut builder = SqlBuilder::new(dialect);
+    builder
+        .push("SELECT ")
+        .push(&select_expr(dialect, column, kind))
+        .push(", COUNT(*) AS frequency FROM ")
+        .push(source)
+        .push(" WHERE ")
+        .push_ident(column)
+        .push(&format!(" IS NOT NULL GROUP BY 1 ORDER BY 2 DESC, 1 LIMIT {}", limit));
+    let decode = [("value".to_string(), kind), ("frequency".to_string(), ValueKind::Integer)];
+    Ok(builder
+        .fetch_rows(pool, &decode)
+        .await?
+        .into_iter()
+        .map(|row| ValueFrequency {
+            count: row.get("frequency").and_then(Value::as_u64).unwrap_or(0),
+            value: row.get("value").cloned().unwrap_or(Value::Null),
+        })
+        .collect())
+}
+
+/// 等宽直方图各桶的行数；`value` 为参与分桶的数值表达式
+#[allow(clippy::too_many_arguments)]
+async fn histogram(
+    pool: &DatabasePool,
+    dialect: Dialect,
+    source: &str,
+    column: &str,
+    value: &str,
+    min: f64,
+    max: f64,
+    non_null: u64,
+    buckets: u32,
+) -> AppResult<Vec<u64>> {
+    if max <= min {
+        return Ok(vec![non_null]);
+    }
+    let last = buckets - 1;
+    let width = (max - min) / buckets as f64;
+    let offset = format!("({} - {}) / {}", value, float_literal(min), float_literal(width));
+    let floor = match dialect {
+        // 偏移量非负，截断即向下取整
+        Dialect::Sqlite => format!("CAST({} AS INTEGER)", offset),
+        _ => format!("FLOOR({})", offset),
+    };
+    let mut builder = SqlBuilder::new(dialect);
+    builder.push(&format!(
+        "SELECT {} AS bucket, COUNT(*) AS frequency FROM (SELECT CASE WHEN {} >= {} THEN {} ELSE {} END AS bucket FROM {} WHERE {} IS NOT NULL) h GROUP BY bucket",
+        text_expr(dialect, "bucket"),
+        value,
+        float_literal(max),
+        last,
+        floor,
+        source,
+        column,
+    ));
+    let decode = [("bucket".to_string(), ValueKind::Number), ("frequency".to_string(), ValueKind::Integer)];
+    let mut counts = vec![0; buckets as usize];
+    for row in builder.fetch_rows(pool, &decode).await? {
+        let bucket = row.get("bucket").and_then(Value::as_f64);
+        let frequency = row.get("frequency").and_then(Value::as_u64).unwrap_or(0);
+        if let Some(bucket) = bucket {
+            counts[(bucket.max(0.0) as usize).min(last as usize)] += frequency;
+        }
+    }
+    Ok(counts)
+}
+
+/// 根据各桶行数生成桶边界
+fn buckets(min: f64, max: f64, counts: Vec<u64>, bound: impl Fn(f64) -> Value) -> Vec<HistogramBucket> {
+    let width = (max - min) / counts.len() as f64;
+    let last = counts.len() - 1;
+    counts
+        .into_iter()
+        .enumerate()
+        .map(|(i, count)| HistogramBucket {
+            lower: bound(min + width * i as f64),
+            upper: bound(if i == last { max } else { min + width * (i + 1) as f64 }),
+            count,
+        })
+        .collect()
+}
+
+/// 按值类别和原生类型确定统计类别
+fn profile_kind(value_kind: ValueKind, data_type: &str) -> ProfileColumnKind {
+    let lower = data_type.trim().to_lowercase();
+    let base = lower.split(['(', ' ']).next().unwrap_or_default();
+    match value_kind {
+        ValueKind::Integer | ValueKind::Unsigned | ValueKind::Number => ProfileColumnKind::Numeric,
+        ValueKind::Boolean => ProfileColumnKind::Boolean,
+        ValueKind::Json | ValueKind::Binary => ProfileColumnKind::Other,
+        ValueKind::Text => match base {
+            "date" | "datetime" | "timestamp" | "timestamptz" | "time" | "timetz" => ProfileColumnKind::Temporal,
+            "char" | "character" | "varchar" | "nchar" | "nvarchar" | "text" | "tinytext" | "mediumtext"
+            | "longtext" | "clob" | "enum" | "set" | "citext" => ProfileColumnKind::Text,
+            _ => ProfileColumnKind::Other,
+        },
+    }
+}
+
+/// 日期列转换为 Unix 秒数的表达式；仅含时刻的列不做直方图
+fn epoch_expr(dialect: Dialect, ident: &str, data_type: &str) -> Option<String> {
+    let lower = data_type.trim().to_lowercase();
+    if lower.starts_with("time") && !lower.starts_with("timestamp") {
+        return None;
+    }
+    Some(match dialect {
+        Dialect::Postgres => format!("EXTRACT(EPOCH FROM {})", ident),
+        Dialect::MySql => format!("TIMESTAMPDIFF(SECOND, '1970-01-01', {})", ident),
+        Dialect::Sqlite => format!("CAST(strftime('%s', {}) AS INTEGER)", ident),
+    })
+}
+
+/// 将 Unix 秒数转换为 RFC 3339 时间（精确到微秒）
+fn epoch_to_json(seconds: f64) -> Value {
+    let micros = (seconds * 1e6).round() as i64;
+    DateTime::<Utc>::from_timestamp_micros(micros)
+        .map(|t| Value::String(t.to_rfc3339()))
+        .unwrap_or(Value::Null)
+}
+
+/// 转换为文本的表达式，统一按文本解码聚合结果
+fn text_expr(dialect: Dialect, expr: &str) -> String {
+    match dialect {
+        Dialect::MySql => format!("CAST({} AS CHAR)", expr),
+        Dialect::Postgres => format!("({})::text", expr),
+        Dialect::Sqlite => expr.to_string(),
+    }
+}
+
+/// 浮点数字面量（始终带小数点，避免整数除法）
+fn float_literal(value: f64) -> String {
+    let text = value.to_string();
+    if text.contains(['.', 'e', 'E']) {
+        text
+    } else {
+        format!("{}.0", text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    #[tokio::test]
+    async fn test_sqlite_profile() {
+        let (manager, pool) = test_support::sqlite_manager(test_support::sqlite_connection("profile")).await;
+        sqlx::query("CREATE TABLE events (id INTEGER PRIMARY KEY, kind TEXT, amount REAL, at DATETIME)")
+            .execute(&pool)
+            .await
+            .unwrap();
+        for (id, kind, amount, at) in [
+            (1, Some("click"), Some(1.0), "2024-01-01 00:00:00"),
+            (2, Some("click"), Some(2.0), "2024-01-02 00:00:00"),
+            (3, Some("view"), None, "2024-01-03 00:00:00"),
+            (4, None, Some(11.0), "2024-01-05 00:00:00"),
+        ] {
+            sqlx::query("INSERT INTO events VALUES (?, ?, ?, ?)")
+                .bind(id)
+                .bind(kind)
+                .bind(amount)
+                .bind(at)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        let service = ProfileService::new(manager, Arc::new(ProfileJobStore::default()));
+        let req = ProfileRequest {
+            histogram_buckets: Some(2),
+            ..Default::default()
+        };
+        let job = service.start("profile", "events", req).await.unwrap();
+        assert_eq!(job.status, ProfileJobStatus::Completed);
+        assert_eq!(service.job("profile", &job.id).await.unwrap().id, job.id);
+        let profile = job.result.unwrap();
+        assert_eq!(profile.row_count, 4);
+
+        let kind = &profile.columns[1];
+        assert_eq!(kind.kind, ProfileColumnKind::Text);
+        assert_eq!((kind.null_count, kind.null_ratio), (1, 0.25));
+        assert_eq!(kind.distinct_count, Some(2));
+        assert_eq!((kind.min_length, kind.max_length), (Some(4), Some(5)));
+        assert_eq!(kind.top_values[0].value, "click");
+        assert_eq!(kind.top_values[0].count, 2);
+
+        let amount = &profile.columns[2];
+        assert_eq!(amount.kind, ProfileColumnKind::Numeric);
+        assert_eq!(amount.avg, Some(14.0 / 3.0));
+        let histogram = amount.histogram.as_ref().unwrap();
+        assert_eq!(histogram.iter().map(|b| b.count).collect::<Vec<_>>(), vec![2, 1]);
+        assert_eq!(histogram[1].upper, 11.0);
+
+        let at = &profile.columns[3];
+        assert_eq!(at.kind, ProfileColumnKind::Temporal);
+        let histogram = at.histogram.as_ref().unwrap();
+        assert_eq!(histogram.iter().map(|b| b.count).collect::<Vec<_>>(), vec![2, 2]);
+        assert_eq!(histogram[0].lower, "2024-01-01T00:00:00+00:00");
+
+        let sampled = service
+            .start(
+                "profile",
+                "events",
+                ProfileRequest {
+                    sample_rows: Some(2),
+                    columns: Some(vec!["kind".into()]),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap()
+            .result
+            .unwrap();
+        assert!(sampled.sampled);
+        assert_eq!((sampled.row_count, sampled.columns.len()), (2, 1));
+    }
+}
@@ -1,6 +1,6 @@
 //! 连接服务路由模块
 
-use axum::{routing::get, Router};
+use axum::{routing::{get, post}, Router};
 use crate::handlers;
 use crate::state::AppState;
 
@@ -8,8 +8,52 @@ use crate::state::AppState;
 pub fn router() -> Router<AppState> {
     Router::new()
         .route("/api/connections", get(handlers::list_connections).post(handlers::create_connection))
-        .route("/api/connections/{id}", get(handlers::get_connection).delete(handlers::delete_connection))
+        .route("/api/connections/diff", post(handlers::diff_schemas))
+        .route("/api/connections/test", post(handlers::dry_run_connection))
+        .route("/api/connections/pools", get(handlers::list_pool_stats))
+        .route("/api/connections/health", get(handlers::list_connection_health))
+        .route("/api/connections/export", post(handlers::export_connections))
+        .route("/api/connections/import", post(handlers::import_connections))
+        .route(
+            "/api/connections/{id}",
+            get(handlers::get_connection)
+                .put(handlers::replace_connection)
+                .patch(handlers::update_connection)
+                .delete(handlers::delete_connection),
+        )
         .route("/api/connections/{id}/test", get(handlers::test_connection))
+        .route("/api/connections/{id}/dsn", get(handlers::export_dsn))
+        .route("/api/connections/{id}/pool", get(handlers::get_pool_stats))
+        .route("/api/connections/{id}/health", get(handlers::get_connection_health))
+        .route("/api/connections/{id}/databases", get(handlers::list_databases))
+        .route("/api/connections/{id}/schemas", get(handlers::list_schemas))
+        .route("/api/connections/{id}/tables", get(handlers::list_tables))
+        .route("/api/connections/{id}/tables/{table}", get(handlers::get_table))
+        .route("/api/connections/{id}/tables/{table}/ddl", get(handlers::get_table_ddl))
+        .route("/api/connections/{id}/tables/{table}/rows", get(handlers::get_table_rows).post(handlers::apply_row_changes))
+        .route("/api/connections/{id}/tables/{table}/profile", post(handlers::profile_table))
+        .route("/api/connections/{id}/profile-jobs/{job_id}", get(handlers::get_profile_job))
+        .route("/api/connections/{id}/catalog", get(handlers::get_catalog))
+        .route("/api/connections/{id}/er-diagram", get(handlers::get_er_diagram))
+        .route("/api/connections/{id}/activity", get(handlers::get_activity))
+        .route("/api/connections/{id}/activity/{pid}/kill", post(handlers::kill_session))
+        .route("/api/connections/{id}/locks", get(handlers::get_locks))
+        .route("/api/connections/{id}/storage", get(handlers::get_storage_stats))
+        .route("/api/connections/{id}/schema-cache/refresh", post(handlers::refresh_schema_cache))
+        .route("/api/connections/{id}/snapshots", get(handlers::list_snapshots).post(handlers::take_snapshot))
+        .route("/api/connections/{id}/snapshots/timeline", get(handlers::get_snapshot_timeline))
+        .route(
+            "/api/connections/{id}/snapshots/schedule",
+            get(handlers::get_snapshot_schedule)
+                .put(handlers::set_snapshot_schedule)
+                .delete(handlers::delete_snapshot_schedule),
+        )
+        .route("/api/connections/{id}/snapshots/{version}", get(handlers::get_snapshot))
+        .route("/api/sql/complete", post(handlers::complete_sql))
+        .route("/api/audit", get(handlers::list_audit_entries))
+        .route("/api/secrets/rotate", post(handlers::rotate_master_key))
+        .route("/api/search", get(handlers::search_metadata))
+        .route("/api/search/refresh", post(handlers::refresh_search_index))
         .route("/api/health", get(handlers::health_check))
         .route("/internal/pools/{id}", get(handlers::get_pool_info))
         // Trait 演示接口
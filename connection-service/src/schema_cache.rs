@@ -0,0 +1,136 @@
+//! Schema 缓存
+//!
+//! 按连接和 schema 缓存完整的表结构，供 SQL 补全等高频功能使用，
+//! 避免每次按键都查询数据库。条目在 TTL 到期后重新加载，也可显式刷新。
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use common::errors::AppResult;
+use common::models::completion::SchemaCacheInfo;
+use common::models::metadata::TableDetail;
+use tokio::sync::RwLock;
+
+use crate::metadata::MetadataService;
+use crate::pool_manager::PoolManager;
+
+/// 缓存的 schema 模型
+pub struct SchemaModel {
+    /// schema 名称
+    pub schema: String,
+    /// 服务器上的所有 schema 名称
+    pub schemas: Vec<String>,
+    /// schema 中所有表和视图的结构
+    pub tables: Vec<TableDetail>,
+    /// 加载时间
+    pub loaded_at: String,
+}
+
+impl SchemaModel {
+    /// 按名称查找表（不区分大小写）
+    pub fn table(&self, name: &str) -> Option<&TableDetail> {
+        self.tables
+            .iter()
+            .find(|t| t.table.name == name)
+            .or_else(|| self.tables.iter().find(|t| t.table.name.eq_ignore_ascii_case(name)))
+    }
+
+    /// 是否存在该 schema（不区分大小写）
+    pub fn has_schema(&self, name: &str) -> bool {
+        self.schemas.iter().any(|s| s.eq_ignore_ascii_case(name))
+    }
+}
+
+/// 缓存条目
+struct Entry {
+    model: Arc<SchemaModel>,
+    expires_at: Instant,
+}
+
+/// 按 (连接 ID, 请求的 schema) 缓存的 schema 模型；schema 为空表示连接的默认 schema
+pub struct SchemaCache {
+    ttl: Duration,
+    entries: RwLock<HashMap<(String, Option<String>), Entry>>,
+}
+
+impl SchemaCache {
+    /// 创建指定有效期的缓存
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// 获取 schema 模型，未缓存或已过期时从数据库加载
+    pub async fn get(
+        &self,
+        pool_manager: &Arc<PoolManager>,
+        id: &str,
+        schema: Option<&str>,
+    ) -> AppResult<Arc<SchemaModel>> {
+        let key = (id.to_string(), schema.map(str::to_string));
+        if let Some(entry) = self.entries.read().await.get(&key) {
+            if entry.expires_at > Instant::now() {
+                return Ok(entry.model.clone());
+            }
+        }
+        self.load(pool_manager, key).await
+    }
+
+    /// 丢弃缓存并重新加载
+    pub async fn refresh(&self, pool_manager: &Arc<PoolManager>, id: &str, schema: Option<&str>) -> AppResult<SchemaCacheInfo> {
+        let key = (id.to_string(), schema.map(str::to_string));
+        let model = self.load(pool_manager, key.clone()).await?;
+        let expires_in_secs = self
+            .entries
+            .read()
+            .await
+            .get(&key)
+            .map(|e| e.expires_at.saturating_duration_since(Instant::now()).as_secs())
+            .unwrap_or_default();
+        Ok(SchemaCacheInfo {
+            connection_id: id.to_string(),
+            schema: model.schema.clone(),
+            schemas: model.schemas.len(),
+            tables: model.tables.len(),
+            columns: model.tables.iter().map(|t| t.columns.len()).sum(),
+            loaded_at: model.loaded_at.clone(),
+            expires_in_secs,
+        })
+    }
+
+    /// 丢弃连接的全部缓存条目
+    pub async fn invalidate(&self, id: &str) {
+        self.entries.write().await.retain(|(connection_id, _), _| connection_id != id);
+    }
+
+    /// 从数据库加载并写入缓存（加载期间不持有锁）
+    async fn load(&self, pool_manager: &Arc<PoolManager>, key: (String, Option<String>)) -> AppResult<Arc<SchemaModel>> {
+        let metadata = MetadataService::new(pool_manager.clone());
+        let (id, requested) = (&key.0, key.1.as_deref());
+        let schemas = metadata.schemas(id).await?;
+        let schema = match requested {
+            Some(schema) => schema.to_string(),
+            None => metadata.current_schema(id).await?,
+        };
+        let tables = metadata.schema_details(id, Some(&schema)).await?;
+        let model = Arc::new(SchemaModel {
+            schema,
+            schemas: schemas.into_iter().map(|s| s.name).collect(),
+            tables,
+            loaded_at: Utc::now().to_rfc3339(),
+        });
+        tracing::debug!(id = %id, schema = %model.schema, tables = model.tables.len(), "schema 缓存已加载");
+        self.entries.write().await.insert(
+            key,
+            Entry {
+                model: model.clone(),
+                expires_at: Instant::now() + self.ttl,
+            },
+        );
+        Ok(model)
+    }
+}
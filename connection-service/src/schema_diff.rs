@@ -0,0 +1,754 @@
+//! Schema 对比服务
+//!
+//! 比较两个连接（可指定 schema）中的表、列、类型、默认值、主键、索引和外键，
+//! 生成结构化差异，以及按目标方言编写的、使目标与源一致的有序迁移脚本。
+//!
+//! 视图不参与比较。索引按列和唯一性匹配，外键按列和被引用列匹配，名称不同不视为差异。
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use common::errors::AppResult;
+use common::models::metadata::{ColumnItem, ForeignKeyItem, IndexItem, TableDetail, TableKind};
+use common::models::schema_diff::{
+    ChangeKind, ColumnDiff, ForeignKeyDiff, IndexDiff, MigrationStep, PrimaryKeyDiff, SchemaDiff,
+    SchemaDiffRequest, TableDiff,
+};
+
+use crate::ddl::{DdlWriter, RenderedDdl};
+use crate::dialect::Dialect;
+use crate::metadata::MetadataService;
+use crate::pool_manager::PoolManager;
+
+/// Schema 对比服务
+pub struct SchemaDiffService {
+    pool_manager: Arc<PoolManager>,
+}
+
+impl SchemaDiffService {
+    /// 创建新的 Schema 对比服务实例
+    pub fn new(pool_manager: Arc<PoolManager>) -> Self {
+        Self { pool_manager }
+    }
+
+    /// 比较源和目标 schema，生成差异和迁移脚本
+    pub async fn diff(&self, req: SchemaDiffRequest) -> AppResult<SchemaDiff> {
+        let source = self.dialect(&req.source_id).await?;
+        let target = self.dialect(&req.target_id).await?;
+        let metadata = MetadataService::new(self.pool_manager.clone());
+
+        let source_schema = match req.source_schema {
+            Some(schema) => schema,
+            None => metadata.current_schema(&req.source_id).await?,
+        };
+        let explicit_target = req.target_schema.is_some();
+        let target_schema = match req.target_schema {
+            Some(schema) => schema,
+            None => metadata.current_schema(&req.target_id).await?,
+        };
+
+        let source_tables = metadata.schema_details(&req.source_id, Some(&source_schema)).await?;
+        let target_tables = metadata.schema_details(&req.target_id, Some(&target_schema)).await?;
+
+        let mut differ = Differ::new(source, target);
+        if explicit_target {
+            differ.use_schema(&target_schema);
+        }
+        let tables = differ.compare(&source_tables, &target_tables);
+        let migration = differ.into_steps();
+
+        Ok(SchemaDiff {
+            source_id: req.source_id,
+            source_schema,
+            target_id: req.target_id,
+            target_schema,
+            dialect: target.db_type(),
+            identical: tables.is_empty(),
+            destructive_steps: migration.0.iter().filter(|s| s.destructive).count(),
+            tables,
+            migration: migration.0,
+            warnings: migration.1,
+        })
+    }
+
+    async fn dialect(&self, id: &str) -> AppResult<Dialect> {
+        self.pool_manager.pool(id).await?.dialect()
+    }
+}
+
+/// 比较同一方言的两组表结构，返回从 `old` 到 `new` 的变化
+///
+/// `Added` 表示只存在于 `new` 中；差异中的 `source` 为新结构，`target` 为旧结构。
+pub fn diff_tables(dialect: Dialect, old: &[TableDetail], new: &[TableDetail]) -> Vec<TableDiff> {
+    Differ::new(dialect, dialect).compare(new, old)
+}
+
+// ============== 差异计算 ==============
+
+/// 迁移阶段，按声明顺序执行
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Phase {
+    UseSchema,
+    DropForeignKeys,
+    DropIndexes,
+    DropPrimaryKeys,
+    CreateTables,
+    AddColumns,
+    AlterColumns,
+    DropColumns,
+    AddPrimaryKeys,
+    CreateIndexes,
+    AddForeignKeys,
+    DropTables,
+}
+
+struct Differ {
+    source: Dialect,
+    target: Dialect,
+    writer: DdlWriter,
+    steps: Vec<(Phase, MigrationStep)>,
+    warnings: Vec<String>,
+}
+
+impl Differ {
+    fn new(source: Dialect, target: Dialect) -> Self {
+        Self {
+            source,
+            target,
+            writer: DdlWriter::new(source, target),
+            steps: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, phase: Phase, description: String, sql: String, destructive: bool) {
+        self.steps.push((
+            phase,
+            MigrationStep {
+                order: 0,
+                description,
+                sql: format!("{};", sql),
+                destructive,
+            },
+        ));
+    }
+
+    /// 按阶段排序并编号，返回 (步骤, 警告)
+    fn into_steps(mut self) -> (Vec<MigrationStep>, Vec<String>) {
+        self.steps.sort_by_key(|(phase, _)| *phase);
+        let steps = self
+            .steps
+            .into_iter()
+            .enumerate()
+            .map(|(i, (_, mut step))| {
+                step.order = i as u32 + 1;
+                step
+            })
+            .collect();
+        (steps, self.warnings)
+    }
+
+    fn ident(&self, name: &str) -> String {
+        self.target.quote_ident(name)
+    }
+
+    /// 迁移语句不带 schema 前缀，显式指定目标 schema 时先切换
+    fn use_schema(&mut self, schema: &str) {
+        let sql = match self.target {
+            Dialect::MySql => format!("USE {}", self.ident(schema)),
+            Dialect::Postgres => format!("SET search_path TO {}", self.ident(schema)),
+            Dialect::Sqlite => {
+                if schema != "main" {
+                    self.warnings.push(format!(
+                        "SQLite 迁移语句不带 schema 前缀，只适用于 main，而目标 schema 为 {}",
+                        schema
+                    ));
+                }
+                return;
+            }
+        };
+        self.push(Phase::UseSchema, format!("切换到 schema {}", schema), sql, false);
+    }
+
+    fn compare(&mut self, source: &[TableDetail], target: &[TableDetail]) -> Vec<TableDiff> {
+        let source: Vec<&TableDetail> = source.iter().filter(|t| t.table.kind == TableKind::Table).collect();
+        let target: Vec<&TableDetail> = target.iter().filter(|t| t.table.kind == TableKind::Table).collect();
+        let target_by_name: HashMap<&str, &TableDetail> =
+            target.iter().map(|t| (t.table.name.as_str(), *t)).collect();
+        let source_names: HashSet<&str> = source.iter().map(|t| t.table.name.as_str()).collect();
+
+        let mut diffs = Vec::new();
+        let mut added = Vec::new();
+        for table in &source {
+            match target_by_name.get(table.table.name.as_str()) {
+                Some(existing) => {
+                    if let Some(diff) = self.compare_table(table, existing) {
+                        diffs.push(diff);
+                    }
+                }
+                None => {
+                    added.push(*table);
+                    diffs.push(table_diff(&table.table.name, ChangeKind::Added));
+                }
+            }
+        }
+        self.create_tables(&added);
+
+        let removed: Vec<&TableDetail> = target
+            .iter()
+            .filter(|t| !source_names.contains(t.table.name.as_str()))
+            .copied()
+            .collect();
+        for table in &removed {
+            diffs.push(table_diff(&table.table.name, ChangeKind::Removed));
+        }
+        self.drop_tables(&removed);
+
+        diffs.sort_by(|a, b| a.table.cmp(&b.table));
+        diffs
+    }
+
+    /// 按外键依赖顺序创建新表（被引用的表在前）
+    fn create_tables(&mut self, tables: &[&TableDetail]) {
+        for table in dependency_order(tables) {
+            let rendered = self.writer.create_table(table);
+            self.warnings.extend(rendered.warnings);
+            for (i, sql) in rendered.statements.into_iter().enumerate() {
+                let description = if i == 0 {
+                    format!("创建表 {}", table.table.name)
+                } else {
+                    format!("为新表 {} 创建索引或注释", table.table.name)
+                };
+                self.push(Phase::CreateTables, description, sql.trim_end_matches(';').to_string(), false);
+            }
+        }
+    }
+
+    /// 按外键依赖的逆序删除表（引用方在前）
+    fn drop_tables(&mut self, tables: &[&TableDetail]) {
+        let mut ordered = dependency_order(tables);
+        ordered.reverse();
+        for table in ordered {
+            self.push(
+                Phase::DropTables,
+                format!("删除表 {}", table.table.name),
+                format!("DROP TABLE {}", self.ident(&table.table.name)),
+                true,
+            );
+        }
+    }
+
+    fn compare_table(&mut self, source: &TableDetail, target: &TableDetail) -> Option<TableDiff> {
+        let name = source.table.name.clone();
+        let mut diff = table_diff(&name, ChangeKind::Modified);
+
+        // 列
+        for column in &source.columns {
+            match target.column(&column.name) {
+                None => {
+                    self.add_column(&name, column);
+                    diff.columns.push(ColumnDiff {
+                        name: column.name.clone(),
+                        change: ChangeKind::Added,
+                        attributes: vec![],
+                        source: Some(column.clone()),
+                        target: None,
+                    });
+                }
+                Some(existing) => {
+                    let attributes = self.column_changes(column, existing);
+                    if !attributes.is_empty() {
+                        self.alter_column(&name, column, existing, &attributes);
+                        diff.columns.push(ColumnDiff {
+                            name: column.name.clone(),
+                            change: ChangeKind::Modified,
+                            attributes,
+                            source: Some(column.clone()),
+                            target: Some(existing.clone()),
+                        });
+                    }
+                }
+            }
+        }
+        for column in target.columns.iter().filter(|c| source.column(&c.name).is_none()) {
+            self.push(
+                Phase::DropColumns,
+                format!("删除列 {}.{}", name, column.name),
+                format!("ALTER TABLE {} DROP COLUMN {}", self.ident(&name), self.ident(&column.name)),
+                true,
+            );
+            diff.columns.push(ColumnDiff {
+                name: column.name.clone(),
+                change: ChangeKind::Removed,
+                attributes: vec![],
+                source: None,
+                target: Some(column.clone()),
+            });
+        }
+
+        // 主键
+        let source_pk = source.primary_key.as_ref().map(|pk| pk.columns.clone()).unwrap_or_default();
+        let target_pk = target.primary_key.as_ref().map(|pk| pk.columns.clone()).unwrap_or_default();
+        if source_pk != target_pk {
+            self.change_primary_key(&name, &source_pk, target);
+            diff.primary_key = Some(PrimaryKeyDiff {
+                source: source_pk,
+                target: target_pk,
+            });
+        }
+
+        // 索引（主键索引由主键比较处理）
+        let signature = |i: &IndexItem| (i.columns.clone(), i.unique);
+        let source_indexes: Vec<&IndexItem> = source.indexes.iter().filter(|i| !i.primary).collect();
+        let target_indexes: Vec<&IndexItem> = target.indexes.iter().filter(|i| !i.primary).collect();
+        for index in &target_indexes {
+            if !source_indexes.iter().any(|i| signature(i) == signature(index)) {
+                self.drop_index(&name, index);
+                diff.indexes.push(IndexDiff {
+                    change: ChangeKind::Removed,
+                    index: (*index).clone(),
+                });
+            }
+        }
+        for index in &source_indexes {
+            if !target_indexes.iter().any(|i| signature(i) == signature(index)) {
+                self.create_index(source, index);
+                diff.indexes.push(IndexDiff {
+                    change: ChangeKind::Added,
+                    index: (*index).clone(),
+                });
+            }
+        }
+
+        // 外键
+        let key = |fk: &ForeignKeyItem| (fk.columns.clone(), fk.referenced_table.clone(), fk.referenced_columns.clone());
+        for fk in &target.foreign_keys {
+            let counterpart = source.foreign_keys.iter().find(|s| key(s) == key(fk));
+            match counterpart {
+                None => {
+                    self.drop_foreign_key(&name, fk);
+                    diff.foreign_keys.push(ForeignKeyDiff {
+                        change: ChangeKind::Removed,
+                        source: None,
+                        target: Some(fk.clone()),
+                    });
+                }
+                Some(s) if rule(&s.on_update) != rule(&fk.on_update) || rule(&s.on_delete) != rule(&fk.on_delete) => {
+                    self.drop_foreign_key(&name, fk);
+                    self.add_foreign_key(&name, s);
+                    diff.foreign_keys.push(ForeignKeyDiff {
+                        change: ChangeKind::Modified,
+                        source: Some(s.clone()),
+                        target: Some(fk.clone()),
+                    });
+                }
+                Some(_) => {}
+            }
+        }
+        for fk in source.foreign_keys.iter().filter(|s| !target.foreign_keys.iter().any(|t| key(t) == key(s))) {
+            self.add_foreign_key(&name, fk);
+            diff.foreign_keys.push(ForeignKeyDiff {
+                change: ChangeKind::Added,
+                source: Some(fk.clone()),
+                target: None,
+            });
+        }
+
+        let changed = !diff.columns.is_empty()
+            || diff.primary_key.is_some()
+            || !diff.indexes.is_empty()
+            || !diff.foreign_keys.is_empty();
+        changed.then_some(diff)
+    }
+
+    /// 以目标方言比较列属性，返回变化的属性名
+    fn column_changes(&self, source: &ColumnItem, target: &ColumnItem) -> Vec<String> {
+        let mut changes = Vec::new();
+        let source_type = self.target.translate_type(self.source, &source.name, &source.data_type).target_type;
+        if normalize_type(self.target, &source_type) != normalize_type(self.target, &target.data_type) {
+            changes.push("data_type".to_string());
+        }
+        if source.nullable != target.nullable {
+            changes.push("nullable".to_string());
+        }
+        if source.auto_increment != target.auto_increment {
+            changes.push("auto_increment".to_string());
+        } else if !source.auto_increment {
+            let source_default = source
+                .default_value
+                .as_deref()
+                .and_then(|d| self.target.translate_default(self.source, d));
+            if normalize_default(source_default.as_deref()) != normalize_default(target.default_value.as_deref()) {
+                changes.push("default_value".to_string());
+            }
+        }
+        changes
+    }
+
+    fn column_definition(&mut self, table: &str, column: &ColumnItem) -> String {
+        let mut scratch = RenderedDdl::default();
+        let definition = self.writer.column_definition(table, column, false, &mut scratch);
+        self.warnings.extend(scratch.warnings);
+        definition
+    }
+
+    fn add_column(&mut self, table: &str, column: &ColumnItem) {
+        if self.target == Dialect::Sqlite && !column.nullable && column.default_value.is_none() {
+            self.warnings.push(format!(
+                "{}.{}: SQLite 不能添加没有默认值的 NOT NULL 列",
+                table, column.name
+            ));
+        }
+        let definition = self.column_definition(table, column);
+        self.push(
+            Phase::AddColumns,
+            format!("添加列 {}.{}", table, column.name),
+            format!("ALTER TABLE {} ADD COLUMN {}", self.ident(table), definition),
+            false,
+        );
+    }
+
+    fn alter_column(&mut self, table: &str, source: &ColumnItem, target: &ColumnItem, attributes: &[String]) {
+        let type_changed = attributes.iter().any(|a| a == "data_type");
+        let description = format!("修改列 {}.{}（{}）", table, source.name, attributes.join(", "));
+        let quoted_table = self.ident(table);
+        let quoted_column = self.ident(&source.name);
+
+        match self.target {
+            Dialect::MySql => {
+                let definition = self.column_definition(table, source);
+                self.push(
+                    Phase::AlterColumns,
+                    description,
+                    format!("ALTER TABLE {} MODIFY COLUMN {}", quoted_table, definition),
+                    type_changed,
+                );
+            }
+            Dialect::Postgres => {
+                let prefix = format!("ALTER TABLE {} ALTER COLUMN {}", quoted_table, quoted_column);
+                for attribute in attributes {
+                    match attribute.as_str() {
+                        "data_type" => {
+                            let mapping = self.target.translate_type(self.source, &source.name, &source.data_type);
+                            self.push(
+                                Phase::AlterColumns,
+                                description.clone(),
+                                format!("{} TYPE {} USING {}::{}", prefix, mapping.target_type, quoted_column, mapping.target_type),
+                                true,
+                            );
+                        }
+                        "nullable" => {
+                            let action = if source.nullable { "DROP NOT NULL" } else { "SET NOT NULL" };
+                            self.push(Phase::AlterColumns, description.clone(), format!("{} {}", prefix, action), false);
+                        }
+                        "default_value" => {
+                            let action = match source
+                                .default_value
+                                .as_deref()
+                                .and_then(|d| self.target.translate_default(self.source, d))
+                            {
+                                Some(expr) => format!("SET DEFAULT {}", expr),
+                                None => "DROP DEFAULT".to_string(),
+                            };
+                            self.push(Phase::AlterColumns, description.clone(), format!("{} {}", prefix, action), false);
+                        }
+                        _ => self.warnings.push(format!(
+                            "{}.{}: 自增属性变化（{} -> {}）需要手动处理",
+                            table, source.name, target.auto_increment, source.auto_increment
+                        )),
+                    }
+                }
+            }
+            Dialect::Sqlite => self.warnings.push(format!(
+                "{}.{}: SQLite 不支持修改列（{}），需要重建表",
+                table,
+                source.name,
+                attributes.join(", ")
+            )),
+        }
+    }
+
+    fn change_primary_key(&mut self, table: &str, source_pk: &[String], target: &TableDetail) {
+        if self.target == Dialect::Sqlite {
+            self.warnings.push(format!("{}: SQLite 不支持修改主键，需要重建表", table));
+            return;
+        }
+        let quoted = self.ident(table);
+        if let Some(pk) = &target.primary_key {
+            let sql = match (self.target, &pk.name) {
+                (Dialect::Postgres, Some(constraint)) => {
+                    format!("ALTER TABLE {} DROP CONSTRAINT {}", quoted, self.ident(constraint))
+                }
+                _ => format!("ALTER TABLE {} DROP PRIMARY KEY", quoted),
+            };
+            self.push(Phase::DropPrimaryKeys, format!("删除表 {} 的主键", table), sql, false);
+        }
+        if !source_pk.is_empty() {
+            let columns: Vec<String> = source_pk.iter().map(|c| self.ident(c)).collect();
+            self.push(
+                Phase::AddPrimaryKeys,
+                format!("为表 {} 添加主键", table),
+                format!("ALTER TABLE {} ADD PRIMARY KEY ({})", quoted, columns.join(", ")),
+                false,
+            );
+        }
+    }
+
+    fn drop_index(&mut self, table: &str, index: &IndexItem) {
+        let description = format!("删除索引 {}.{}", table, index.name);
+        let name = self.ident(&index.name);
+        match self.target {
+            Dialect::MySql => {
+                let sql = format!("DROP INDEX {} ON {}", name, self.ident(table));
+                self.push(Phase::DropIndexes, description, sql, false);
+            }
+            Dialect::Postgres => {
+                // 唯一约束支撑的索引只能通过删除约束来删除
+                if index.unique {
+                    let sql = format!("ALTER TABLE {} DROP CONSTRAINT IF EXISTS {}", self.ident(table), name);
+                    self.push(Phase::DropIndexes, description.clone(), sql, false);
+                }
+                self.push(Phase::DropIndexes, description, format!("DROP INDEX IF EXISTS {}", name), false);
+            }
+            Dialect::Sqlite if index.name.starts_with("sqlite_autoindex_") => self.warnings.push(format!(
+                "{}: 唯一约束 {} 由 SQLite 自动创建，需要重建表才能删除",
+                table, index.name
+            )),
+            Dialect::Sqlite => self.push(Phase::DropIndexes, description, format!("DROP INDEX {}", name), false),
+        }
+    }
+
+    fn create_index(&mut self, table: &TableDetail, index: &IndexItem) {
+        // SQLite 自动索引的名称不能用于新建索引
+        let mut index = index.clone();
+        if index.name.starts_with("sqlite_autoindex_") {
+            index.name = format!("uq_{}_{}", table.table.name, index.columns.join("_"));
+        }
+        let mut scratch = RenderedDdl::default();
+        let sql = self.writer.create_index(&table.table.name, &index, table, &mut scratch);
+        self.warnings.extend(scratch.warnings);
+        if let Some(sql) = sql {
+            self.push(
+                Phase::CreateIndexes,
+                format!("创建索引 {}.{}", table.table.name, index.name),
+                sql,
+                false,
+            );
+        }
+    }
+
+    fn drop_foreign_key(&mut self, table: &str, fk: &ForeignKeyItem) {
+        let keyword = match self.target {
+            Dialect::MySql => "FOREIGN KEY",
+            Dialect::Postgres => "CONSTRAINT",
+            Dialect::Sqlite => {
+                self.warnings.push(format!("{}: SQLite 不支持删除外键 {}，需要重建表", table, fk.name));
+                return;
+            }
+        };
+        self.push(
+            Phase::DropForeignKeys,
+            format!("删除外键 {}.{}", table, fk.name),
+            format!("ALTER TABLE {} DROP {} {}", self.ident(table), keyword, self.ident(&fk.name)),
+            false,
+        );
+    }
+
+    fn add_foreign_key(&mut self, table: &str, fk: &ForeignKeyItem) {
+        if self.target == Dialect::Sqlite {
+            self.warnings.push(format!("{}: SQLite 不支持添加外键 {}，需要重建表", table, fk.name));
+            return;
+        }
+        self.push(
+            Phase::AddForeignKeys,
+            format!("添加外键 {}.{}", table, fk.name),
+            format!("ALTER TABLE {} ADD {}", self.ident(table), self.writer.foreign_key(fk)),
+            false,
+        );
+    }
+}
+
+fn table_diff(table: &str, change: ChangeKind) -> TableDiff {
+    TableDiff {
+        table: table.to_string(),
+        change,
+        columns: vec![],
+        primary_key: None,
+        indexes: vec![],
+        foreign_keys: vec![],
+    }
+}
+
+/// 按外键依赖排序：被引用的表在前，循环依赖的表保持原顺序排在最后
+fn dependency_order<'a>(tables: &[&'a TableDetail]) -> Vec<&'a TableDetail> {
+    let names: HashSet<&str> = tables.iter().map(|t| t.table.name.as_str()).collect();
+    let mut done: HashSet<&str> = HashSet::new();
+    let mut ordered = Vec::with_capacity(tables.len());
+    loop {
+        let ready: Vec<&TableDetail> = tables
+            .iter()
+            .filter(|t| !done.contains(t.table.name.as_str()))
+            .filter(|t| {
+                t.foreign_keys.iter().all(|fk| {
+                    let referenced = fk.referenced_table.as_str();
+                    referenced == t.table.name || !names.contains(referenced) || done.contains(referenced)
+                })
+            })
+            .copied()
+            .collect();
+        if ready.is_empty() {
+            break;
+        }
+        for table in ready {
+            done.insert(table.table.name.as_str());
+            ordered.push(table);
+        }
+    }
+    ordered.extend(tables.iter().filter(|t| !done.contains(t.table.name.as_str())).copied());
+    ordered
+}
+
+/// 规范化类型以便比较：小写、合并空白，去掉 MySQL 整数类型的显示宽度
+fn normalize_type(dialect: Dialect, data_type: &str) -> String {
+    let normalized = data_type.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+    if dialect != Dialect::MySql || normalized.starts_with("tinyint(1)") {
+        return normalized;
+    }
+    let integer = ["tinyint", "smallint", "mediumint", "int", "bigint"]
+        .iter()
+        .any(|t| normalized.starts_with(&format!("{}(", t)));
+    match (integer, normalized.find('('), normalized.find(')')) {
+        (true, Some(open), Some(close)) => format!("{}{}", &normalized[..open], &normalized[close + 1..]),
+        _ => normalized,
+    }
+}
+
+fn normalize_default(default: Option<&str>) -> Option<String> {
+    default.map(|d| d.trim().to_lowercase()).filter(|d| d != "null")
+}
+
+fn rule(rule: &Option<String>) -> &str {
+    match rule.as_deref() {
+        None | Some("RESTRICT") => "NO ACTION",
+        Some(rule) => rule,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::models::metadata::{PrimaryKeyItem, TableItem};
+
+    fn column(name: &str, data_type: &str, nullable: bool) -> ColumnItem {
+        ColumnItem {
+            name: name.to_string(),
+            ordinal: 0,
+            data_type: data_type.to_string(),
+            nullable,
+            default_value: None,
+            is_primary_key: false,
+            auto_increment: false,
+            comment: None,
+        }
+    }
+
+    fn table(name: &str, columns: Vec<ColumnItem>) -> TableDetail {
+        TableDetail {
+            table: TableItem {
+                schema: "public".to_string(),
+                name: name.to_string(),
+                kind: TableKind::Table,
+                row_estimate: None,
+                comment: None,
+            },
+            columns,
+            primary_key: Some(PrimaryKeyItem {
+                name: Some(format!("{}_pkey", name)),
+                columns: vec!["id".to_string()],
+            }),
+            indexes: vec![],
+            foreign_keys: vec![],
+        }
+    }
+
+    fn diff(source: Dialect, target: Dialect, a: &[TableDetail], b: &[TableDetail]) -> (Vec<TableDiff>, Vec<MigrationStep>) {
+        let mut differ = Differ::new(source, target);
+        let tables = differ.compare(a, b);
+        (tables, differ.into_steps().0)
+    }
+
+    #[test]
+    fn test_identical_schemas_have_no_steps() {
+        let users = table("users", vec![column("id", "integer", false), column("email", "text", false)]);
+        let (tables, steps) = diff(Dialect::Postgres, Dialect::Postgres, std::slice::from_ref(&users), std::slice::from_ref(&users));
+        assert!(tables.is_empty());
+        assert!(steps.is_empty());
+    }
+
+    #[test]
+    fn test_postgres_migration_is_ordered_and_flags_destructive_steps() {
+        let mut orders = table("orders", vec![column("id", "integer", false), column("user_id", "integer", false)]);
+        orders.foreign_keys.push(ForeignKeyItem {
+            name: "orders_user_id_fkey".to_string(),
+            columns: vec!["user_id".to_string()],
+            referenced_schema: None,
+            referenced_table: "users".to_string(),
+            referenced_columns: vec!["id".to_string()],
+            on_update: None,
+            on_delete: Some("CASCADE".to_string()),
+        });
+        let mut email = column("email", "character varying(200)", true);
+        email.default_value = Some("''::character varying".to_string());
+        let source = vec![
+            orders,
+            table("users", vec![column("id", "integer", false), email]),
+        ];
+        let target = vec![
+            table("users", vec![column("id", "integer", false), column("email", "character varying(100)", false), column("legacy", "text", true)]),
+            table("audit", vec![column("id", "integer", false)]),
+        ];
+
+        let (tables, steps) = diff(Dialect::Postgres, Dialect::Postgres, &source, &target);
+        let changes: Vec<_> = tables.iter().map(|t| (t.table.as_str(), t.change)).collect();
+        assert_eq!(
+            changes,
+            vec![("audit", ChangeKind::Removed), ("orders", ChangeKind::Added), ("users", ChangeKind::Modified)]
+        );
+        assert_eq!(tables[2].columns[0].attributes, vec!["data_type", "nullable", "default_value"]);
+
+        let sql: Vec<_> = steps.iter().map(|s| (s.sql.as_str(), s.destructive)).collect();
+        assert!(sql[0].0.starts_with("CREATE TABLE \"orders\""));
+        assert_eq!(
+            &sql[1..],
+            &[
+                ("ALTER TABLE \"users\" ALTER COLUMN \"email\" TYPE character varying(200) USING \"email\"::character varying(200);", true),
+                ("ALTER TABLE \"users\" ALTER COLUMN \"email\" DROP NOT NULL;", false),
+                ("ALTER TABLE \"users\" ALTER COLUMN \"email\" SET DEFAULT ''::character varying;", false),
+                ("ALTER TABLE \"users\" DROP COLUMN \"legacy\";", true),
+                ("DROP TABLE \"audit\";", true),
+            ]
+        );
+        assert_eq!(steps.last().unwrap().order, 6);
+    }
+
+    #[test]
+    fn test_dependency_order_and_mysql_display_width() {
+        let users = table("users", vec![column("id", "int", false)]);
+        let mut orders = table("orders", vec![column("id", "int", false)]);
+        orders.foreign_keys.push(ForeignKeyItem {
+            name: "fk".to_string(),
+            columns: vec!["user_id".to_string()],
+            referenced_schema: None,
+            referenced_table: "users".to_string(),
+            referenced_columns: vec!["id".to_string()],
+            on_update: None,
+            on_delete: None,
+        });
+        let ordered: Vec<_> = dependency_order(&[&orders, &users]).iter().map(|t| t.table.name.as_str()).collect();
+        assert_eq!(ordered, vec!["users", "orders"]);
+
+        assert_eq!(normalize_type(Dialect::MySql, "INT(11) unsigned"), "int unsigned");
+        assert_eq!(normalize_type(Dialect::MySql, "tinyint(1)"), "tinyint(1)");
+        assert_eq!(normalize_type(Dialect::MySql, "varchar(20)"), "varchar(20)");
+    }
+}
@@ -0,0 +1,449 @@
+//! 元数据搜索索引
+//!
+//! 在内存中索引所有连接的表、视图、列和索引的名称及注释，支持跨连接搜索：
+//! - 后台任务定期重建过期的连接索引，新增的连接在下一个周期内被索引
+//! - 重建期间不持有锁，失败时保留上一次的索引并记录错误
+//! - 名称支持完全匹配、前缀、单词前缀、包含和模糊（字符按顺序出现）匹配，注释支持包含匹配
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use common::errors::{AppError, AppResult};
+use common::models::metadata::{TableDetail, TableKind};
+use common::models::search::{
+    IndexedConnection, SearchHit, SearchIndexStatus, SearchObjectType, SearchQuery, SearchResult,
+};
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+use validator::Validate;
+
+use crate::completion::match_quality;
+use crate::metadata::MetadataService;
+use crate::pool_manager::PoolManager;
+
+/// 后台任务检查过期索引的间隔
+const INDEXER_TICK: Duration = Duration::from_secs(30);
+/// 默认返回的命中数
+const DEFAULT_LIMIT: u32 = 50;
+/// 只匹配注释时的得分
+const COMMENT_SCORE: u32 = 25;
+
+/// 被索引的对象
+#[derive(Debug, Clone)]
+struct IndexedObject {
+    schema: String,
+    object_type: SearchObjectType,
+    name: String,
+    table: Option<String>,
+    detail: Option<String>,
+    comment: Option<String>,
+}
+
+/// 单个连接的索引
+struct ConnectionIndex {
+    name: String,
+    objects: Arc<Vec<IndexedObject>>,
+    indexed_at: Option<String>,
+    refreshed: Instant,
+    error: Option<String>,
+}
+
+/// 跨连接的元数据搜索索引
+pub struct SearchIndex {
+    interval: Duration,
+    connections: RwLock<HashMap<String, ConnectionIndex>>,
+}
+
+impl SearchIndex {
+    /// 创建按指定间隔刷新的空索引
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            connections: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// 重建所有连接的索引
+    pub async fn refresh_all(&self, pool_manager: &Arc<PoolManager>) -> SearchIndexStatus {
+        self.prune(pool_manager).await;
+        for config in pool_manager.list_connections().await {
+            if let Err(e) = self.refresh_connection(pool_manager, &config.id, true).await {
+                warn!(connection_id = %config.id, error = %e, "搜索索引重建失败");
+            }
+        }
+        self.status().await
+    }
+
+    /// 重建从未索引或已过期的连接索引
+    pub async fn refresh_stale(&self, pool_manager: &Arc<PoolManager>) {
+        self.prune(pool_manager).await;
+        for config in pool_manager.list_connections().await {
+            let stale = self
+                .connections
+                .read()
+                .await
+                .get(&config.id)
+                .is_none_or(|c| c.refreshed.elapsed() >= self.interval);
+            if !stale {
+                continue;
+            }
+            match self.refresh_connection(pool_manager, &config.id, false).await {
+                Ok(Some(objects)) => info!(connection_id = %config.id, objects, "搜索索引已重建"),
+                Ok(None) => {}
+                Err(e) => warn!(connection_id = %config.id, error = %e, "搜索索引重建失败"),
+            }
+        }
+    }
+
+    /// 重建单个连接的索引，返回对象数；不支持元数据的连接类型返回 None
+    ///
+    /// `open` 为 false 时不为空闲连接打开连接池，同样返回 None，
+    /// 避免后台刷新让空闲连接池一直保持打开。
+    async fn refresh_connection(
+        &self,
+        pool_manager: &Arc<PoolManager>,
+        id: &str,
+        open: bool,
+    ) -> AppResult<Option<usize>> {
+        let config = pool_manager
+            .get_connection(id)
+            .await
+            .ok_or_else(|| AppError::ConnectionNotFound(id.to_string()))?;
+        let pool = if open { Some(pool_manager.pool(id).await?) } else { pool_manager.get_pool(id).await };
+        let Some(pool) = pool else {
+            return Ok(None);
+        };
+        if pool.dialect().is_err() {
+            return Ok(None);
+        }
+
+        let result = load_objects(pool_manager, id).await;
+        let mut connections = self.connections.write().await;
+        let entry = connections.entry(id.to_string()).or_insert_with(|| ConnectionIndex {
+            name: config.name.clone(),
+            objects: Arc::new(Vec::new()),
+            indexed_at: None,
+            refreshed: Instant::now(),
+            error: None,
+        });
+        entry.name = config.name;
+        entry.refreshed = Instant::now();
+        match result {
+            Ok(objects) => {
+                let count = objects.len();
+                entry.objects = Arc::new(objects);
+                entry.indexed_at = Some(Utc::now().to_rfc3339());
+                entry.error = None;
+                Ok(Some(count))
+            }
+            Err(e) => {
+                entry.error = Some(e.to_string());
+                Err(e)
+            }
+        }
+    }
+
+    /// 移除已删除连接的索引
+    async fn prune(&self, pool_manager: &Arc<PoolManager>) {
+        let ids: HashSet<String> = pool_manager.list_connections().await.into_iter().map(|c| c.id).collect();
+        self.connections.write().await.retain(|id, _| ids.contains(id));
+    }
+
+    /// 索引状态
+    pub async fn status(&self) -> SearchIndexStatus {
+        let connections = self.connections.read().await;
+        let mut items: Vec<IndexedConnection> = connections
+            .iter()
+            .map(|(id, c)| IndexedConnection {
+                connection_id: id.clone(),
+                connection_name: c.name.clone(),
+                objects: c.objects.len(),
+                indexed_at: c.indexed_at.clone(),
+                error: c.error.clone(),
+            })
+            .collect();
+        items.sort_by(|a, b| a.connection_name.cmp(&b.connection_name).then(a.connection_id.cmp(&b.connection_id)));
+        SearchIndexStatus {
+            objects: items.iter().map(|c| c.objects).sum(),
+            connections: items,
+        }
+    }
+
+    /// 搜索索引，结果按得分排序
+    pub async fn search(&self, query: &SearchQuery) -> AppResult<SearchResult> {
+        query.validate()?;
+        let types = parse_types(query.types.as_deref())?;
+        let needle = query.q.trim();
+        // `table.column` 形式：限定符匹配列和索引所属的表，或表和视图所属的 schema
+        let (qualifier, needle) = match needle.rsplit_once('.') {
+            Some((qualifier, name)) if !qualifier.is_empty() => (Some(qualifier), name),
+            _ => (None, needle),
+        };
+        let comment_needle = query.q.trim().to_lowercase();
+
+        // 只在持锁期间复制对象列表的引用
+        let snapshot: Vec<(String, String, Arc<Vec<IndexedObject>>)> = self
+            .connections
+            .read()
+            .await
+            .iter()
+            .filter(|(id, _)| query.connection_id.as_ref().is_none_or(|c| c == *id))
+            .map(|(id, c)| (id.clone(), c.name.clone(), c.objects.clone()))
+            .collect();
+
+        let mut hits = Vec::new();
+        for (id, name, objects) in &snapshot {
+            for object in objects.iter() {
+                if types.as_ref().is_some_and(|t| !t.contains(&object.object_type)) {
+                    continue;
+                }
+                if let Some(score) = score(object, qualifier, needle, &comment_needle) {
+                    hits.push(SearchHit {
+                        connection_id: id.clone(),
+                        connection_name: name.clone(),
+                        schema: object.schema.clone(),
+                        object_type: object.object_type,
+                        name: object.name.clone(),
+                        table: object.table.clone(),
+                        detail: object.detail.clone(),
+                        comment: object.comment.clone(),
+                        score,
+                    });
+                }
+            }
+        }
+        hits.sort_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then(a.name.len().cmp(&b.name.len()))
+                .then_with(|| a.connection_name.cmp(&b.connection_name))
+                .then_with(|| a.schema.cmp(&b.schema))
+                .then_with(|| a.table.cmp(&b.table))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        let total = hits.len();
+        hits.truncate(query.limit.unwrap_or(DEFAULT_LIMIT) as usize);
+        Ok(SearchResult {
+            query: query.q.clone(),
+            total,
+            hits,
+            indexed_connections: snapshot.len(),
+        })
+    }
+}
+
+/// 启动后台索引任务
+pub fn spawn_indexer(pool_manager: Arc<PoolManager>, index: Arc<SearchIndex>) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(INDEXER_TICK);
+        loop {
+            ticker.tick().await;
+            index.refresh_stale(&pool_manager).await;
+        }
+    });
+}
+
+/// 读取连接所有 schema 中的对象
+async fn load_objects(pool_manager: &Arc<PoolManager>, id: &str) -> AppResult<Vec<IndexedObject>> {
+    let metadata = MetadataService::new(pool_manager.clone());
+    let mut objects = Vec::new();
+    for schema in metadata.schemas(id).await? {
+        let tables = metadata.schema_details(id, Some(&schema.name)).await?;
+        objects.extend(objects_of(&schema.name, &tables));
+    }
+    Ok(objects)
+}
+
+/// 把表结构展开为表、列和索引对象
+fn objects_of(schema: &str, tables: &[TableDetail]) -> Vec<IndexedObject> {
+    let mut objects = Vec::new();
+    for table in tables {
+        let name = &table.table.name;
+        objects.push(IndexedObject {
+            schema: schema.to_string(),
+            object_type: match table.table.kind {
+                TableKind::Table => SearchObjectType::Table,
+                TableKind::View | TableKind::MaterializedView => SearchObjectType::View,
+            },
+            name: name.clone(),
+            table: None,
+            detail: None,
+            comment: table.table.comment.clone(),
+        });
+        objects.extend(table.columns.iter().map(|c| IndexedObject {
+            schema: schema.to_string(),
+            object_type: SearchObjectType::Column,
+            name: c.name.clone(),
+            table: Some(name.clone()),
+            detail: Some(c.data_type.clone()),
+            comment: c.comment.clone(),
+        }));
+        objects.extend(table.indexes.iter().map(|i| IndexedObject {
+            schema: schema.to_string(),
+            object_type: SearchObjectType::Index,
+            name: i.name.clone(),
+            table: Some(name.clone()),
+            detail: Some(i.columns.join(", ")),
+            comment: None,
+        }));
+    }
+    objects
+}
+
+fn parse_types(types: Option<&str>) -> AppResult<Option<HashSet<SearchObjectType>>> {
+    let Some(types) = types else {
+        return Ok(None);
+    };
+    types
+        .split(',')
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(|t| match t.to_ascii_lowercase().as_str() {
+            "table" => Ok(SearchObjectType::Table),
+            "view" => Ok(SearchObjectType::View),
+            "column" => Ok(SearchObjectType::Column),
+            "index" => Ok(SearchObjectType::Index),
+            _ => Err(AppError::InvalidInput(format!(
+                "未知的对象类型 {}，可选 table、view、column、index",
+                t
+            ))),
+        })
+        .collect::<AppResult<HashSet<_>>>()
+        .map(Some)
+}
+
+/// 计算对象得分：名称匹配程度加对象类型权重，不匹配返回 None
+fn score(object: &IndexedObject, qualifier: Option<&str>, needle: &str, comment_needle: &str) -> Option<u32> {
+    let weight = match object.object_type {
+        SearchObjectType::Table => 12,
+        SearchObjectType::View => 10,
+        SearchObjectType::Column => 6,
+        SearchObjectType::Index => 4,
+    };
+    if let Some(qualifier) = qualifier {
+        let owner = object.table.as_deref().unwrap_or(&object.schema);
+        match_quality(qualifier, owner)?;
+    }
+    let by_name = match needle.is_empty() {
+        true => qualifier.map(|_| 50),
+        false => match_quality(needle, &object.name),
+    };
+    let by_comment = object
+        .comment
+        .as_ref()
+        .filter(|c| qualifier.is_none() && c.to_lowercase().contains(comment_needle))
+        .map(|_| COMMENT_SCORE);
+    by_name.max(by_comment).map(|s| s + weight)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::models::metadata::{ColumnItem, IndexItem, TableItem};
+
+    fn table(name: &str, kind: TableKind, comment: Option<&str>, columns: &[&str]) -> TableDetail {
+        TableDetail {
+            table: TableItem {
+                schema: "public".into(),
+                name: name.into(),
+                kind,
+                row_estimate: None,
+                comment: comment.map(str::to_string),
+            },
+            columns: columns
+                .iter()
+                .enumerate()
+                .map(|(i, c)| ColumnItem {
+                    name: c.to_string(),
+                    ordinal: i as u32 + 1,
+                    data_type: "text".into(),
+                    nullable: true,
+                    default_value: None,
+                    is_primary_key: false,
+                    auto_increment: false,
+                    comment: None,
+                })
+                .collect(),
+            primary_key: None,
+            indexes: vec![IndexItem {
+                name: format!("{}_pkey", name),
+                columns: vec!["id".into()],
+                unique: true,
+                primary: true,
+                index_type: None,
+            }],
+            foreign_keys: vec![],
+        }
+    }
+
+    async fn index() -> SearchIndex {
+        let index = SearchIndex::new(Duration::from_secs(600));
+        let tables = [
+            table("orders_archive", TableKind::Table, None, &["id", "archived_at"]),
+            table("orders", TableKind::Table, Some("customer purchases"), &["id", "status"]),
+            table("v_orders", TableKind::View, None, &["id"]),
+        ];
+        let mut connections = index.connections.write().await;
+        for (id, name) in [("a", "shop"), ("b", "legacy")] {
+            connections.insert(
+                id.into(),
+                ConnectionIndex {
+                    name: name.into(),
+                    objects: Arc::new(objects_of("public", &tables)),
+                    indexed_at: None,
+                    refreshed: Instant::now(),
+                    error: None,
+                },
+            );
+        }
+        drop(connections);
+        index
+    }
+
+    async fn search(index: &SearchIndex, q: &str, types: Option<&str>) -> Vec<(String, SearchObjectType, Option<String>)> {
+        let query = SearchQuery {
+            q: q.into(),
+            connection_id: Some("a".into()),
+            types: types.map(str::to_string),
+            limit: Some(5),
+        };
+        let result = index.search(&query).await.unwrap();
+        result.hits.into_iter().map(|h| (h.name, h.object_type, h.table)).collect()
+    }
+
+    #[tokio::test]
+    async fn test_ranking_and_filters() {
+        let index = index().await;
+
+        let hits = search(&index, "orders_archive", None).await;
+        assert_eq!(hits[0], ("orders_archive".into(), SearchObjectType::Table, None));
+
+        // 单词前缀匹配
+        let hits = search(&index, "archive", Some("table")).await;
+        assert_eq!(hits, vec![("orders_archive".into(), SearchObjectType::Table, None)]);
+
+        // 模糊匹配
+        let hits = search(&index, "ordarc", None).await;
+        assert_eq!(hits[0].0, "orders_archive");
+
+        // 注释匹配
+        let hits = search(&index, "purchases", None).await;
+        assert_eq!(hits, vec![("orders".into(), SearchObjectType::Table, None)]);
+
+        // 限定名：只匹配 orders 表的列
+        let hits = search(&index, "orders.st", Some("column")).await;
+        assert_eq!(hits, vec![("status".into(), SearchObjectType::Column, Some("orders".into()))]);
+
+        let all = index
+            .search(&SearchQuery { q: "orders".into(), ..Default::default() })
+            .await
+            .unwrap();
+        assert_eq!(all.indexed_connections, 2);
+        assert_eq!(all.hits[0].name, "orders");
+
+        let invalid = SearchQuery { q: "x".into(), types: Some("trigger".into()), ..Default::default() };
+        assert!(matches!(index.search(&invalid).await, Err(AppError::InvalidInput(_))));
+    }
+}
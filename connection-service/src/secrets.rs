@@ -0,0 +1,318 @@
+//! 密钥环与密码加密
+//!
+//! 保存在磁盘上的连接密码使用 AES-256-GCM 加密，密文带格式版本和密钥 ID：
+//!
+//! ```text
+//! enc:v1:<密钥 ID>:<base64(nonce || 密文 || tag)>
+//! ```
+//!
+//! 密钥 ID 取密钥 SHA-256 摘要的前 8 个十六进制字符，因此多个密钥可以共存：
+//! 新密文总是使用当前密钥，旧密文按 ID 找到对应的密钥解密。连接 ID 作为附加认证数据，
+//! 密文无法在连接之间挪用。
+//!
+//! 主密钥来自 `MASTER_KEY` 环境变量，或密钥文件（每行一个 base64 密钥，第一行为当前密钥）。
+//! 只有密钥文件支持在线轮换。
+
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use common::config::AppConfig;
+use common::errors::{AppError, AppResult};
+use common::models::secrets::{KeyRotationResult, RotateKeyRequest};
+use common::utils::fs::write_private;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::digest::{digest, SHA256};
+use ring::rand::{SecureRandom, SystemRandom};
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+
+use crate::audit::{AuditLog, AuditRecord};
+use crate::pool_manager::PoolManager;
+
+/// 密文前缀（含格式版本）
+const PREFIX: &str = "enc:v1:";
+const KEY_LEN: usize = 32;
+const DEFAULT_KEY_FILE: &str = "master.key";
+const KEY_FILE_HEADER: &str = "# 连接密码主密钥，每行一个 base64 密钥，第一行为当前密钥。\n\
+                               # 仍有密文使用的旧密钥不能删除。\n";
+
+/// 一个主密钥
+struct MasterKey {
+    id: String,
+    bytes: [u8; KEY_LEN],
+}
+
+impl MasterKey {
+    fn new(bytes: [u8; KEY_LEN]) -> Self {
+        let id = digest(&SHA256, &bytes).as_ref()[..4]
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        Self { id, bytes }
+    }
+
+    fn parse(encoded: &str) -> AppResult<Self> {
+        let bytes: [u8; KEY_LEN] = BASE64
+            .decode(encoded.trim())
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| AppError::Configuration("主密钥必须是 32 字节密钥的 base64 编码".into()))?;
+        Ok(Self::new(bytes))
+    }
+
+    fn generate(rng: &SystemRandom) -> AppResult<Self> {
+        let mut bytes = [0u8; KEY_LEN];
+        rng.fill(&mut bytes).map_err(|_| AppError::Internal("生成密钥失败".into()))?;
+        Ok(Self::new(bytes))
+    }
+
+    fn cipher(&self) -> LessSafeKey {
+        // 密钥长度固定为 32 字节，构造不会失败
+        LessSafeKey::new(UnboundKey::new(&AES_256_GCM, &self.bytes).expect("AES-256 密钥长度"))
+    }
+}
+
+/// 主密钥来源
+enum KeySource {
+    Env,
+    File(PathBuf),
+}
+
+/// 主密钥环，第一个密钥为当前密钥
+pub struct Keyring {
+    keys: RwLock<Vec<MasterKey>>,
+    source: KeySource,
+    rng: SystemRandom,
+    /// 串行化密钥文件的修改
+    file_lock: Mutex<()>,
+}
+
+impl Keyring {
+    /// 按配置加载主密钥
+    ///
+    /// 未设置 `MASTER_KEY` 和 `MASTER_KEY_FILE` 时使用 `data_dir/master.key`，不存在则生成；
+    /// 显式指定的密钥文件不存在时报错。
+    pub async fn load(config: &AppConfig) -> AppResult<Self> {
+        let rng = SystemRandom::new();
+        let (keys, source) = if let Some(env) = &config.master_key {
+            let keys = env.split(',').filter(|k| !k.trim().is_empty()).map(MasterKey::parse).collect::<AppResult<Vec<_>>>()?;
+            (keys, KeySource::Env)
+        } else {
+            let path = match &config.master_key_file {
+                Some(path) => PathBuf::from(path),
+                None => PathBuf::from(&config.data_dir).join(DEFAULT_KEY_FILE),
+            };
+            let keys = match tokio::fs::read_to_string(&path).await {
+                Ok(contents) => contents
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                    .map(MasterKey::parse)
+                    .collect::<AppResult<Vec<_>>>()?,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound && config.master_key_file.is_none() => {
+                    let keys = vec![MasterKey::generate(&rng)?];
+                    write_key_file(&path, &keys).await?;
+                    warn!(path = %path.display(), "未配置主密钥，已生成新的密钥文件，请妥善备份");
+                    keys
+                }
+                Err(e) => {
+                    return Err(AppError::Configuration(format!("无法读取密钥文件 {}: {}", path.display(), e)));
+                }
+            };
+            (keys, KeySource::File(path))
+        };
+        if keys.is_empty() {
+            return Err(AppError::Configuration("未配置任何主密钥".into()));
+        }
+        let mut unique: Vec<MasterKey> = Vec::with_capacity(keys.len());
+        for key in keys {
+            if unique.iter().all(|k| k.id != key.id) {
+                unique.push(key);
+            }
+        }
+        Ok(Self { keys: RwLock::new(unique), source, rng, file_lock: Mutex::new(()) })
+    }
+
+    /// 当前密钥 ID
+    pub fn active_id(&self) -> String {
+        self.keys.read().expect("keyring lock")[0].id.clone()
+    }
+
+    /// 全部密钥 ID，当前密钥在前
+    pub fn key_ids(&self) -> Vec<String> {
+        self.keys.read().expect("keyring lock").iter().map(|k| k.id.clone()).collect()
+    }
+
+    /// 用当前密钥加密；`context` 作为附加认证数据
+    pub fn encrypt(&self, plaintext: &str, context: &str) -> AppResult<String> {
+        let keys = self.keys.read().expect("keyring lock");
+        let key = &keys[0];
+        let mut nonce = [0u8; NONCE_LEN];
+        self.rng.fill(&mut nonce).map_err(|_| AppError::Internal("生成随机数失败".into()))?;
+        let mut sealed = plaintext.as_bytes().to_vec();
+        key.cipher()
+            .seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), Aad::from(context.as_bytes()), &mut sealed)
+            .map_err(|_| AppError::Internal("加密失败".into()))?;
+        let mut payload = nonce.to_vec();
+        payload.extend_from_slice(&sealed);
+        Ok(format!("{}{}:{}", PREFIX, key.id, BASE64.encode(payload)))
+    }
+
+    /// 解密 [`Self::encrypt`] 生成的密文
+    pub fn decrypt(&self, ciphertext: &str, context: &str) -> AppResult<String> {
+        let (key_id, encoded) = ciphertext
+            .strip_prefix(PREFIX)
+            .and_then(|rest| rest.split_once(':'))
+            .ok_or_else(|| AppError::Configuration("不支持的密文格式".into()))?;
+        let keys = self.keys.read().expect("keyring lock");
+        let key = keys
+            .iter()
+            .find(|k| k.id == key_id)
+            .ok_or_else(|| AppError::Configuration(format!("缺少密钥 {}", key_id)))?;
+        let mut payload = BASE64
+            .decode(encoded)
+            .map_err(|_| AppError::Configuration("密文不是有效的 base64".into()))?;
+        if payload.len() < NONCE_LEN {
+            return Err(AppError::Configuration("密文过短".into()));
+        }
+        let mut sealed = payload.split_off(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(&payload).map_err(|_| AppError::Configuration("密文过短".into()))?;
+        let plaintext = key
+            .cipher()
+            .open_in_place(nonce, Aad::from(context.as_bytes()), &mut sealed)
+            .map_err(|_| AppError::Configuration(format!("密钥 {} 无法解密，密文已损坏或密钥不匹配", key_id)))?;
+        String::from_utf8(plaintext.to_vec()).map_err(|_| AppError::Configuration("解密结果不是有效的 UTF-8".into()))
+    }
+
+    /// 判断值是否是当前密钥加密的密文
+    pub fn is_current(&self, value: &str) -> bool {
+        value
+            .strip_prefix(PREFIX)
+            .and_then(|rest| rest.split_once(':'))
+            .is_some_and(|(key_id, _)| key_id == self.active_id())
+    }
+
+    /// 生成新密钥并设为当前密钥，旧密钥保留用于解密；返回新旧密钥 ID
+    async fn rotate(&self) -> AppResult<(String, String)> {
+        let KeySource::File(path) = &self.source else {
+            return Err(AppError::Conflict(
+                "主密钥来自 MASTER_KEY 环境变量，无法在线轮换；请改用 MASTER_KEY_FILE".into(),
+            ));
+        };
+        let _guard = self.file_lock.lock().await;
+        let key = MasterKey::generate(&self.rng)?;
+        let (new_id, previous_id) = (key.id.clone(), self.active_id());
+        let mut keys: Vec<MasterKey> = vec![key];
+        keys.extend(self.keys.read().expect("keyring lock").iter().map(|k| MasterKey::new(k.bytes)));
+        // 先落盘再切换，崩溃时磁盘上的密钥文件总能解密已有密文
+        write_key_file(path, &keys).await?;
+        *self.keys.write().expect("keyring lock") = keys;
+        Ok((new_id, previous_id))
+    }
+
+    /// 只保留当前密钥
+    async fn retire_previous(&self) -> AppResult<()> {
+        let KeySource::File(path) = &self.source else { return Ok(()) };
+        let _guard = self.file_lock.lock().await;
+        let active = MasterKey::new(self.keys.read().expect("keyring lock")[0].bytes);
+        write_key_file(path, std::slice::from_ref(&active)).await?;
+        self.keys.write().expect("keyring lock").truncate(1);
+        Ok(())
+    }
+}
+
+async fn write_key_file(path: &std::path::Path, keys: &[MasterKey]) -> AppResult<()> {
+    let mut contents = KEY_FILE_HEADER.to_string();
+    for key in keys {
+        contents.push_str(&BASE64.encode(key.bytes));
+        contents.push('\n');
+    }
+    write_private(path, contents.as_bytes()).await?;
+    Ok(())
+}
+
+/// 密钥管理服务
+pub struct SecretService {
+    keyring: Arc<Keyring>,
+    pool_manager: Arc<PoolManager>,
+    audit: Arc<AuditLog>,
+}
+
+impl SecretService {
+    /// 创建新的密钥管理服务实例
+    pub fn new(keyring: Arc<Keyring>, pool_manager: Arc<PoolManager>, audit: Arc<AuditLog>) -> Self {
+        Self { keyring, pool_manager, audit }
+    }
+
+    /// 轮换到新的主密钥并重新加密所有保存的密码，写入审计日志
+    pub async fn rotate(&self, req: RotateKeyRequest, request_id: Option<String>) -> AppResult<KeyRotationResult> {
+        let result = self.rotate_inner(req.retire_previous).await;
+        let record = AuditRecord {
+            action: "secrets.rotate",
+            connection_id: None,
+            target: result.as_ref().ok().map(|r| r.0.clone()),
+            request_id,
+        };
+        let audit_id = self
+            .audit
+            .record(record, result.as_ref().map(|_| ()).map_err(|e| e.to_string()), None)
+            .await?;
+        let (active_key_id, previous_key_id, re_encrypted) = result?;
+        info!(active_key_id = %active_key_id, re_encrypted, "已轮换主密钥");
+        Ok(KeyRotationResult {
+            active_key_id,
+            previous_key_id,
+            re_encrypted,
+            key_ids: self.keyring.key_ids(),
+            audit_id,
+        })
+    }
+
+    async fn rotate_inner(&self, retire_previous: bool) -> AppResult<(String, String, usize)> {
+        let (active, previous) = self.keyring.rotate().await?;
+        let re_encrypted = self.pool_manager.reencrypt_secrets().await?;
+        if retire_previous {
+            self.keyring.retire_previous().await?;
+        }
+        Ok((active, previous, re_encrypted))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(data_dir: &std::path::Path) -> AppConfig {
+        AppConfig {
+            data_dir: data_dir.to_string_lossy().to_string(),
+            master_key: None,
+            master_key_file: None,
+            ..AppConfig::load()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rotation_keeps_old_ciphertext_readable() {
+        let dir = std::env::temp_dir().join(format!("keyring-{}", uuid::Uuid::new_v4()));
+        let keyring = Keyring::load(&config(&dir)).await.unwrap();
+        let old = keyring.encrypt("s3cret", "conn-1").unwrap();
+        assert!(old.starts_with(&format!("enc:v1:{}:", keyring.active_id())));
+        assert!(keyring.decrypt(&old, "conn-2").is_err(), "ciphertext is bound to its connection");
+
+        let (new_id, _) = keyring.rotate().await.unwrap();
+        assert!(!keyring.is_current(&old));
+        assert_eq!(keyring.decrypt(&old, "conn-1").unwrap(), "s3cret");
+
+        // 重新加载的密钥环与内存中的一致
+        let reloaded = Keyring::load(&config(&dir)).await.unwrap();
+        assert_eq!(reloaded.key_ids(), keyring.key_ids());
+        assert_eq!(reloaded.active_id(), new_id);
+
+        keyring.retire_previous().await.unwrap();
+        let reloaded = Keyring::load(&config(&dir)).await.unwrap();
+        assert!(reloaded.decrypt(&old, "conn-1").is_err());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
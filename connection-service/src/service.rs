@@ -2,15 +2,27 @@
 //!
 //! 使用 Trait 模式实现，支持多种实现方式（真实实现、Mock实现等）
 
+use std::cmp::Ordering;
 use std::sync::Arc;
 use async_trait::async_trait;
 use chrono::Utc;
 use uuid::Uuid;
 
 use common::errors::{AppError, AppResult};
-use common::models::connection::{ConnectionItem, CreateConnectionRequest};
+use common::models::connection::{
+    ConnectionConfig, ConnectionDsn, ConnectionItem, ConnectionListQuery, ConnectionStatus,
+    CreateConnectionRequest, DbType, Environment, UpdateConnectionRequest,
+};
+use common::models::pool::{ConnectionHealth, HealthOverview, PoolStats, TunnelStatus};
+use common::response::PaginatedData;
+use common::utils::dsn;
+use tokio::task::JoinSet;
+use validator::Validate;
 use crate::pool_manager::PoolManager;
 
+const DEFAULT_PAGE_SIZE: u32 = 50;
+const MAX_PAGE_SIZE: u32 = 1000;
+
 // ============================================================
 // 1️⃣ 定义 Trait（类似 Java 的 Service 接口）
 // ============================================================
@@ -30,6 +42,9 @@ pub trait ConnectionServiceTrait: Send + Sync {
     /// 根据 ID 获取连接
     async fn get(&self, id: &str) -> AppResult<ConnectionItem>;
     
+    /// 更新连接，未提供的字段保持不变
+    async fn update(&self, id: &str, req: UpdateConnectionRequest) -> AppResult<ConnectionItem>;
+
     /// 根据 ID 删除连接
     async fn delete(&self, id: &str) -> AppResult<()>;
     
@@ -53,38 +68,265 @@ impl ConnectionService {
     pub fn new(pool_manager: Arc<PoolManager>) -> Self {
         Self { pool_manager }
     }
+
+    /// 按文件夹、标签、环境、类型和名称筛选连接，排序后分页返回
+    ///
+    /// 只为当前页的连接查询连接池状态。
+    pub async fn list_page(&self, query: &ConnectionListQuery) -> AppResult<PaginatedData<ConnectionItem>> {
+        let (field, descending) = parse_sort(query.sort.as_deref())?;
+        let filter = ListFilter::new(query);
+        let mut configs: Vec<_> = self
+            .pool_manager
+            .list_connections()
+            .await
+            .into_iter()
+            .filter(|c| filter.matches(c))
+            .collect();
+        configs.sort_by(|a, b| compare(field, descending, a, b));
+
+        let page = query.page.unwrap_or(1).max(1);
+        let page_size = query.page_size.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+        let total = configs.len() as u64;
+        let mut items = Vec::new();
+        for config in configs.into_iter().skip((page as usize - 1) * page_size as usize).take(page_size as usize) {
+            items.push(self.item(config).await);
+        }
+        Ok(PaginatedData::new(items, page, page_size, total))
+    }
+
+    /// 将连接导出为连接 URL，密码以 `***` 代替
+    pub async fn dsn(&self, id: &str) -> AppResult<ConnectionDsn> {
+        let config = self
+            .pool_manager
+            .get_connection(id)
+            .await
+            .ok_or_else(|| AppError::ConnectionNotFound(id.to_string()))?;
+        Ok(ConnectionDsn { id: config.id.clone(), dsn: dsn::render(&config, true)? })
+    }
+
+    /// 获取连接池统计；`probe` 为 true 时先探测一次，必要时打开连接池
+    ///
+    /// 探测失败不会返回错误，而是体现在统计的状态和最近错误中。
+    pub async fn pool_stats(&self, id: &str, probe: bool) -> AppResult<PoolStats> {
+        if probe && self.pool_manager.connection_exists(id).await {
+            if let Err(e) = self.pool_manager.probe(id).await {
+                tracing::warn!(connection_id = %id, error = %e, "连接池探测失败");
+            }
+        }
+        self.pool_manager.stats(id).await
+    }
+
+    /// 获取连接当前 SSH 隧道的状态；连接池未打开或不使用隧道时为 `None`
+    pub async fn tunnel_status(&self, id: &str) -> Option<TunnelStatus> {
+        self.pool_manager.tunnel_status(id).await
+    }
+
+    /// 获取所有连接的连接池统计；`probe` 为 true 时并发探测已打开的连接池
+    ///
+    /// 批量探测不会打开空闲连接的连接池。
+    pub async fn all_pool_stats(&self, probe: bool) -> Vec<PoolStats> {
+        if probe {
+            let mut probes = JoinSet::new();
+            for id in self.pool_manager.open_pool_ids().await {
+                let pool_manager = self.pool_manager.clone();
+                probes.spawn(async move {
+                    if let Some(Err(e)) = pool_manager.probe_open(&id).await {
+                        tracing::warn!(connection_id = %id, error = %e, "连接池探测失败");
+                    }
+                });
+            }
+            while probes.join_next().await.is_some() {}
+        }
+        self.pool_manager.all_stats().await
+    }
+
+    /// 获取连接的健康历史：状态变化、连续失败次数和探测延迟分位数
+    pub async fn health(&self, id: &str) -> AppResult<ConnectionHealth> {
+        self.pool_manager.health(id).await
+    }
+
+    /// 获取所有连接的健康历史及正常、故障和未知的连接数
+    pub async fn health_overview(&self) -> HealthOverview {
+        self.pool_manager.health_overview().await
+    }
+
+    /// 转换为响应项，并附上连接池的当前状态
+    async fn item(&self, config: ConnectionConfig) -> ConnectionItem {
+        let (status, last_error) = self.pool_manager.status(&config.id).await;
+        ConnectionItem { status, last_error, ..ConnectionItem::from(config) }
+    }
+}
+
+/// 连接列表的筛选条件，已规范化
+struct ListFilter {
+    folder: Option<String>,
+    tags: Vec<String>,
+    search: Option<String>,
+    environment: Option<Environment>,
+    db_type: Option<DbType>,
+}
+
+impl ListFilter {
+    fn new(query: &ConnectionListQuery) -> Self {
+        Self {
+            folder: query.folder.clone(),
+            tags: query
+                .tags
+                .as_deref()
+                .unwrap_or_default()
+                .split(',')
+                .map(str::trim)
+                .filter(|t| !t.is_empty())
+                .map(str::to_lowercase)
+                .collect(),
+            search: query.search.as_deref().map(str::trim).filter(|s| !s.is_empty()).map(str::to_lowercase),
+            environment: query.environment,
+            db_type: query.db_type.clone(),
+        }
+    }
+
+    fn matches(&self, config: &ConnectionConfig) -> bool {
+        self.folder.as_deref().is_none_or(|folder| config.in_folder(folder))
+            && self.tags.iter().all(|tag| config.tags.iter().any(|t| t.to_lowercase() == *tag))
+            && self.search.as_deref().is_none_or(|s| config.name.to_lowercase().contains(s))
+            && self.environment.is_none_or(|e| config.environment == Some(e))
+            && self.db_type.as_ref().is_none_or(|d| config.db_type == *d)
+    }
+}
+
+/// 可排序字段
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SortField {
+    Name,
+    Folder,
+    Environment,
+    DbType,
+    CreatedAt,
+    UpdatedAt,
+}
+
+fn parse_sort(sort: Option<&str>) -> AppResult<(SortField, bool)> {
+    let sort = sort.map(str::trim).filter(|s| !s.is_empty()).unwrap_or("name");
+    let (name, descending) = match sort.strip_prefix('-') {
+        Some(name) => (name, true),
+        None => (sort, false),
+    };
+    let field = match name {
+        "name" => SortField::Name,
+        "folder" => SortField::Folder,
+        "environment" => SortField::Environment,
+        "db_type" => SortField::DbType,
+        "created_at" => SortField::CreatedAt,
+        "updated_at" => SortField::UpdatedAt,
+        _ => return Err(AppError::InvalidInput(format!("不支持按 {} 排序", name))),
+    };
+    Ok((field, descending))
+}
+
+/// 按字段比较，缺失值总是排在最后；相同时依次按名称（不区分大小写）和 ID 排序，保证顺序稳定
+fn compare(field: SortField, descending: bool, a: &ConnectionConfig, b: &ConnectionConfig) -> Ordering {
+    let by_name = |a: &ConnectionConfig, b: &ConnectionConfig| {
+        a.name.to_lowercase().cmp(&b.name.to_lowercase()).then_with(|| a.name.cmp(&b.name))
+    };
+    let ordering = match field {
+        SortField::Name => Some(by_name(a, b)),
+        SortField::Folder => present(a.folder.as_ref(), b.folder.as_ref()),
+        SortField::Environment => present(a.environment.as_ref(), b.environment.as_ref()),
+        SortField::DbType => Some(a.db_type.to_string().cmp(&b.db_type.to_string())),
+        SortField::CreatedAt => Some(a.created_at.cmp(&b.created_at)),
+        SortField::UpdatedAt => present(a.updated_at.as_ref(), b.updated_at.as_ref()),
+    };
+    let ordering = match ordering {
+        Some(ordering) if descending => ordering.reverse(),
+        Some(ordering) => ordering,
+        None => if missing(field, a) { Ordering::Greater } else { Ordering::Less },
+    };
+    ordering.then_with(|| by_name(a, b)).then_with(|| a.id.cmp(&b.id))
+}
+
+/// 两侧都有值时比较；只有一侧有值时返回 `None`，由调用方把缺失值排到最后
+fn present<T: Ord>(a: Option<T>, b: Option<T>) -> Option<Ordering> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.cmp(&b)),
+        (None, None) => Some(Ordering::Equal),
+        _ => None,
+    }
+}
+
+fn missing(field: SortField, config: &ConnectionConfig) -> bool {
+    match field {
+        SortField::Folder => config.folder.is_none(),
+        SortField::Environment => config.environment.is_none(),
+        SortField::UpdatedAt => config.updated_at.is_none(),
+        _ => false,
+    }
+}
+
+/// 校验连接配置中的一节，错误信息带上该节的名称
+fn validate_section(name: &str, section: &impl Validate) -> AppResult<()> {
+    section.validate().map_err(|e| match AppError::from(e) {
+        AppError::Validation(message) => AppError::Validation(format!("{}: {}", name, message)),
+        other => other,
+    })
 }
 
 /// 为 ConnectionService 实现 Trait
 #[async_trait]
 impl ConnectionServiceTrait for ConnectionService {
     async fn list(&self) -> Vec<ConnectionItem> {
-        self.pool_manager
-            .list_connections()
-            .await
-            .into_iter()
-            .map(ConnectionItem::from)
-            .collect()
+        let mut configs = self.pool_manager.list_connections().await;
+        configs.sort_by(|a, b| compare(SortField::Name, false, a, b));
+        let mut items = Vec::new();
+        for config in configs {
+            items.push(self.item(config).await);
+        }
+        items
     }
 
     async fn create(&self, req: CreateConnectionRequest) -> AppResult<ConnectionItem> {
         let id = Uuid::new_v4().to_string();
         let created_at = Utc::now().to_rfc3339();
-        let config = req.into_config(id.clone(), created_at);
+        req.validate()?;
+        let config = req.into_config(id.clone(), created_at)?;
 
         // 添加到连接池管理器（会进行验证并建立连接）
         self.pool_manager.add_connection(config.clone()).await?;
 
         tracing::info!(id = %id, name = %config.name, "连接已创建");
-        Ok(ConnectionItem::from(config))
+        Ok(self.item(config).await)
     }
 
     async fn get(&self, id: &str) -> AppResult<ConnectionItem> {
-        self.pool_manager
+        let config = self
+            .pool_manager
             .get_connection(id)
             .await
-            .map(ConnectionItem::from)
-            .ok_or_else(|| AppError::ConnectionNotFound(id.to_string()))
+            .ok_or_else(|| AppError::ConnectionNotFound(id.to_string()))?;
+        Ok(self.item(config).await)
+    }
+
+    async fn update(&self, id: &str, req: UpdateConnectionRequest) -> AppResult<ConnectionItem> {
+        req.validate()?;
+        let current = self
+            .pool_manager
+            .get_connection(id)
+            .await
+            .ok_or_else(|| AppError::ConnectionNotFound(id.to_string()))?;
+        let mut config = req.apply(current);
+        // TLS 和 SSH 设置可能部分沿用原有的密钥，合并后再校验
+        if let Some(tls) = &config.tls {
+            validate_section("tls", tls)?;
+        }
+        if let Some(ssh) = &config.ssh_tunnel {
+            validate_section("ssh_tunnel", ssh)?;
+        }
+        config.updated_at = Some(Utc::now().to_rfc3339());
+
+        // 先用新配置建立连接，成功后才替换连接池
+        self.pool_manager.update_connection(config.clone()).await?;
+
+        tracing::info!(id = %id, name = %config.name, "连接已更新");
+        Ok(self.item(config).await)
     }
 
     async fn delete(&self, id: &str) -> AppResult<()> {
@@ -140,18 +382,8 @@ impl ConnectionServiceTrait for MockConnectionService {
 
     async fn create(&self, req: CreateConnectionRequest) -> AppResult<ConnectionItem> {
         // Mock 实现：直接返回一个假的连接
-        let id = Uuid::new_v4().to_string();
-        Ok(ConnectionItem {
-            id,
-            name: req.name,
-            db_type: req.db_type,
-            host: req.host,
-            port: req.port,
-            database: req.database,
-            username: req.username,
-            file_path: req.file_path,
-            created_at: Utc::now().to_rfc3339(),
-        })
+        let config = req.into_config(Uuid::new_v4().to_string(), Utc::now().to_rfc3339())?;
+        Ok(ConnectionItem { status: ConnectionStatus::Connected, ..ConnectionItem::from(config) })
     }
 
     async fn get(&self, id: &str) -> AppResult<ConnectionItem> {
@@ -163,6 +395,19 @@ impl ConnectionServiceTrait for MockConnectionService {
             .ok_or_else(|| AppError::ConnectionNotFound(id.to_string()))
     }
 
+    async fn update(&self, id: &str, req: UpdateConnectionRequest) -> AppResult<ConnectionItem> {
+        // Mock 实现：在预设数据上应用修改
+        let mut item = self.get(id).await?;
+        if let Some(name) = req.name {
+            item.name = name;
+        }
+        if let Some(read_only) = req.read_only {
+            item.read_only = read_only;
+        }
+        item.updated_at = Some(Utc::now().to_rfc3339());
+        Ok(item)
+    }
+
     async fn delete(&self, id: &str) -> AppResult<()> {
         // Mock 实现：检查是否存在
         if self.connections.iter().any(|c| c.id == id) {
@@ -178,21 +423,49 @@ impl ConnectionServiceTrait for MockConnectionService {
     }
 }
 
-// ============================================================
-// 4️⃣ 演示：使用 Trait 统一调用
-// ============================================================
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
 
-/// 演示函数：展示如何使用 Trait 进行统一调用
-/// 
-/// 这个函数接受任何实现了 ConnectionServiceTrait 的类型
-/// 不管是真实实现还是 Mock 实现，调用方式完全一样
-pub async fn demo_trait_usage<S: ConnectionServiceTrait>(service: &S) {
-    // 获取所有连接
-    let connections = service.list().await;
-    println!("连接数量: {}", connections.len());
-    
-    // 遍历连接
-    for conn in &connections {
-        println!("  - {} ({})", conn.name, conn.id);
+    use super::*;
+
+    fn config(id: &str, body: serde_json::Value) -> ConnectionConfig {
+        let req: CreateConnectionRequest = serde_json::from_value(body).unwrap();
+        req.validate().unwrap();
+        req.into_config(id.to_string(), format!("2026-01-0{}T00:00:00Z", id)).unwrap()
+    }
+
+    fn list(configs: &[ConnectionConfig], query: serde_json::Value) -> Vec<String> {
+        let query: ConnectionListQuery = serde_json::from_value(query).unwrap();
+        let (field, descending) = parse_sort(query.sort.as_deref()).unwrap();
+        let filter = ListFilter::new(&query);
+        let mut matched: Vec<_> = configs.iter().filter(|c| filter.matches(c)).collect();
+        matched.sort_by(|a, b| compare(field, descending, a, b));
+        matched.into_iter().map(|c| c.id.clone()).collect()
+    }
+
+    #[test]
+    fn test_list_filters_and_sorts_connections() {
+        let configs = [
+            config("1", json!({"name": "orders", "db_type": "sqlite", "folder": " shop/ backend/", "tags": ["Billing", "billing", " "], "environment": "prod"})),
+            config("2", json!({"name": "Orders", "db_type": "sqlite", "folder": "shop", "tags": ["billing"], "environment": "dev"})),
+            config("3", json!({"name": "audit", "db_type": "sqlite", "folder": "shopfront", "color": "#0af"})),
+            config("4", json!({"name": "orders", "db_type": "sqlite", "environment": "staging"})),
+        ];
+        assert_eq!(configs[0].folder.as_deref(), Some("shop/backend"));
+        assert_eq!(configs[0].tags, ["Billing"]);
+
+        // 名称不区分大小写比较，完全相同时按 ID 排序，顺序稳定
+        assert_eq!(list(&configs, json!({})), ["3", "2", "1", "4"]);
+        assert_eq!(list(&configs, json!({"folder": "shop/"})), ["2", "1"]);
+        assert_eq!(list(&configs, json!({"tags": "BILLING", "search": "ORD"})), ["2", "1"]);
+        assert_eq!(list(&configs, json!({"environment": "prod"})), ["1"]);
+        // 缺少环境标签的连接无论升序降序都排在最后
+        assert_eq!(list(&configs, json!({"sort": "-environment"})), ["1", "4", "2", "3"]);
+        assert!(parse_sort(Some("host")).is_err());
+
+        let invalid: CreateConnectionRequest =
+            serde_json::from_value(json!({"name": "x", "db_type": "sqlite", "color": "blue"})).unwrap();
+        assert!(invalid.validate().is_err());
     }
 }
@@ -0,0 +1,488 @@
+//! Schema 快照服务
+//!
+//! 按需或按计划保存连接的 schema 结构快照，版本化存储在 `data_dir/snapshots/{连接 ID}/` 下：
+//! - `v000001.json`、`v000002.json` …：完整快照
+//! - `schedule.json`：定时快照配置
+//!
+//! 相邻快照之间的差异构成变更时间线；检测到变化时可通过 Webhook 通知。
+//! 定时快照只在结构发生变化时保存新版本。
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use common::errors::{AppError, AppResult};
+use common::models::snapshot::{
+    SchemaSnapshot, SnapshotChange, SnapshotResult, SnapshotSchedule, SnapshotScheduleRequest,
+    SnapshotSummary, SnapshotTrigger,
+};
+use common::utils::fs::write_atomic;
+use tokio::sync::{Mutex, RwLock};
+use tracing::{info, warn};
+use validator::Validate;
+
+use crate::dialect::Dialect;
+use crate::metadata::MetadataService;
+use crate::pool_manager::PoolManager;
+use crate::schema_diff::diff_tables;
+
+/// 调度器检查到期计划的间隔
+const SCHEDULER_TICK: Duration = Duration::from_secs(30);
+/// Webhook 请求超时时间
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+const SCHEDULE_FILE: &str = "schedule.json";
+
+// ============== 存储 ==============
+
+/// 快照存储
+pub struct SnapshotStore {
+    root: PathBuf,
+    /// 定时快照配置，按连接 ID 索引
+    schedules: RwLock<HashMap<String, SnapshotSchedule>>,
+    /// 串行化版本号分配和写入
+    write_lock: Mutex<()>,
+    http_client: reqwest::Client,
+}
+
+impl SnapshotStore {
+    /// 打开 `data_dir/snapshots`，加载已保存的定时快照配置
+    pub fn open(data_dir: &str) -> Self {
+        let root = PathBuf::from(data_dir).join("snapshots");
+        let mut schedules = HashMap::new();
+        if let Ok(entries) = std::fs::read_dir(&root) {
+            for entry in entries.flatten() {
+                let path = entry.path().join(SCHEDULE_FILE);
+                let Ok(contents) = std::fs::read(&path) else { continue };
+                match serde_json::from_slice::<SnapshotSchedule>(&contents) {
+                    Ok(schedule) => {
+                        schedules.insert(entry.file_name().to_string_lossy().to_string(), schedule);
+                    }
+                    Err(e) => warn!(path = %path.display(), error = %e, "忽略无法解析的快照计划"),
+                }
+            }
+        }
+        Self {
+            root,
+            schedules: RwLock::new(schedules),
+            write_lock: Mutex::new(()),
+            http_client: reqwest::Client::builder()
+                .timeout(WEBHOOK_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// 连接的快照目录；拒绝可能越出存储目录的 ID
+    fn connection_dir(&self, id: &str) -> AppResult<PathBuf> {
+        if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            return Err(AppError::InvalidInput(format!("非法的连接 ID: {}", id)));
+        }
+        Ok(self.root.join(id))
+    }
+
+    /// 已保存的版本号（升序）
+    async fn versions(&self, id: &str) -> AppResult<Vec<u32>> {
+        let mut entries = match tokio::fs::read_dir(self.connection_dir(id)?).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(e.into()),
+        };
+        let mut versions = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if let Some(version) = name
+                .strip_prefix('v')
+                .and_then(|n| n.strip_suffix(".json"))
+                .and_then(|n| n.parse().ok())
+            {
+                versions.push(version);
+            }
+        }
+        versions.sort_unstable();
+        Ok(versions)
+    }
+
+    /// 读取指定版本的快照
+    pub async fn load(&self, id: &str, version: u32) -> AppResult<SchemaSnapshot> {
+        let path = self.connection_dir(id)?.join(format!("v{:06}.json", version));
+        match tokio::fs::read(&path).await {
+            Ok(contents) => Ok(serde_json::from_slice(&contents)
+                .map_err(|e| AppError::Internal(format!("快照 {} 已损坏: {}", path.display(), e)))?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                Err(AppError::NotFound(format!("snapshot {} of connection {}", version, id)))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// 按版本升序读取所有快照
+    async fn load_all(&self, id: &str) -> AppResult<Vec<SchemaSnapshot>> {
+        let mut snapshots = Vec::new();
+        for version in self.versions(id).await? {
+            snapshots.push(self.load(id, version).await?);
+        }
+        Ok(snapshots)
+    }
+
+    async fn save(&self, snapshot: &SchemaSnapshot) -> AppResult<()> {
+        let path = self
+            .connection_dir(&snapshot.connection_id)?
+            .join(format!("v{:06}.json", snapshot.version));
+        write_atomic(&path, &serde_json::to_vec_pretty(snapshot)?).await?;
+        Ok(())
+    }
+
+    /// 获取连接的定时快照配置
+    pub async fn schedule(&self, id: &str) -> Option<SnapshotSchedule> {
+        self.schedules.read().await.get(id).cloned()
+    }
+
+    async fn save_schedule(&self, id: &str, schedule: SnapshotSchedule) -> AppResult<()> {
+        let path = self.connection_dir(id)?.join(SCHEDULE_FILE);
+        write_atomic(&path, &serde_json::to_vec_pretty(&schedule)?).await?;
+        self.schedules.write().await.insert(id.to_string(), schedule);
+        Ok(())
+    }
+
+    async fn remove_schedule(&self, id: &str) -> AppResult<bool> {
+        let path = self.connection_dir(id)?.join(SCHEDULE_FILE);
+        if self.schedules.write().await.remove(id).is_none() {
+            return Ok(false);
+        }
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(true),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// 删除连接的全部快照和定时快照配置
+    ///
+    /// 连接被删除后调用，避免以相同 ID 重新导入的连接沿用旧的时间线和 Webhook。
+    pub async fn remove_connection(&self, id: &str) -> AppResult<()> {
+        let dir = self.connection_dir(id)?;
+        let _guard = self.write_lock.lock().await;
+        self.schedules.write().await.remove(id);
+        match tokio::fs::remove_dir_all(&dir).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// 到期需要执行的定时快照
+    async fn due_schedules(&self, now: DateTime<Utc>) -> Vec<(String, SnapshotSchedule)> {
+        self.schedules
+            .read()
+            .await
+            .iter()
+            .filter(|(_, schedule)| {
+                schedule
+                    .last_run_at
+                    .as_deref()
+                    .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+                    .map(|last| (now - last.with_timezone(&Utc)).num_seconds() >= schedule.interval_secs as i64)
+                    .unwrap_or(true)
+            })
+            .map(|(id, schedule)| (id.clone(), schedule.clone()))
+            .collect()
+    }
+
+    /// 记录一次定时执行的结果
+    async fn record_run(&self, id: &str, error: Option<String>) {
+        let Some(mut schedule) = self.schedule(id).await else { return };
+        schedule.last_run_at = Some(Utc::now().to_rfc3339());
+        schedule.last_error = error;
+        if let Err(e) = self.save_schedule(id, schedule).await {
+            warn!(connection_id = %id, error = %e, "保存快照计划失败");
+        }
+    }
+
+    /// 向 Webhook 发送结构漂移通知（后台执行，失败只记录日志）
+    fn notify_drift(&self, url: String, connection_id: String, change: SnapshotChange) {
+        let client = self.http_client.clone();
+        tokio::spawn(async move {
+            let payload = serde_json::json!({
+                "event": "schema.drift",
+                "connection_id": connection_id,
+                "change": change,
+            });
+            match client.post(&url).json(&payload).send().await {
+                Ok(resp) if resp.status().is_success() => {
+                    info!(connection_id = %connection_id, url = %url, "已发送结构漂移通知")
+                }
+                Ok(resp) => {
+                    warn!(connection_id = %connection_id, url = %url, status = %resp.status(), "Webhook 返回错误状态")
+                }
+                Err(e) => warn!(connection_id = %connection_id, url = %url, error = %e, "Webhook 请求失败"),
+            }
+        });
+    }
+}
+
+// ============== 服务 ==============
+
+/// 快照服务
+pub struct SnapshotService {
+    pool_manager: Arc<PoolManager>,
+    store: Arc<SnapshotStore>,
+}
+
+impl SnapshotService {
+    /// 创建新的快照服务实例
+    pub fn new(pool_manager: Arc<PoolManager>, store: Arc<SnapshotStore>) -> Self {
+        Self { pool_manager, store }
+    }
+
+    /// 立即保存快照，并与同一 schema 的上一版本比较
+    ///
+    /// 定时快照在结构未变化时不保存新版本。
+    pub async fn take(&self, id: &str, schema: Option<String>, trigger: SnapshotTrigger) -> AppResult<SnapshotResult> {
+        let connection = self
+            .pool_manager
+            .get_connection(id)
+            .await
+            .ok_or_else(|| AppError::ConnectionNotFound(id.to_string()))?;
+        let dialect = Dialect::from_db_type(&connection.db_type)?;
+        let metadata = MetadataService::new(self.pool_manager.clone());
+        let schema = match schema {
+            Some(schema) => schema,
+            None => metadata.current_schema(id).await?,
+        };
+        let tables = metadata.schema_details(id, Some(&schema)).await?;
+
+        let _guard = self.store.write_lock.lock().await;
+        let versions = self.store.versions(id).await?;
+        let mut previous = None;
+        for version in versions.iter().rev() {
+            let snapshot = self.store.load(id, *version).await?;
+            if snapshot.schema == schema {
+                previous = Some(snapshot);
+                break;
+            }
+        }
+        let changes = previous
+            .as_ref()
+            .map(|p| diff_tables(dialect, &p.tables, &tables))
+            .unwrap_or_default();
+
+        if let (SnapshotTrigger::Scheduled, Some(previous), true) = (trigger, &previous, changes.is_empty()) {
+            return Ok(SnapshotResult {
+                snapshot: SnapshotSummary::from(previous),
+                created: false,
+                changes,
+            });
+        }
+
+        let snapshot = SchemaSnapshot {
+            connection_id: id.to_string(),
+            version: versions.last().copied().unwrap_or(0) + 1,
+            schema,
+            dialect: dialect.db_type(),
+            taken_at: Utc::now().to_rfc3339(),
+            trigger,
+            tables,
+        };
+        self.store.save(&snapshot).await?;
+        drop(_guard);
+
+        if let (Some(previous), false) = (&previous, changes.is_empty()) {
+            info!(connection_id = %id, version = snapshot.version, tables = changes.len(), "检测到结构变化");
+            if let Some(url) = self.store.schedule(id).await.and_then(|s| s.webhook_url) {
+                let change = SnapshotChange {
+                    from_version: previous.version,
+                    to_version: snapshot.version,
+                    schema: snapshot.schema.clone(),
+                    detected_at: snapshot.taken_at.clone(),
+                    tables: changes.clone(),
+                };
+                self.store.notify_drift(url, id.to_string(), change);
+            }
+        }
+
+        Ok(SnapshotResult {
+            snapshot: SnapshotSummary::from(&snapshot),
+            created: true,
+            changes,
+        })
+    }
+
+    /// 列出连接的所有快照（版本升序）
+    pub async fn list(&self, id: &str) -> AppResult<Vec<SnapshotSummary>> {
+        let snapshots = self.store.load_all(id).await?;
+        Ok(snapshots.iter().map(SnapshotSummary::from).collect())
+    }
+
+    /// 获取指定版本的完整快照
+    pub async fn get(&self, id: &str, version: u32) -> AppResult<SchemaSnapshot> {
+        self.store.load(id, version).await
+    }
+
+    /// 相邻快照（同一 schema）之间的变更时间线，只包含有变化的条目
+    pub async fn timeline(&self, id: &str) -> AppResult<Vec<SnapshotChange>> {
+        let snapshots = self.store.load_all(id).await?;
+        let mut latest: HashMap<&str, &SchemaSnapshot> = HashMap::new();
+        let mut timeline = Vec::new();
+        for snapshot in &snapshots {
+            if let Some(previous) = latest.insert(&snapshot.schema, snapshot) {
+                let dialect = Dialect::from_db_type(&snapshot.dialect)?;
+                let tables = diff_tables(dialect, &previous.tables, &snapshot.tables);
+                if !tables.is_empty() {
+                    timeline.push(SnapshotChange {
+                        from_version: previous.version,
+                        to_version: snapshot.version,
+                        schema: snapshot.schema.clone(),
+                        detected_at: snapshot.taken_at.clone(),
+                        tables,
+                    });
+                }
+            }
+        }
+        Ok(timeline)
+    }
+
+    /// 获取定时快照配置
+    pub async fn schedule(&self, id: &str) -> AppResult<SnapshotSchedule> {
+        self.store
+            .schedule(id)
+            .await
+            .ok_or_else(|| AppError::NotFound(format!("snapshot schedule of connection {}", id)))
+    }
+
+    /// 设置定时快照；保留上次执行时间，避免修改配置后立即重复执行
+    pub async fn set_schedule(&self, id: &str, req: SnapshotScheduleRequest) -> AppResult<SnapshotSchedule> {
+        req.validate()?;
+        let connection = self
+            .pool_manager
+            .get_connection(id)
+            .await
+            .ok_or_else(|| AppError::ConnectionNotFound(id.to_string()))?;
+        Dialect::from_db_type(&connection.db_type)?;
+
+        let previous = self.store.schedule(id).await;
+        let schedule = SnapshotSchedule {
+            interval_secs: req.interval_secs,
+            schema: req.schema,
+            webhook_url: req.webhook_url,
+            last_run_at: previous.as_ref().and_then(|s| s.last_run_at.clone()),
+            last_error: previous.and_then(|s| s.last_error),
+        };
+        self.store.save_schedule(id, schedule.clone()).await?;
+        Ok(schedule)
+    }
+
+    /// 删除定时快照配置
+    pub async fn remove_schedule(&self, id: &str) -> AppResult<()> {
+        if self.store.remove_schedule(id).await? {
+            Ok(())
+        } else {
+            Err(AppError::NotFound(format!("snapshot schedule of connection {}", id)))
+        }
+    }
+}
+
+/// 启动定时快照调度器
+pub fn spawn_scheduler(pool_manager: Arc<PoolManager>, store: Arc<SnapshotStore>) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(SCHEDULER_TICK);
+        loop {
+            ticker.tick().await;
+            for (id, schedule) in store.due_schedules(Utc::now()).await {
+                let service = SnapshotService::new(pool_manager.clone(), store.clone());
+                let result = service.take(&id, schedule.schema, SnapshotTrigger::Scheduled).await;
+                let error = match result {
+                    // 连接已被删除，清理遗留的快照和计划
+                    Err(AppError::ConnectionNotFound(_)) => {
+                        if let Err(e) = store.remove_connection(&id).await {
+                            warn!(connection_id = %id, error = %e, "清理已删除连接的快照失败");
+                        }
+                        continue;
+                    }
+                    Ok(result) => {
+                        if result.created {
+                            info!(connection_id = %id, version = result.snapshot.version, "已保存定时快照");
+                        }
+                        None
+                    }
+                    Err(e) => {
+                        warn!(connection_id = %id, error = %e, "定时快照失败");
+                        Some(e.to_string())
+                    }
+                };
+                store.record_run(&id, error).await;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::models::connection::{ConnectionConfig, DbType};
+    use common::models::schema_diff::ChangeKind;
+
+    use crate::test_support;
+
+    #[tokio::test]
+    async fn test_snapshots_are_versioned_and_scheduled_runs_skip_unchanged() {
+        let dir = std::env::temp_dir().join(format!("snapshot-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let config = ConnectionConfig {
+            file_path: Some(dir.join("db.sqlite").to_string_lossy().to_string()),
+            ..test_support::connection("snap", DbType::SQLite)
+        };
+        let (manager, pool) = test_support::sqlite_manager(config).await;
+        let store = Arc::new(SnapshotStore::open(&dir.to_string_lossy()));
+        let service = SnapshotService::new(manager, store.clone());
+
+        sqlx::query("CREATE TABLE users (id INTEGER PRIMARY KEY, email TEXT)").execute(&pool).await.unwrap();
+        let first = service.take("snap", None, SnapshotTrigger::Manual).await.unwrap();
+        assert!(first.created && first.changes.is_empty());
+        assert_eq!(first.snapshot.version, 1);
+
+        let unchanged = service.take("snap", None, SnapshotTrigger::Scheduled).await.unwrap();
+        assert!(!unchanged.created);
+        assert_eq!(unchanged.snapshot.version, 1);
+
+        sqlx::query("ALTER TABLE users ADD COLUMN name TEXT").execute(&pool).await.unwrap();
+        let changed = service.take("snap", None, SnapshotTrigger::Scheduled).await.unwrap();
+        assert!(changed.created);
+        assert_eq!(changed.snapshot.version, 2);
+
+        let timeline = service.timeline("snap").await.unwrap();
+        assert_eq!(timeline.len(), 1);
+        assert_eq!((timeline[0].from_version, timeline[0].to_version), (1, 2));
+        let column = &timeline[0].tables[0].columns[0];
+        assert_eq!((column.name.as_str(), column.change), ("name", ChangeKind::Added));
+
+        assert_eq!(service.list("snap").await.unwrap().len(), 2);
+        assert!(matches!(service.get("snap", 9).await, Err(AppError::NotFound(_))));
+        assert!(matches!(service.get("../snap", 1).await, Err(AppError::InvalidInput(_))));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn test_removed_connection_drops_schedule_and_snapshots() {
+        let dir = std::env::temp_dir().join(format!("snapshot-{}", uuid::Uuid::new_v4()));
+        let (manager, pool) = test_support::sqlite_manager(test_support::sqlite_connection("gone")).await;
+        let store = Arc::new(SnapshotStore::open(&dir.to_string_lossy()));
+        let service = SnapshotService::new(manager.clone(), store.clone());
+
+        sqlx::query("CREATE TABLE t (id INTEGER PRIMARY KEY)").execute(&pool).await.unwrap();
+        service.take("gone", None, SnapshotTrigger::Manual).await.unwrap();
+        let req = SnapshotScheduleRequest { interval_secs: 60, schema: None, webhook_url: None };
+        service.set_schedule("gone", req).await.unwrap();
+        assert_eq!(store.due_schedules(Utc::now()).await.len(), 1);
+
+        manager.remove_connection("gone").await.unwrap();
+        store.remove_connection("gone").await.unwrap();
+        assert!(store.due_schedules(Utc::now()).await.is_empty());
+        // 重新加载的存储中也没有遗留的计划
+        let reopened = SnapshotStore::open(&dir.to_string_lossy());
+        assert!(reopened.schedule("gone").await.is_none());
+        assert!(service.list("gone").await.unwrap().is_empty());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,399 @@
+//! 参数化 SQL 构建与结果解码
+//!
+//! 用户输入只会以绑定参数的形式进入 SQL，标识符统一按方言引用：
+//! - MySQL / SQLite 使用 `?` 占位符，按 JSON 值类型绑定
+//! - PostgreSQL 使用 `$n` 占位符，参数以文本绑定并转换为列类型
+//!
+//! 查询结果按列类型解码为 JSON 值，写入时按同样的规则把 JSON 值转换为参数。
+
+use common::errors::{AppError, AppResult};
+use serde_json::{Map, Number, Value};
+use sqlx::mysql::MySqlRow;
+use sqlx::postgres::PgRow;
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Row, TypeInfo, ValueRef};
+
+use crate::dialect::Dialect;
+use crate::pool_manager::DatabasePool;
+
+/// 绑定参数
+#[derive(Debug, Clone, PartialEq)]
+pub enum SqlParam {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Text(String),
+    Bytes(Vec<u8>),
+}
+
+impl SqlParam {
+    /// 从 JSON 标量转换；数组和对象不能作为单个参数
+    pub fn from_json(value: &Value) -> AppResult<Self> {
+        match value {
+            Value::Null => Ok(SqlParam::Null),
+            Value::Bool(b) => Ok(SqlParam::Bool(*b)),
+            Value::Number(n) => Ok(match n.as_i64() {
+                Some(i) => SqlParam::Int(i),
+                None => SqlParam::Float(n.as_f64().unwrap_or_default()),
+            }),
+            Value::String(s) => Ok(SqlParam::Text(s.clone())),
+            other => Err(AppError::InvalidInput(format!("不支持的参数值: {}", other))),
+        }
+    }
+
+    /// 按列的值类别转换：JSON 列整体序列化，二进制列接受 `0x` / `\x` 开头的十六进制
+    pub fn for_column(kind: ValueKind, value: &Value) -> AppResult<Self> {
+        match (kind, value) {
+            (_, Value::Null) => Ok(SqlParam::Null),
+            (ValueKind::Json, value) => Ok(SqlParam::Text(value.to_string())),
+            (ValueKind::Binary, Value::String(s)) => match s.strip_prefix("0x").or_else(|| s.strip_prefix("\\x")) {
+                Some(hex) => decode_hex(hex)
+                    .map(SqlParam::Bytes)
+                    .ok_or_else(|| AppError::InvalidInput(format!("无效的十六进制值: {}", s))),
+                None => Ok(SqlParam::Bytes(s.as_bytes().to_vec())),
+            },
+            (_, value) => Self::from_json(value),
+        }
+    }
+
+    /// PostgreSQL 中以文本形式绑定
+    fn as_text(&self) -> Option<String> {
+        match self {
+            SqlParam::Null => None,
+            SqlParam::Bool(b) => Some(b.to_string()),
+            SqlParam::Int(i) => Some(i.to_string()),
+            SqlParam::Float(f) => Some(f.to_string()),
+            SqlParam::Text(s) => Some(s.clone()),
+            SqlParam::Bytes(bytes) => Some(format!("\\x{}", encode_hex(bytes))),
+        }
+    }
+}
+
+/// 按参数类型绑定（MySQL / SQLite）
+macro_rules! bind_typed {
+    ($query:expr, $params:expr) => {{
+        let mut query = $query;
+        for param in $params {
+            query = match param {
+                SqlParam::Null => query.bind(None::<String>),
+                SqlParam::Bool(b) => query.bind(*b),
+                SqlParam::Int(i) => query.bind(*i),
+                SqlParam::Float(f) => query.bind(*f),
+                SqlParam::Text(s) => query.bind(s.as_str()),
+                SqlParam::Bytes(bytes) => query.bind(bytes.as_slice()),
+            };
+        }
+        query
+    }};
+}
+
+/// 以文本绑定（PostgreSQL，由 SQL 中的 CAST 转换类型）
+macro_rules! bind_text {
+    ($query:expr, $params:expr) => {{
+        let mut query = $query;
+        for param in $params {
+            query = query.bind(param.as_text());
+        }
+        query
+    }};
+}
+
+/// 列值在 JSON 中的表示方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueKind {
+    Boolean,
+    Integer,
+    Unsigned,
+    Number,
+    Json,
+    Binary,
+    Text,
+}
+
+impl ValueKind {
+    /// 根据原生列类型判断
+    pub fn of(dialect: Dialect, data_type: &str) -> Self {
+        let lower = data_type.trim().to_lowercase();
+        if lower.ends_with("[]") {
+            return ValueKind::Text;
+        }
+        let base = lower.split(['(', ' ']).next().unwrap_or_default();
+        match base {
+            "bool" | "boolean" => ValueKind::Boolean,
+            "tinyint" | "smallint" | "mediumint" | "int" | "integer" | "bigint" | "int2" | "int4" | "int8"
+            | "serial" | "bigserial" | "smallserial" | "year" | "bit" => {
+                if dialect == Dialect::MySql && lower.contains("unsigned") {
+                    ValueKind::Unsigned
+                } else {
+                    ValueKind::Integer
+                }
+            }
+            "decimal" | "numeric" | "real" | "float" | "float4" | "float8" | "double" => ValueKind::Number,
+            "json" | "jsonb" => ValueKind::Json,
+            "bytea" | "blob" | "tinyblob" | "mediumblob" | "longblob" | "binary" | "varbinary" => ValueKind::Binary,
+            _ => ValueKind::Text,
+        }
+    }
+}
+
+/// 查询列表达式，保证结果能按 `kind` 解码（列名作为别名）
+pub fn select_expr(dialect: Dialect, column: &str, kind: ValueKind) -> String {
+    let ident = dialect.quote_ident(column);
+    match dialect {
+        Dialect::MySql => match kind {
+            ValueKind::Integer | ValueKind::Boolean => format!("CAST({} AS SIGNED) AS {}", ident, ident),
+            ValueKind::Unsigned => format!("CAST({} AS UNSIGNED) AS {}", ident, ident),
+            ValueKind::Binary => format!("CONCAT('0x', HEX({})) AS {}", ident, ident),
+            _ => format!("CAST({} AS CHAR) AS {}", ident, ident),
+        },
+        Dialect::Postgres => format!("{}::text AS {}", ident, ident),
+        Dialect::Sqlite => ident,
+    }
+}
+
+/// 参数化 SQL 构建器
+pub struct SqlBuilder {
+    dialect: Dialect,
+    sql: String,
+    params: Vec<SqlParam>,
+}
+
+impl SqlBuilder {
+    /// 创建空的构建器
+    pub fn new(dialect: Dialect) -> Self {
+        Self {
+            dialect,
+            sql: String::new(),
+            params: Vec::new(),
+        }
+    }
+
+    /// 追加 SQL 片段（只能是服务端生成的文本）
+    pub fn push(&mut self, sql: &str) -> &mut Self {
+        self.sql.push_str(sql);
+        self
+    }
+
+    /// 追加引用后的标识符
+    pub fn push_ident(&mut self, ident: &str) -> &mut Self {
+        let quoted = self.dialect.quote_ident(ident);
+        self.push(&quoted)
+    }
+
+    /// 追加绑定参数；PostgreSQL 中参数按 `cast` 转换为列类型
+    pub fn push_param(&mut self, param: SqlParam, cast: Option<&str>) -> &mut Self {
+        self.params.push(param);
+        let placeholder = match (self.dialect, cast) {
+            (Dialect::Postgres, Some(data_type)) => format!("CAST(${} AS {})", self.params.len(), data_type),
+            (Dialect::Postgres, None) => format!("${}", self.params.len()),
+            _ => "?".to_string(),
+        };
+        self.push(&placeholder)
+    }
+
+    /// 执行查询并按列解码为 JSON 对象
+    pub async fn fetch_rows(&self, pool: &DatabasePool, columns: &[(String, ValueKind)]) -> AppResult<Vec<Value>> {
+        let params = &self.params;
+        let rows: Vec<Value> = match pool {
+            DatabasePool::MySQL(pool) => {
+                let rows = bind_typed!(sqlx::query(&self.sql), params).fetch_all(pool).await?;
+                rows.iter().map(|row| decode_mysql(row, columns)).collect::<AppResult<_>>()?
+            }
+            DatabasePool::Postgres(pool) => {
+                let rows = bind_text!(sqlx::query(&self.sql), params).fetch_all(pool).await?;
+                rows.iter().map(|row| decode_postgres(row, columns)).collect::<AppResult<_>>()?
+            }
+            DatabasePool::SQLite(pool) => {
+                let rows = bind_typed!(sqlx::query(&self.sql), params).fetch_all(pool).await?;
+                rows.iter().map(|row| decode_sqlite(row, columns)).collect::<AppResult<_>>()?
+            }
+            _ => return Err(AppError::UnsupportedDatabaseType("仅支持 MySQL、PostgreSQL 和 SQLite 连接".into())),
+        };
+        Ok(rows)
+    }
+
+    /// 执行查询并返回第一行第一列的整数（用于 COUNT）
+    pub async fn fetch_count(&self, pool: &DatabasePool) -> AppResult<i64> {
+        let columns = [("count".to_string(), ValueKind::Integer)];
+        let rows = self.fetch_rows(pool, &columns).await?;
+        Ok(rows
+            .first()
+            .and_then(|row| row.get("count"))
+            .and_then(Value::as_i64)
+            .unwrap_or(0))
+    }
+}
+
+/// 在一个事务中依次执行语句，返回各语句影响的行数
+///
+/// 每条语句执行后调用 `check(序号, 影响行数)`，任一语句失败或 `check` 返回错误时整体回滚。
+pub async fn execute_in_transaction<F>(pool: &DatabasePool, statements: &[SqlBuilder], mut check: F) -> AppResult<Vec<u64>>
+where
+    F: FnMut(usize, u64) -> AppResult<()>,
+{
+    let mut affected = Vec::with_capacity(statements.len());
+    match pool {
+        DatabasePool::MySQL(pool) => {
+            let mut tx = pool.begin().await?;
+            for (i, statement) in statements.iter().enumerate() {
+                let result = bind_typed!(sqlx::query(&statement.sql), &statement.params)
+                    .execute(&mut *tx)
+                    .await?;
+                check(i, result.rows_affected())?;
+                affected.push(result.rows_affected());
+            }
+            tx.commit().await?;
+        }
+        DatabasePool::Postgres(pool) => {
+            let mut tx = pool.begin().await?;
+            for (i, statement) in statements.iter().enumerate() {
+                let result = bind_text!(sqlx::query(&statement.sql), &statement.params)
+                    .execute(&mut *tx)
+                    .await?;
+                check(i, result.rows_affected())?;
+                affected.push(result.rows_affected());
+            }
+            tx.commit().await?;
+        }
+        DatabasePool::SQLite(pool) => {
+            let mut tx = pool.begin().await?;
+            for (i, statement) in statements.iter().enumerate() {
+                let result = bind_typed!(sqlx::query(&statement.sql), &statement.params)
+                    .execute(&mut *tx)
+                    .await?;
+                check(i, result.rows_affected())?;
+                affected.push(result.rows_affected());
+            }
+            tx.commit().await?;
+        }
+        _ => return Err(AppError::UnsupportedDatabaseType("仅支持 MySQL、PostgreSQL 和 SQLite 连接".into())),
+    }
+    Ok(affected)
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02X}", b)).collect()
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| hex.get(i..i + 2).and_then(|b| u8::from_str_radix(b, 16).ok()))
+        .collect()
+}
+
+/// 将文本值按类别转换为 JSON
+fn text_to_json(text: String, kind: ValueKind) -> Value {
+    match kind {
+        ValueKind::Boolean => match text.as_str() {
+            "t" | "true" | "1" => Value::Bool(true),
+            "f" | "false" | "0" => Value::Bool(false),
+            _ => Value::String(text),
+        },
+        ValueKind::Integer | ValueKind::Unsigned | ValueKind::Number => {
+            // NaN / Infinity 等非有限值保留为字符串
+            match serde_json::from_str::<Number>(&text) {
+                Ok(n) => Value::Number(n),
+                Err(_) => Value::String(text),
+            }
+        }
+        ValueKind::Json => serde_json::from_str(&text).unwrap_or(Value::String(text)),
+        ValueKind::Binary | ValueKind::Text => Value::String(text),
+    }
+}
+
+fn decode_mysql(row: &MySqlRow, columns: &[(String, ValueKind)]) -> AppResult<Value> {
+    let mut object = Map::with_capacity(columns.len());
+    for (i, (name, kind)) in columns.iter().enumerate() {
+        let value = match kind {
+            ValueKind::Integer | ValueKind::Boolean => row.try_get::<Option<i64>, _>(i)?.map(Value::from),
+            ValueKind::Unsigned => row.try_get::<Option<u64>, _>(i)?.map(Value::from),
+            _ => row.try_get::<Option<String>, _>(i)?.map(|t| text_to_json(t, *kind)),
+        };
+        object.insert(name.clone(), value.unwrap_or(Value::Null));
+    }
+    Ok(Value::Object(object))
+}
+
+fn decode_postgres(row: &PgRow, columns: &[(String, ValueKind)]) -> AppResult<Value> {
+    let mut object = Map::with_capacity(columns.len());
+    for (i, (name, kind)) in columns.iter().enumerate() {
+        // COUNT(*) 等未转换为文本的整数列
+        let value = if row.try_get_raw(i)?.type_info().name() == "INT8" {
+            row.try_get::<Option<i64>, _>(i)?.map(Value::from)
+        } else {
+            row.try_get::<Option<String>, _>(i)?.map(|t| text_to_json(t, *kind))
+        };
+        object.insert(name.clone(), value.unwrap_or(Value::Null));
+    }
+    Ok(Value::Object(object))
+}
+
+fn decode_sqlite(row: &SqliteRow, columns: &[(String, ValueKind)]) -> AppResult<Value> {
+    let mut object = Map::with_capacity(columns.len());
+    for (i, (name, kind)) in columns.iter().enumerate() {
+        let raw = row.try_get_raw(i)?;
+        // SQLite 按值的存储类型解码，而不是列声明类型
+        let value = if raw.is_null() {
+            Value::Null
+        } else {
+            match raw.type_info().name() {
+                "INTEGER" => {
+                    let n: i64 = row.try_get(i)?;
+                    match kind {
+                        ValueKind::Boolean => Value::Bool(n != 0),
+                        _ => Value::from(n),
+                    }
+                }
+                "REAL" => Number::from_f64(row.try_get(i)?).map(Value::Number).unwrap_or(Value::Null),
+                "BLOB" => {
+                    let bytes: Vec<u8> = row.try_get(i)?;
+                    Value::String(format!("0x{}", encode_hex(&bytes)))
+                }
+                _ => text_to_json(row.try_get(i)?, *kind),
+            }
+        };
+        object.insert(name.clone(), value);
+    }
+    Ok(Value::Object(object))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_value_kind() {
+        assert_eq!(ValueKind::of(Dialect::MySql, "int unsigned"), ValueKind::Unsigned);
+        assert_eq!(ValueKind::of(Dialect::MySql, "tinyint(1)"), ValueKind::Integer);
+        assert_eq!(ValueKind::of(Dialect::Postgres, "numeric(10,2)"), ValueKind::Number);
+        assert_eq!(ValueKind::of(Dialect::Postgres, "double precision"), ValueKind::Number);
+        assert_eq!(ValueKind::of(Dialect::Postgres, "integer[]"), ValueKind::Text);
+        assert_eq!(ValueKind::of(Dialect::Postgres, "interval"), ValueKind::Text);
+        assert_eq!(ValueKind::of(Dialect::Sqlite, "BOOLEAN"), ValueKind::Boolean);
+    }
+
+    #[test]
+    fn test_placeholders() {
+        let mut pg = SqlBuilder::new(Dialect::Postgres);
+        pg.push("SELECT * FROM ")
+            .push_ident("t")
+            .push(" WHERE ")
+            .push_ident("id")
+            .push(" = ")
+            .push_param(SqlParam::Int(1), Some("integer"))
+            .push(" AND ")
+            .push_ident("name")
+            .push(" LIKE ")
+            .push_param(SqlParam::Text("a%".into()), None);
+        assert_eq!(pg.sql, "SELECT * FROM \"t\" WHERE \"id\" = CAST($1 AS integer) AND \"name\" LIKE $2");
+
+        let mut mysql = SqlBuilder::new(Dialect::MySql);
+        mysql.push_ident("id").push(" = ").push_param(SqlParam::Int(1), Some("int"));
+        assert_eq!(mysql.sql, "`id` = ?");
+    }
+}
@@ -1,21 +1,43 @@
 //! Application state for connection service.
 
 use std::sync::Arc;
+use std::time::Duration;
 use common::config::AppConfig;
+use crate::audit::AuditLog;
+use crate::connection_store::ConnectionStore;
 use crate::pool_manager::PoolManager;
+use crate::profile::ProfileJobStore;
+use crate::schema_cache::SchemaCache;
+use crate::search_index::SearchIndex;
+use crate::secrets::Keyring;
+use crate::snapshot::SnapshotStore;
 
 /// Application state shared across handlers.
 #[derive(Clone)]
 pub struct AppState {
     pub config: AppConfig,
     pub pool_manager: Arc<PoolManager>,
+    pub snapshots: Arc<SnapshotStore>,
+    pub profile_jobs: Arc<ProfileJobStore>,
+    pub schema_cache: Arc<SchemaCache>,
+    pub search_index: Arc<SearchIndex>,
+    pub audit: Arc<AuditLog>,
+    pub keyring: Arc<Keyring>,
 }
 
 impl AppState {
     /// Creates a new application state.
-    pub fn new(config: AppConfig) -> Self {
+    pub fn new(config: AppConfig, keyring: Keyring) -> Self {
+        let keyring = Arc::new(keyring);
+        let store = ConnectionStore::open(&config.data_dir, keyring.clone());
         Self {
-            pool_manager: Arc::new(PoolManager::new(config.clone())),
+            pool_manager: Arc::new(PoolManager::with_store(config.clone(), store)),
+            snapshots: Arc::new(SnapshotStore::open(&config.data_dir)),
+            profile_jobs: Arc::new(ProfileJobStore::default()),
+            schema_cache: Arc::new(SchemaCache::new(Duration::from_secs(config.schema_cache_ttl_secs))),
+            audit: Arc::new(AuditLog::open(&config.data_dir)),
+            search_index: Arc::new(SearchIndex::new(Duration::from_secs(config.search_index_interval_secs))),
+            keyring,
             config,
         }
     }
@@ -0,0 +1,452 @@
+//! 存储统计服务
+//!
+//! 统计 schema 中各表和索引的大小、行数估计、扫描次数及最近的维护时间：
+//! - PostgreSQL：`pg_class` 的大小函数，`pg_stat_user_tables` / `pg_stat_user_indexes`
+//! - MySQL：`information_schema.TABLES` / `STATISTICS`；索引大小来自 `mysql.innodb_index_stats`，
+//!   扫描次数来自 `performance_schema`，无权限或未启用时省略
+//! - SQLite：`dbstat` 虚拟表，行数来自 `sqlite_stat1`，未分析时精确计数
+//!
+//! 结果在内存中排序和分页。
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::DateTime;
+use common::errors::{AppError, AppResult};
+use common::models::storage::{IndexStorage, StorageQuery, TableStorage};
+use common::response::PaginatedData;
+use sqlx::{MySqlPool, PgPool, Row, SqlitePool};
+
+use crate::dialect::Dialect;
+use crate::metadata::MetadataService;
+use crate::pool_manager::{DatabasePool, PoolManager};
+
+const DEFAULT_PAGE_SIZE: u32 = 50;
+const MAX_PAGE_SIZE: u32 = 1000;
+
+/// 存储统计服务
+pub struct StorageService {
+    pool_manager: Arc<PoolManager>,
+}
+
+impl StorageService {
+    /// 创建新的存储统计服务实例
+    pub fn new(pool_manager: Arc<PoolManager>) -> Self {
+        Self { pool_manager }
+    }
+
+    /// 获取 schema 中各表的存储统计
+    pub async fn tables(&self, id: &str, query: &StorageQuery) -> AppResult<PaginatedData<TableStorage>> {
+        let (field, descending) = parse_sort(query.sort.as_deref())?;
+        let pool = self.pool_manager.pool(id).await?;
+        let schema = match &query.schema {
+            Some(schema) => schema.clone(),
+            None => MetadataService::new(self.pool_manager.clone()).current_schema(id).await?,
+        };
+        let mut tables = match &pool {
+            DatabasePool::Postgres(pool) => postgres_tables(pool, &schema).await?,
+            DatabasePool::MySQL(pool) => mysql_tables(pool, &schema).await?,
+            DatabasePool::SQLite(pool) => sqlite_tables(pool, &schema).await?,
+            _ => {
+                return Err(AppError::UnsupportedDatabaseType(
+                    "存储统计仅支持 MySQL、PostgreSQL 和 SQLite 连接".into(),
+                ))
+            }
+        };
+        if query.unused_indexes.unwrap_or(false) {
+            tables.retain(|t| t.indexes.iter().any(|i| i.unused));
+        }
+
+        tables.sort_by(|a, b| {
+            let ordering = compare(field, a, b);
+            let ordering = if descending { ordering.reverse() } else { ordering };
+            // 缺失值总是排在最后
+            match (field.is_missing(a), field.is_missing(b)) {
+                (false, true) => Ordering::Less,
+                (true, false) => Ordering::Greater,
+                _ => ordering,
+            }
+            .then_with(|| a.table.cmp(&b.table))
+        });
+
+        let page = query.page.unwrap_or(1).max(1);
+        let page_size = query.page_size.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+        let total = tables.len() as u64;
+        let items = tables
+            .into_iter()
+            .skip((page as usize - 1) * page_size as usize)
+            .take(page_size as usize)
+            .collect();
+        Ok(PaginatedData::new(items, page, page_size, total))
+    }
+}
+
+/// 可排序字段
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SortField {
+    Table,
+    RowEstimate,
+    TableBytes,
+    IndexBytes,
+    TotalBytes,
+    SeqScans,
+    IndexScans,
+    LastVacuum,
+    LastAnalyze,
+}
+
+impl SortField {
+    fn number(self, t: &TableStorage) -> Option<i64> {
+        match self {
+            SortField::RowEstimate => t.row_estimate,
+            SortField::TableBytes => t.table_bytes,
+            SortField::IndexBytes => t.index_bytes,
+            SortField::TotalBytes => t.total_bytes,
+            SortField::SeqScans => t.seq_scans,
+            SortField::IndexScans => t.index_scans,
+            _ => None,
+        }
+    }
+
+    fn text(self, t: &TableStorage) -> Option<&str> {
+        match self {
+            SortField::Table => Some(&t.table),
+            SortField::LastVacuum => t.last_vacuum.as_deref(),
+            SortField::LastAnalyze => t.last_analyze.as_deref(),
+            _ => None,
+        }
+    }
+
+    fn is_missing(self, t: &TableStorage) -> bool {
+        self.number(t).is_none() && self.text(t).is_none()
+    }
+}
+
+fn parse_sort(sort: Option<&str>) -> AppResult<(SortField, bool)> {
+    let sort = sort.map(str::trim).filter(|s| !s.is_empty()).unwrap_or("-total_bytes");
+    let (name, descending) = match sort.strip_prefix('-') {
+        Some(name) => (name, true),
+        None => (sort, false),
+    };
+    let field = match name {
+        "table" => SortField::Table,
+        "row_estimate" => SortField::RowEstimate,
+        "table_bytes" => SortField::TableBytes,
+        "index_bytes" => SortField::IndexBytes,
+        "total_bytes" => SortField::TotalBytes,
+        "seq_scans" => SortField::SeqScans,
+        "index_scans" => SortField::IndexScans,
+        "last_vacuum" => SortField::LastVacuum,
+        "last_analyze" => SortField::LastAnalyze,
+        _ => return Err(AppError::InvalidInput(format!("不支持按 {} 排序", name))),
+    };
+    Ok((field, descending))
+}
+
+fn compare(field: SortField, a: &TableStorage, b: &TableStorage) -> Ordering {
+    // RFC 3339 时间戳（同为 UTC）可以按字符串比较
+    field.number(a).cmp(&field.number(b)).then_with(|| field.text(a).cmp(&field.text(b)))
+}
+
+fn epoch_to_rfc3339(secs: Option<i64>) -> Option<String> {
+    secs.and_then(|s| DateTime::from_timestamp(s, 0)).map(|t| t.to_rfc3339())
+}
+
+/// 索引从未被扫描且不承担约束时视为未使用
+fn index(name: String, bytes: Option<i64>, scans: Option<i64>, unique: bool, primary: bool) -> IndexStorage {
+    IndexStorage {
+        unused: scans == Some(0) && !unique && !primary,
+        name,
+        bytes,
+        scans,
+        unique,
+        primary,
+    }
+}
+
+async fn postgres_tables(pool: &PgPool, schema: &str) -> AppResult<Vec<TableStorage>> {
+    let rows = sqlx::query(
+        "SELECT c.relname::text AS table_name, \
+                CASE WHEN c.reltuples >= 0 THEN c.reltuples::int8 ELSE s.n_live_tup END AS row_estimate, \
+                pg_table_size(c.oid) AS table_bytes, pg_indexes_size(c.oid) AS index_bytes, \
+                pg_total_relation_size(c.oid) AS total_bytes, s.seq_scan AS seq_scans, s.idx_scan AS index_scans, \
+                EXTRACT(EPOCH FROM GREATEST(s.last_vacuum, s.last_autovacuum))::int8 AS last_vacuum, \
+                EXTRACT(EPOCH FROM GREATEST(s.last_analyze, s.last_autoanalyze))::int8 AS last_analyze \
+         FROM pg_class c \
+         JOIN pg_namespace n ON n.oid = c.relnamespace \
+         LEFT JOIN pg_stat_user_tables s ON s.relid = c.oid \
+         WHERE n.nspname = $1 AND c.relkind IN ('r', 'p', 'm')",
+    )
+    .bind(schema)
+    .fetch_all(pool)
+    .await?;
+
+    let index_rows = sqlx::query(
+        "SELECT t.relname::text AS table_name, i.relname::text AS index_name, \
+                pg_relation_size(i.oid) AS bytes, s.idx_scan AS scans, x.indisunique AS is_unique, \
+                x.indisprimary AS is_primary \
+         FROM pg_index x \
+         JOIN pg_class i ON i.oid = x.indexrelid \
+         JOIN pg_class t ON t.oid = x.indrelid \
+         JOIN pg_namespace n ON n.oid = t.relnamespace \
+         LEFT JOIN pg_stat_user_indexes s ON s.indexrelid = x.indexrelid \
+         WHERE n.nspname = $1 \
+         ORDER BY i.relname",
+    )
+    .bind(schema)
+    .fetch_all(pool)
+    .await?;
+    let mut indexes: HashMap<String, Vec<IndexStorage>> = HashMap::new();
+    for row in &index_rows {
+        indexes.entry(row.try_get("table_name")?).or_default().push(index(
+            row.try_get("index_name")?,
+            row.try_get("bytes")?,
+            row.try_get("scans")?,
+            row.try_get("is_unique")?,
+            row.try_get("is_primary")?,
+        ));
+    }
+
+    rows.iter()
+        .map(|row| {
+            let table: String = row.try_get("table_name")?;
+            Ok(TableStorage {
+                schema: schema.to_string(),
+                row_estimate: row.try_get("row_estimate")?,
+                table_bytes: row.try_get("table_bytes")?,
+                index_bytes: row.try_get("index_bytes")?,
+                total_bytes: row.try_get("total_bytes")?,
+                seq_scans: row.try_get("seq_scans")?,
+                index_scans: row.try_get("index_scans")?,
+                last_vacuum: epoch_to_rfc3339(row.try_get("last_vacuum")?),
+                last_analyze: epoch_to_rfc3339(row.try_get("last_analyze")?),
+                indexes: indexes.remove(&table).unwrap_or_default(),
+                table,
+            })
+        })
+        .collect()
+}
+
+async fn mysql_tables(pool: &MySqlPool, schema: &str) -> AppResult<Vec<TableStorage>> {
+    let rows = sqlx::query(
+        "SELECT CAST(TABLE_NAME AS CHAR) AS table_name, CAST(TABLE_ROWS AS SIGNED) AS row_estimate, \
+                CAST(DATA_LENGTH AS SIGNED) AS table_bytes, CAST(INDEX_LENGTH AS SIGNED) AS index_bytes \
+         FROM information_schema.TABLES \
+         WHERE TABLE_SCHEMA = ? AND TABLE_TYPE = 'BASE TABLE'",
+    )
+    .bind(schema)
+    .fetch_all(pool)
+    .await?;
+    let index_rows = sqlx::query(
+        "SELECT CAST(TABLE_NAME AS CHAR) AS table_name, CAST(INDEX_NAME AS CHAR) AS index_name, \
+                CAST(MIN(NON_UNIQUE) AS SIGNED) AS non_unique \
+         FROM information_schema.STATISTICS \
+         WHERE TABLE_SCHEMA = ? \
+         GROUP BY TABLE_NAME, INDEX_NAME \
+         ORDER BY INDEX_NAME",
+    )
+    .bind(schema)
+    .fetch_all(pool)
+    .await?;
+
+    // 以下统计需要额外权限或 performance_schema，不可用时省略
+    let index_bytes: HashMap<(String, String), i64> = sqlx::query_as::<_, (String, String, i64)>(
+        "SELECT CAST(table_name AS CHAR), CAST(index_name AS CHAR), CAST(stat_value * @@innodb_page_size AS SIGNED) \
+         FROM mysql.innodb_index_stats WHERE database_name = ? AND stat_name = 'size'",
+    )
+    .bind(schema)
+    .fetch_all(pool)
+    .await
+    .map(|rows| rows.into_iter().map(|(t, i, b)| ((t, i), b)).collect())
+    .unwrap_or_default();
+    let usage: Option<HashMap<(String, String), i64>> = sqlx::query_as::<_, (String, String, i64)>(
+        "SELECT CAST(OBJECT_NAME AS CHAR), CAST(COALESCE(INDEX_NAME, '') AS CHAR), CAST(COUNT_READ AS SIGNED) \
+         FROM performance_schema.table_io_waits_summary_by_index_usage WHERE OBJECT_SCHEMA = ?",
+    )
+    .bind(schema)
+    .fetch_all(pool)
+    .await
+    .ok()
+    .map(|rows| rows.into_iter().map(|(t, i, n)| ((t, i), n)).collect());
+    let analyzed: HashMap<String, i64> = sqlx::query_as::<_, (String, i64)>(
+        "SELECT CAST(table_name AS CHAR), CAST(UNIX_TIMESTAMP(last_update) AS SIGNED) \
+         FROM mysql.innodb_table_stats WHERE database_name = ?",
+    )
+    .bind(schema)
+    .fetch_all(pool)
+    .await
+    .map(|rows| rows.into_iter().collect())
+    .unwrap_or_default();
+
+    let mut indexes: HashMap<String, Vec<IndexStorage>> = HashMap::new();
+    for row in &index_rows {
+        let table: String = row.try_get("table_name")?;
+        let name: String = row.try_get("index_name")?;
+        let key = (table.clone(), name.clone());
+        // performance_schema 中没有记录的索引视为未被扫描
+        let scans = usage.as_ref().map(|u| u.get(&key).copied().unwrap_or(0));
+        let primary = name == "PRIMARY";
+        let unique = row.try_get::<i64, _>("non_unique")? == 0;
+        indexes
+            .entry(table)
+            .or_default()
+            .push(index(name, index_bytes.get(&key).copied(), scans, unique, primary));
+    }
+
+    rows.iter()
+        .map(|row| {
+            let table: String = row.try_get("table_name")?;
+            let table_bytes: Option<i64> = row.try_get("table_bytes")?;
+            let index_bytes: Option<i64> = row.try_get("index_bytes")?;
+            let indexes = indexes.remove(&table).unwrap_or_default();
+            let (seq_scans, index_scans) = match &usage {
+                Some(usage) => (
+                    usage.get(&(table.clone(), String::new())).copied(),
+                    Some(indexes.iter().filter_map(|i| i.scans).sum()),
+                ),
+                None => (None, None),
+            };
+            Ok(TableStorage {
+                schema: schema.to_string(),
+                row_estimate: row.try_get("row_estimate")?,
+                total_bytes: table_bytes.zip(index_bytes).map(|(t, i)| t + i),
+                table_bytes,
+                index_bytes,
+                seq_scans,
+                index_scans,
+                last_vacuum: None,
+                last_analyze: epoch_to_rfc3339(analyzed.get(&table).copied()),
+                indexes,
+                table,
+            })
+        })
+        .collect()
+}
+
+async fn sqlite_tables(pool: &SqlitePool, schema: &str) -> AppResult<Vec<TableStorage>> {
+    let master = format!("{}.sqlite_master", Dialect::Sqlite.quote_ident(schema));
+    let tables: Vec<String> = sqlx::query_scalar(&format!(
+        "SELECT name FROM {} WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+        master
+    ))
+    .fetch_all(pool)
+    .await?;
+    let index_rows = sqlx::query(&format!(
+        "SELECT m.name AS table_name, il.name AS index_name, il.\"unique\" AS is_unique, il.origin AS origin \
+         FROM {} m, pragma_index_list(m.name, ?) il \
+         WHERE m.type = 'table' ORDER BY il.name",
+        master
+    ))
+    .bind(schema)
+    .fetch_all(pool)
+    .await?;
+    let sizes: HashMap<String, i64> = sqlx::query_as::<_, (String, i64)>(
+        "SELECT name, SUM(pgsize) FROM dbstat WHERE schema = ? GROUP BY name",
+    )
+    .bind(schema)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| AppError::DatabaseQuery(format!("读取 dbstat 失败（需要启用 SQLITE_ENABLE_DBSTAT_VTAB）：{}", e)))?
+    .into_iter()
+    .collect();
+    let stats: HashMap<String, i64> = sqlx::query_as::<_, (String, String)>(&format!(
+        "SELECT tbl, stat FROM {}.sqlite_stat1 WHERE idx IS NULL OR idx = tbl",
+        Dialect::Sqlite.quote_ident(schema)
+    ))
+    .fetch_all(pool)
+    .await
+    .map(|rows| {
+        rows.into_iter()
+            .filter_map(|(t, s)| Some((t, s.split_whitespace().next()?.parse().ok()?)))
+            .collect()
+    })
+    .unwrap_or_default();
+
+    let mut indexes: HashMap<String, Vec<IndexStorage>> = HashMap::new();
+    for row in &index_rows {
+        let name: String = row.try_get("index_name")?;
+        let origin: String = row.try_get("origin")?;
+        let bytes = sizes.get(&name).copied();
+        indexes.entry(row.try_get("table_name")?).or_default().push(index(
+            name,
+            bytes,
+            None,
+            row.try_get::<i64, _>("is_unique")? != 0,
+            origin == "pk",
+        ));
+    }
+
+    let mut result = Vec::with_capacity(tables.len());
+    for table in tables {
+        let row_estimate = match stats.get(&table) {
+            Some(&n) => n,
+            None => {
+                let sql = format!(
+                    "SELECT COUNT(*) FROM {}",
+                    Dialect::Sqlite.qualified_table(Some(schema), &table)
+                );
+                sqlx::query_scalar(&sql).fetch_one(pool).await?
+            }
+        };
+        let indexes = indexes.remove(&table).unwrap_or_default();
+        let table_bytes = sizes.get(&table).copied();
+        let index_bytes: i64 = indexes.iter().filter_map(|i| i.bytes).sum();
+        result.push(TableStorage {
+            schema: schema.to_string(),
+            row_estimate: Some(row_estimate),
+            total_bytes: table_bytes.map(|t| t + index_bytes),
+            table_bytes,
+            index_bytes: Some(index_bytes),
+            seq_scans: None,
+            index_scans: None,
+            last_vacuum: None,
+            last_analyze: None,
+            indexes,
+            table,
+        });
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    #[tokio::test]
+    async fn test_sqlite_storage_sorted_and_paginated() {
+        let config = test_support::sqlite_connection("storage");
+        let path = config.file_path.clone().unwrap();
+        let (manager, pool) = test_support::sqlite_manager(config).await;
+        sqlx::raw_sql(
+            "CREATE TABLE small (id INTEGER PRIMARY KEY, v TEXT); \
+             CREATE TABLE big (id INTEGER PRIMARY KEY, code TEXT UNIQUE, v TEXT); \
+             CREATE INDEX big_v ON big (v); \
+             WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 2000) \
+             INSERT INTO big SELECT i, 'c' || i, hex(randomblob(32)) FROM n; \
+             INSERT INTO small VALUES (1, 'a');",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let service = StorageService::new(manager);
+        let page = service.tables("storage", &StorageQuery::default()).await.unwrap();
+        assert_eq!(page.pagination.total, 2);
+        let big = &page.items[0];
+        assert_eq!((big.table.as_str(), big.row_estimate), ("big", Some(2000)));
+        assert!(big.total_bytes.unwrap() > big.table_bytes.unwrap());
+        let names: Vec<_> = big.indexes.iter().map(|i| (i.name.as_str(), i.unique)).collect();
+        assert_eq!(names, vec![("big_v", false), ("sqlite_autoindex_big_1", true)]);
+
+        let query = StorageQuery { sort: Some("table".into()), page_size: Some(1), page: Some(2), ..Default::default() };
+        let page = service.tables("storage", &query).await.unwrap();
+        assert_eq!(page.items[0].table, "small");
+        assert!(!page.pagination.has_next);
+
+        let invalid = StorageQuery { sort: Some("-size".into()), ..Default::default() };
+        assert!(matches!(service.tables("storage", &invalid).await, Err(AppError::InvalidInput(_))));
+        let _ = std::fs::remove_file(path);
+    }
+}
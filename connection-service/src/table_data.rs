@@ -0,0 +1,616 @@
+//! 表数据浏览服务
+//!
+//! 按结构化条件读取表中的行，无需编写 SQL：
+//! - 列投影、多列排序（默认按主键排序以保证分页稳定）
+//! - 过滤条件统一以 AND 组合，值全部作为绑定参数
+//! - 分页总数可精确计算，大表未过滤时使用目录中的估算值
+//!
+//! 并按主键插入、更新和删除行：
+//! - 一批修改在同一事务中执行，任一失败则全部回滚
+//! - 乐观并发：携带原始值时，行已被他人修改则拒绝（冲突）
+//! - 没有主键的表和只读连接拒绝修改
+
+use std::sync::Arc;
+
+use common::errors::{AppError, AppResult};
+use common::models::metadata::{ColumnItem, TableDetail};
+use common::models::table_data::{
+    CountMode, FilterOperator, RowChange, RowChangeKind, RowChangeResult, RowChangesRequest,
+    RowChangesResult, RowFilter, RowsQuery,
+};
+use common::response::PaginatedData;
+use serde_json::{Map, Value};
+use validator::Validate;
+
+use crate::dialect::Dialect;
+use crate::metadata::MetadataService;
+use crate::pool_manager::PoolManager;
+use crate::sql_builder::{execute_in_transaction, select_expr, SqlBuilder, SqlParam, ValueKind};
+
+/// 默认每页行数
+const DEFAULT_PAGE_SIZE: u32 = 50;
+
+/// 每页最大行数
+const MAX_PAGE_SIZE: u32 = 1000;
+
+/// 自动模式下，估算行数达到该值的未过滤查询使用估算总数
+const AUTO_ESTIMATE_THRESHOLD: i64 = 100_000;
+
+/// 表数据服务
+pub struct TableDataService {
+    pool_manager: Arc<PoolManager>,
+}
+
+impl TableDataService {
+    /// 创建新的表数据服务实例
+    pub fn new(pool_manager: Arc<PoolManager>) -> Self {
+        Self { pool_manager }
+    }
+
+    /// 分页读取表中的行
+    pub async fn rows(&self, id: &str, table: &str, query: &RowsQuery) -> AppResult<PaginatedData<Value>> {
+        let pool = self.pool_manager.pool(id).await?;
+        let dialect = pool.dialect()?;
+        let detail = MetadataService::new(self.pool_manager.clone())
+            .table(id, query.schema.as_deref(), table)
+            .await?;
+
+        let columns = projection(dialect, &detail, query.columns.as_deref())?;
+        let filters = parse_filters(&detail, query.filters.as_deref())?;
+        let order = parse_sort(&detail, query.sort.as_deref())?;
+        let page = query.page.unwrap_or(1).max(1);
+        let page_size = query.page_size.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+        let table_sql = dialect.qualified_table(Some(&detail.table.schema), &detail.table.name);
+
+        let mut select = SqlBuilder::new(dialect);
+        let exprs: Vec<String> = columns
+            .iter()
+            .map(|(name, kind)| select_expr(dialect, name, *kind))
+            .collect();
+        select.push("SELECT ").push(&exprs.join(", ")).push(" FROM ").push(&table_sql);
+        push_where(&mut select, dialect, &detail, &filters)?;
+        if !order.is_empty() {
+            let terms: Vec<String> = order
+                .iter()
+                .map(|(column, desc)| format!("{}{}", dialect.quote_ident(column), if *desc { " DESC" } else { "" }))
+                .collect();
+            select.push(" ORDER BY ").push(&terms.join(", "));
+        }
+        let offset = u64::from(page - 1) * u64::from(page_size);
+        select.push(&format!(" LIMIT {} OFFSET {}", page_size, offset));
+        let items = select.fetch_rows(&pool, &columns).await?;
+
+        // 估算值只对未过滤的查询有意义
+        let estimate = match (query.count.unwrap_or_default(), detail.table.row_estimate) {
+            (CountMode::Estimated, Some(n)) if filters.is_empty() => Some(n),
+            (CountMode::Auto, Some(n)) if filters.is_empty() && n >= AUTO_ESTIMATE_THRESHOLD => Some(n),
+            _ => None,
+        };
+        match estimate {
+            Some(n) => Ok(PaginatedData::new(items, page, page_size, n.max(0) as u64).with_estimated_total()),
+            None => {
+                let mut count = SqlBuilder::new(dialect);
+                count.push("SELECT COUNT(*) FROM ").push(&table_sql);
+                push_where(&mut count, dialect, &detail, &filters)?;
+                let total = count.fetch_count(&pool).await?;
+                Ok(PaginatedData::new(items, page, page_size, total.max(0) as u64))
+            }
+        }
+    }
+
+    /// 在一个事务中按主键应用一批行修改
+    pub async fn apply_changes(&self, id: &str, table: &str, req: RowChangesRequest) -> AppResult<RowChangesResult> {
+        req.validate()?;
+        let config = self
+            .pool_manager
+            .get_connection(id)
+            .await
+            .ok_or_else(|| AppError::ConnectionNotFound(id.to_string()))?;
+        if config.read_only {
+            return Err(AppError::Forbidden(format!("连接 {} 为只读连接，禁止修改数据", config.name)));
+        }
+        if config.requires_write_confirmation() && !req.confirm_prod {
+            return Err(AppError::Forbidden(format!(
+                "连接 {} 为生产环境连接，修改数据需设置 confirm_prod 确认",
+                config.name
+            )));
+        }
+        let pool = self.pool_manager.pool(id).await?;
+        let dialect = pool.dialect()?;
+        let detail = MetadataService::new(self.pool_manager.clone())
+            .table(id, req.schema.as_deref(), table)
+            .await?;
+        let primary_key = match &detail.primary_key {
+            Some(pk) if !pk.columns.is_empty() => pk.columns.clone(),
+            _ => {
+                return Err(AppError::InvalidInput(format!(
+                    "表 {} 没有主键，无法按行修改",
+                    detail.table.name
+                )))
+            }
+        };
+
+        let statements = req
+            .changes
+            .iter()
+            .map(|change| change_statement(dialect, &detail, &primary_key, change))
+            .collect::<AppResult<Vec<_>>>()?;
+        // 更新和删除未命中任何行，说明主键不存在或原始值已被他人修改
+        let affected = execute_in_transaction(&pool, &statements, |i, affected| {
+            if affected == 0 && req.changes[i].kind != RowChangeKind::Insert {
+                return Err(AppError::Conflict(format!(
+                    "第 {} 个修改的目标行不存在或已被修改，请刷新后重试",
+                    i + 1
+                )));
+            }
+            Ok(())
+        })
+        .await?;
+
+        tracing::info!(id = %id, table = %detail.table.name, changes = req.changes.len(), "行修改已提交");
+        Ok(RowChangesResult {
+            affected: affected.iter().sum(),
+            changes: req
+                .changes
+                .iter()
+                .zip(affected)
+                .map(|(change, affected)| RowChangeResult {
+                    kind: change.kind,
+                    affected,
+                })
+                .collect(),
+        })
+    }
+}
+
+/// 生成单个行修改的语句
+fn change_statement(
+    dialect: Dialect,
+    detail: &TableDetail,
+    primary_key: &[String],
+    change: &RowChange,
+) -> AppResult<SqlBuilder> {
+    let table_sql = dialect.qualified_table(Some(&detail.table.schema), &detail.table.name);
+    let mut builder = SqlBuilder::new(dialect);
+    match change.kind {
+        RowChangeKind::Insert => {
+            if change.values.is_empty() {
+                return Err(AppError::InvalidInput("插入的行至少需要一个列值".into()));
+            }
+            let names: Vec<String> = change
+                .values
+                .keys()
+                .map(|name| column(detail, name).map(|c| dialect.quote_ident(&c.name)))
+                .collect::<AppResult<_>>()?;
+            builder
+                .push("INSERT INTO ")
+                .push(&table_sql)
+                .push(" (")
+                .push(&names.join(", "))
+                .push(") VALUES (");
+            for (i, (name, value)) in change.values.iter().enumerate() {
+                if i > 0 {
+                    builder.push(", ");
+                }
+                push_value(&mut builder, dialect, column(detail, name)?, value)?;
+            }
+            builder.push(")");
+        }
+        RowChangeKind::Update => {
+            if change.values.is_empty() {
+                return Err(AppError::InvalidInput("更新的行至少需要一个修改的列值".into()));
+            }
+            builder.push("UPDATE ").push(&table_sql).push(" SET ");
+            for (i, (name, value)) in change.values.iter().enumerate() {
+                if i > 0 {
+                    builder.push(", ");
+                }
+                let column = column(detail, name)?;
+                builder.push_ident(&column.name).push(" = ");
+                push_value(&mut builder, dialect, column, value)?;
+            }
+            push_row_match(&mut builder, dialect, detail, primary_key, change)?;
+        }
+        RowChangeKind::Delete => {
+            builder.push("DELETE FROM ").push(&table_sql);
+            push_row_match(&mut builder, dialect, detail, primary_key, change)?;
+        }
+    }
+    Ok(builder)
+}
+
+/// 追加按类型转换的列值参数
+fn push_value(builder: &mut SqlBuilder, dialect: Dialect, column: &ColumnItem, value: &Value) -> AppResult<()> {
+    let param = SqlParam::for_column(ValueKind::of(dialect, &column.data_type), value)?;
+    builder.push_param(param, Some(&column.data_type));
+    Ok(())
+}
+
+/// 追加定位目标行的 WHERE 子句：完整主键，加上客户端提供的原始值
+fn push_row_match(
+    builder: &mut SqlBuilder,
+    dialect: Dialect,
+    detail: &TableDetail,
+    primary_key: &[String],
+    change: &RowChange,
+) -> AppResult<()> {
+    validate_key(primary_key, &change.key)?;
+    for (i, name) in primary_key.iter().enumerate() {
+        builder.push(if i == 0 { " WHERE " } else { " AND " });
+        let column = column(detail, name)?;
+        builder.push_ident(&column.name).push(" = ");
+        push_value(builder, dialect, column, &change.key[name])?;
+    }
+    for (name, value) in &change.original {
+        if primary_key.contains(name) {
+            continue;
+        }
+        let column = column(detail, name)?;
+        builder.push(" AND ");
+        push_original(builder, dialect, column, value)?;
+    }
+    Ok(())
+}
+
+/// 主键必须完整且不含其他列，值不能为空
+fn validate_key(primary_key: &[String], key: &Map<String, Value>) -> AppResult<()> {
+    if let Some(extra) = key.keys().find(|name| !primary_key.contains(name)) {
+        return Err(AppError::InvalidInput(format!("列 {} 不是主键列", extra)));
+    }
+    for name in primary_key {
+        match key.get(name) {
+            Some(value) if !value.is_null() => {}
+            _ => return Err(AppError::InvalidInput(format!("缺少主键列 {} 的值", name))),
+        }
+    }
+    Ok(())
+}
+
+/// 追加与原始值的空值安全比较
+fn push_original(builder: &mut SqlBuilder, dialect: Dialect, column: &ColumnItem, value: &Value) -> AppResult<()> {
+    let kind = ValueKind::of(dialect, &column.data_type);
+    let param = SqlParam::for_column(kind, value)?;
+    let ident = dialect.quote_ident(&column.name);
+    match (dialect, kind) {
+        // json 类型没有相等运算符，按 jsonb 比较
+        (Dialect::Postgres, ValueKind::Json) => {
+            builder
+                .push(&format!("{}::jsonb IS NOT DISTINCT FROM ", ident))
+                .push_param(param, Some("jsonb"));
+        }
+        (Dialect::Postgres, _) => {
+            builder
+                .push(&format!("{} IS NOT DISTINCT FROM ", ident))
+                .push_param(param, Some(&column.data_type));
+        }
+        (Dialect::MySql, ValueKind::Json) => {
+            builder.push(&format!("{} <=> CAST(", ident)).push_param(param, None).push(" AS JSON)");
+        }
+        (Dialect::MySql, _) => {
+            builder.push(&format!("{} <=> ", ident)).push_param(param, None);
+        }
+        (Dialect::Sqlite, ValueKind::Json) => {
+            builder.push(&format!("json({}) IS json(", ident)).push_param(param, None).push(")");
+        }
+        (Dialect::Sqlite, _) => {
+            builder.push(&format!("{} IS ", ident)).push_param(param, None);
+        }
+    }
+    Ok(())
+}
+
+/// 查找列，不存在时返回参数错误
+fn column<'a>(detail: &'a TableDetail, name: &str) -> AppResult<&'a ColumnItem> {
+    detail
+        .column(name)
+        .ok_or_else(|| AppError::InvalidInput(format!("表 {} 中不存在列 {}", detail.table.name, name)))
+}
+
+/// 解析逗号分隔的列表，忽略空项
+fn split_list(list: &str) -> impl Iterator<Item = &str> {
+    list.split(',').map(str::trim).filter(|s| !s.is_empty())
+}
+
+/// 解析返回的列及其解码方式，默认为全部列
+fn projection(dialect: Dialect, detail: &TableDetail, columns: Option<&str>) -> AppResult<Vec<(String, ValueKind)>> {
+    let selected: Vec<&ColumnItem> = match columns {
+        Some(list) if !list.trim().is_empty() => split_list(list).map(|name| column(detail, name)).collect::<AppResult<_>>()?,
+        _ => detail.columns.iter().collect(),
+    };
+    Ok(selected
+        .into_iter()
+        .map(|c| (c.name.clone(), ValueKind::of(dialect, &c.data_type)))
+        .collect())
+}
+
+/// 解析 JSON 数组形式的过滤条件并校验列名
+fn parse_filters(detail: &TableDetail, filters: Option<&str>) -> AppResult<Vec<RowFilter>> {
+    let filters: Vec<RowFilter> = match filters {
+        Some(json) if !json.trim().is_empty() => serde_json::from_str(json)
+            .map_err(|e| AppError::InvalidInput(format!("过滤条件格式错误: {}", e)))?,
+        _ => Vec::new(),
+    };
+    for filter in &filters {
+        column(detail, &filter.column)?;
+    }
+    Ok(filters)
+}
+
+/// 解析排序列（`-` 前缀表示降序），默认按主键排序
+fn parse_sort(detail: &TableDetail, sort: Option<&str>) -> AppResult<Vec<(String, bool)>> {
+    match sort {
+        Some(list) if !list.trim().is_empty() => split_list(list)
+            .map(|term| {
+                let (name, desc) = match term.strip_prefix('-') {
+                    Some(name) => (name.trim(), true),
+                    None => (term.strip_prefix('+').unwrap_or(term).trim(), false),
+                };
+                column(detail, name).map(|c| (c.name.clone(), desc))
+            })
+            .collect(),
+        _ => Ok(detail
+            .primary_key
+            .as_ref()
+            .map(|pk| pk.columns.iter().map(|c| (c.clone(), false)).collect())
+            .unwrap_or_default()),
+    }
+}
+
+/// 追加 WHERE 子句
+fn push_where(builder: &mut SqlBuilder, dialect: Dialect, detail: &TableDetail, filters: &[RowFilter]) -> AppResult<()> {
+    for (i, filter) in filters.iter().enumerate() {
+        builder.push(if i == 0 { " WHERE " } else { " AND " });
+        push_filter(builder, dialect, column(detail, &filter.column)?, filter)?;
+    }
+    Ok(())
+}
+
+/// 追加单个过滤条件
+fn push_filter(builder: &mut SqlBuilder, dialect: Dialect, column: &ColumnItem, filter: &RowFilter) -> AppResult<()> {
+    let cast = Some(column.data_type.as_str());
+    let comparison = match filter.op {
+        FilterOperator::Eq => Some("="),
+        FilterOperator::Ne => Some("<>"),
+        FilterOperator::Gt => Some(">"),
+        FilterOperator::Gte => Some(">="),
+        FilterOperator::Lt => Some("<"),
+        FilterOperator::Lte => Some("<="),
+        _ => None,
+    };
+    if let Some(op) = comparison {
+        if filter.value.is_null() {
+            return Err(AppError::InvalidInput(format!(
+                "列 {} 的比较值不能为空，请使用 is_null / is_not_null",
+                column.name
+            )));
+        }
+        builder
+            .push_ident(&column.name)
+            .push(&format!(" {} ", op))
+            .push_param(SqlParam::from_json(&filter.value)?, cast);
+        return Ok(());
+    }
+
+    // LIKE 类条件按文本比较
+    let ident = dialect.quote_ident(&column.name);
+    let text_ident = match dialect {
+        Dialect::Postgres => format!("{}::text", ident),
+        _ => ident,
+    };
+    match filter.op {
+        FilterOperator::Like | FilterOperator::NotLike => {
+            let pattern = text_value(column, &filter.value)?;
+            let op = if filter.op == FilterOperator::Like { " LIKE " } else { " NOT LIKE " };
+            builder.push(&text_ident).push(op).push_param(SqlParam::Text(pattern), None);
+        }
+        FilterOperator::Contains | FilterOperator::StartsWith | FilterOperator::EndsWith => {
+            let escaped = escape_like(&text_value(column, &filter.value)?);
+            let pattern = match filter.op {
+                FilterOperator::Contains => format!("%{}%", escaped),
+                FilterOperator::StartsWith => format!("{}%", escaped),
+                _ => format!("%{}", escaped),
+            };
+            builder
+                .push(&text_ident)
+                .push(" LIKE ")
+                .push_param(SqlParam::Text(pattern), None)
+                .push(" ESCAPE '!'");
+        }
+        FilterOperator::In | FilterOperator::NotIn => {
+            let values = match &filter.value {
+                Value::Array(values) if !values.is_empty() => values,
+                _ => {
+                    return Err(AppError::InvalidInput(format!(
+                        "列 {} 的 in / not_in 条件需要非空数组",
+                        column.name
+                    )))
+                }
+            };
+            builder
+                .push_ident(&column.name)
+                .push(if filter.op == FilterOperator::In { " IN (" } else { " NOT IN (" });
+            for (i, value) in values.iter().enumerate() {
+                if i > 0 {
+                    builder.push(", ");
+                }
+                builder.push_param(SqlParam::from_json(value)?, cast);
+            }
+            builder.push(")");
+        }
+        FilterOperator::IsNull => {
+            builder.push_ident(&column.name).push(" IS NULL");
+        }
+        FilterOperator::IsNotNull => {
+            builder.push_ident(&column.name).push(" IS NOT NULL");
+        }
+        _ => unreachable!("比较运算符已在上方处理"),
+    }
+    Ok(())
+}
+
+/// LIKE 类条件的值必须是字符串
+fn text_value(column: &ColumnItem, value: &Value) -> AppResult<String> {
+    value
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| AppError::InvalidInput(format!("列 {} 的匹配条件需要字符串值", column.name)))
+}
+
+/// 转义 LIKE 通配符，使用 `!` 作为转义字符
+fn escape_like(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        if matches!(ch, '!' | '%' | '_') {
+            escaped.push('!');
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::models::connection::{ConnectionConfig, Environment};
+    use serde_json::json;
+
+    use crate::test_support;
+
+    async fn sqlite_service(read_only: bool, environment: Option<Environment>) -> TableDataService {
+        let config = ConnectionConfig { read_only, environment, ..test_support::sqlite_connection("rows") };
+        let (manager, pool) = test_support::sqlite_manager(config).await;
+        for sql in [
+            "CREATE TABLE items (id INTEGER PRIMARY KEY, name TEXT, price REAL, active BOOLEAN)",
+            "CREATE TABLE logs (message TEXT)",
+        ] {
+            sqlx::query(sql).execute(&pool).await.unwrap();
+        }
+        for (id, name, price, active) in [(1, "a%b", 1.5, true), (2, "apple", 2.0, false), (3, "banana", 3.25, true)] {
+            sqlx::query("INSERT INTO items VALUES (?, ?, ?, ?)")
+                .bind(id)
+                .bind(name)
+                .bind(price)
+                .bind(active)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+        TableDataService::new(manager)
+    }
+
+    fn changes(changes: Value) -> RowChangesRequest {
+        serde_json::from_value(json!({ "changes": changes })).unwrap()
+    }
+
+    async fn names(service: &TableDataService) -> Vec<Value> {
+        let query = RowsQuery { columns: Some("name".into()), ..Default::default() };
+        service.rows("rows", "items", &query).await.unwrap().items
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_rows_with_filters_sort_and_paging() {
+        let service = sqlite_service(false, None).await;
+
+        let page = service
+            .rows("rows", "items", &RowsQuery { page_size: Some(2), ..Default::default() })
+            .await
+            .unwrap();
+        assert_eq!(page.pagination.total, 3);
+        assert!(page.pagination.has_next);
+        assert_eq!(page.items[0], json!({"id": 1, "name": "a%b", "price": 1.5, "active": true}));
+
+        let filters = json!([
+            {"column": "name", "op": "contains", "value": "%"},
+            {"column": "active", "op": "eq", "value": true}
+        ]);
+        let matched = service
+            .rows(
+                "rows",
+                "items",
+                &RowsQuery { filters: Some(filters.to_string()), columns: Some("id".into()), ..Default::default() },
+            )
+            .await
+            .unwrap();
+        assert_eq!(matched.items, vec![json!({"id": 1})]);
+        assert_eq!(matched.pagination.total, 1);
+
+        let sorted = service
+            .rows(
+                "rows",
+                "items",
+                &RowsQuery {
+                    filters: Some(json!([{"column": "id", "op": "in", "value": [2, 3]}]).to_string()),
+                    sort: Some("-price".into()),
+                    columns: Some("name".into()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(sorted.items, vec![json!({"name": "banana"}), json!({"name": "apple"})]);
+
+        let missing = service
+            .rows("rows", "items", &RowsQuery { sort: Some("nope".into()), ..Default::default() })
+            .await;
+        assert!(matches!(missing, Err(AppError::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_row_changes_with_optimistic_concurrency() {
+        let service = sqlite_service(false, None).await;
+        let result = service
+            .apply_changes(
+                "rows",
+                "items",
+                changes(json!([
+                    {"kind": "insert", "values": {"id": 4, "name": "cherry", "active": false}},
+                    {"kind": "update", "key": {"id": 2}, "values": {"name": "apricot"}, "original": {"name": "apple"}},
+                    {"kind": "delete", "key": {"id": 1}}
+                ])),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.affected, 3);
+        assert_eq!(
+            names(&service).await,
+            vec![json!({"name": "apricot"}), json!({"name": "banana"}), json!({"name": "cherry"})]
+        );
+
+        // 原始值已过期：整批回滚，前面的插入也不生效
+        let stale = service
+            .apply_changes(
+                "rows",
+                "items",
+                changes(json!([
+                    {"kind": "insert", "values": {"id": 5, "name": "date"}},
+                    {"kind": "update", "key": {"id": 2}, "values": {"name": "avocado"}, "original": {"name": "apple"}}
+                ])),
+            )
+            .await;
+        assert!(matches!(stale, Err(AppError::Conflict(_))));
+        assert_eq!(names(&service).await.len(), 3);
+
+        let missing_key = service
+            .apply_changes("rows", "items", changes(json!([{"kind": "delete", "key": {"name": "banana"}}])))
+            .await;
+        assert!(matches!(missing_key, Err(AppError::InvalidInput(_))));
+        let no_pk = service
+            .apply_changes("rows", "logs", changes(json!([{"kind": "insert", "values": {"message": "x"}}])))
+            .await;
+        assert!(matches!(no_pk, Err(AppError::InvalidInput(_))));
+
+        let read_only = sqlite_service(true, None)
+            .await
+            .apply_changes("rows", "items", changes(json!([{"kind": "delete", "key": {"id": 1}}])))
+            .await;
+        assert!(matches!(read_only, Err(AppError::Forbidden(_))));
+
+        // 生产环境连接需要显式确认
+        let prod = sqlite_service(false, Some(Environment::Prod)).await;
+        let delete = json!([{"kind": "delete", "key": {"id": 1}}]);
+        let unconfirmed = prod.apply_changes("rows", "items", changes(delete.clone())).await;
+        assert!(matches!(unconfirmed, Err(AppError::Forbidden(ref m)) if m.contains("confirm_prod")));
+        let confirmed = serde_json::from_value(json!({ "changes": delete, "confirm_prod": true })).unwrap();
+        assert!(prod.apply_changes("rows", "items", confirmed).await.is_ok());
+    }
+}
@@ -0,0 +1,51 @@
+//! 测试辅助
+//!
+//! 各模块测试共用的连接配置和临时 SQLite 连接。配置从 JSON 反序列化，
+//! 新增的可选字段取默认值，不需要修改测试。
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use common::config::AppConfig;
+use common::models::connection::{ConnectionConfig, DbType};
+use sqlx::SqlitePool;
+
+use crate::pool_manager::{DatabasePool, PoolManager};
+
+/// 只有 ID、名称和数据库类型的连接配置，名称与 ID 相同
+pub fn connection(id: &str, db_type: DbType) -> ConnectionConfig {
+    serde_json::from_value(serde_json::json!({
+        "id": id,
+        "name": id,
+        "db_type": db_type,
+        "created_at": "",
+    }))
+    .expect("连接配置")
+}
+
+/// 临时目录下不重复的 SQLite 数据库文件路径，`prefix` 标识所属测试
+pub fn temp_db(prefix: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("{}-{}.db", prefix, uuid::Uuid::new_v4()))
+}
+
+/// 使用临时数据库文件的 SQLite 连接配置
+pub fn sqlite_connection(id: &str) -> ConnectionConfig {
+    ConnectionConfig {
+        file_path: Some(temp_db(id).to_string_lossy().to_string()),
+        ..connection(id, DbType::SQLite)
+    }
+}
+
+/// 使用默认配置的连接池管理器
+pub fn manager() -> Arc<PoolManager> {
+    Arc::new(PoolManager::new(AppConfig::load()))
+}
+
+/// 注册 SQLite 连接 `config` 并返回管理器和打开的连接池
+pub async fn sqlite_manager(config: ConnectionConfig) -> (Arc<PoolManager>, SqlitePool) {
+    let id = config.id.clone();
+    let manager = manager();
+    manager.add_connection(config).await.unwrap();
+    let DatabasePool::SQLite(pool) = manager.get_pool(&id).await.unwrap() else { unreachable!() };
+    (manager, pool)
+}
@@ -0,0 +1,326 @@
+//! TLS 设置
+//!
+//! 把连接的 [`TlsOptions`] 应用到驱动：
+//! - MySQL / PostgreSQL：映射到 sqlx 的 `ssl_mode` 和 PEM 证书选项
+//! - Redis：构造 rustls 客户端配置，经由 [`crate::tunnel`] 的本地转发完成握手
+//!
+//! CA 证书、客户端证书和私钥既可以是 PEM 内容，也可以是服务可读的文件路径；
+//! 打开连接前统一读取并解析，格式错误在这里报告，而不是在握手时。
+
+use std::str::FromStr;
+use std::sync::Arc;
+
+use common::errors::{AppError, AppResult};
+use common::models::connection::{ConnectionConfig, TlsMode, TlsOptions};
+use common::utils::dsn::REDIS_TLS_OPTION;
+use sqlx::mysql::{MySqlConnectOptions, MySqlSslMode};
+use sqlx::postgres::{PgConnectOptions, PgSslMode};
+use tokio_rustls::rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use tokio_rustls::rustls::client::WebPkiServerVerifier;
+use tokio_rustls::rustls::crypto::{self, CryptoProvider};
+use tokio_rustls::rustls::pki_types::pem::PemObject;
+use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime};
+use tokio_rustls::rustls::{self, CertificateError, ClientConfig, DigitallySignedStruct, RootCertStore, SignatureScheme};
+use tokio_rustls::TlsConnector;
+
+/// 读取并校验过的证书材料，保留原始 PEM 供 sqlx 使用
+struct Material {
+    ca_cert: Option<Vec<u8>>,
+    client_cert: Option<Vec<u8>>,
+    client_key: Option<Vec<u8>>,
+}
+
+impl Material {
+    async fn load(tls: &TlsOptions) -> AppResult<Self> {
+        let client_key = match &tls.client_key {
+            Some(value) => {
+                let pem = read_pem("client_key", value).await?;
+                private_key(&pem)?;
+                Some(pem)
+            }
+            None => None,
+        };
+        Ok(Self {
+            ca_cert: load_certificates("ca_cert", tls.ca_cert.as_deref()).await?,
+            client_cert: load_certificates("client_cert", tls.client_cert.as_deref()).await?,
+            client_key,
+        })
+    }
+}
+
+/// 读取 PEM：以 `-----BEGIN` 开头的值视为内容，否则视为文件路径
+async fn read_pem(field: &str, value: &str) -> AppResult<Vec<u8>> {
+    if value.trim_start().starts_with("-----BEGIN") {
+        return Ok(value.as_bytes().to_vec());
+    }
+    tokio::fs::read(value)
+        .await
+        .map_err(|e| AppError::Validation(format!("tls.{}: 无法读取文件 {}: {}", field, value, e)))
+}
+
+async fn load_certificates(field: &str, value: Option<&str>) -> AppResult<Option<Vec<u8>>> {
+    let Some(value) = value else { return Ok(None) };
+    let pem = read_pem(field, value).await?;
+    certificates(field, &pem)?;
+    Ok(Some(pem))
+}
+
+/// 解析 PEM 中的全部证书，至少要有一张
+fn certificates(field: &str, pem: &[u8]) -> AppResult<Vec<CertificateDer<'static>>> {
+    let certs = CertificateDer::pem_slice_iter(pem)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| AppError::Validation(format!("tls.{}: 证书格式错误: {}", field, e)))?;
+    if certs.is_empty() {
+        return Err(AppError::Validation(format!("tls.{}: 没有找到 PEM 证书", field)));
+    }
+    Ok(certs)
+}
+
+fn private_key(pem: &[u8]) -> AppResult<PrivateKeyDer<'static>> {
+    PrivateKeyDer::from_pem_slice(pem)
+        .map_err(|e| AppError::Validation(format!("tls.client_key: 私钥格式错误: {}", e)))
+}
+
+/// 由 URL 构造 PostgreSQL 连接选项并应用 TLS 设置
+///
+/// 未配置 TLS 时保留 URL 中的 `sslmode` 等参数。
+pub async fn pg_options(url: &str, tls: Option<&TlsOptions>) -> AppResult<PgConnectOptions> {
+    let mut options = PgConnectOptions::from_str(url).map_err(|e| AppError::Validation(e.to_string()))?;
+    let Some(tls) = tls else { return Ok(options) };
+    let material = Material::load(tls).await?;
+    options = options.ssl_mode(match tls.mode {
+        TlsMode::Disable => PgSslMode::Disable,
+        TlsMode::Prefer => PgSslMode::Prefer,
+        TlsMode::Require => PgSslMode::Require,
+        TlsMode::VerifyCa => PgSslMode::VerifyCa,
+        TlsMode::VerifyFull => PgSslMode::VerifyFull,
+    });
+    if let Some(pem) = material.ca_cert {
+        options = options.ssl_root_cert_from_pem(pem);
+    }
+    if let Some(pem) = material.client_cert {
+        options = options.ssl_client_cert_from_pem(pem);
+    }
+    if let Some(pem) = material.client_key {
+        options = options.ssl_client_key_from_pem(pem);
+    }
+    Ok(options)
+}
+
+/// 由 URL 构造 MySQL 连接选项并应用 TLS 设置
+///
+/// 未配置 TLS 时保留 URL 中的 `ssl-mode` 等参数。
+pub async fn mysql_options(url: &str, tls: Option<&TlsOptions>) -> AppResult<MySqlConnectOptions> {
+    let mut options = MySqlConnectOptions::from_str(url).map_err(|e| AppError::Validation(e.to_string()))?;
+    let Some(tls) = tls else { return Ok(options) };
+    let material = Material::load(tls).await?;
+    options = options.ssl_mode(match tls.mode {
+        TlsMode::Disable => MySqlSslMode::Disabled,
+        TlsMode::Prefer => MySqlSslMode::Preferred,
+        TlsMode::Require => MySqlSslMode::Required,
+        TlsMode::VerifyCa => MySqlSslMode::VerifyCa,
+        TlsMode::VerifyFull => MySqlSslMode::VerifyIdentity,
+    });
+    if let Some(pem) = material.ca_cert {
+        options = options.ssl_ca_from_pem(pem);
+    }
+    if let Some(pem) = material.client_cert {
+        options = options.ssl_client_cert_from_pem(pem);
+    }
+    if let Some(pem) = material.client_key {
+        options = options.ssl_client_key_from_pem(pem);
+    }
+    Ok(options)
+}
+
+/// Redis 实际使用的 TLS 设置
+///
+/// 显式的 `tls` 优先；否则 `rediss://` 按 verify-full 处理。返回 `None` 表示明文连接。
+pub fn redis_tls(config: &ConnectionConfig) -> Option<TlsOptions> {
+    match &config.tls {
+        Some(tls) => (tls.mode != TlsMode::Disable).then(|| tls.clone()),
+        None if config.options.get(REDIS_TLS_OPTION).is_some_and(|v| v == "true") => {
+            Some(TlsOptions { mode: TlsMode::VerifyFull, ..Default::default() })
+        }
+        None => None,
+    }
+}
+
+/// 为 Redis 构造 TLS 连接器和用于校验的服务器名
+///
+/// 信任的根证书为公共 Web CA 加上 `ca_cert`；prefer / require 不校验证书，
+/// verify-ca 校验证书链但不校验主机名。
+pub async fn connector(host: &str, tls: &TlsOptions) -> AppResult<(TlsConnector, ServerName<'static>)> {
+    let material = Material::load(tls).await?;
+    let provider = Arc::new(crypto::ring::default_provider());
+
+    let mut roots = RootCertStore { roots: webpki_roots::TLS_SERVER_ROOTS.to_vec() };
+    if let Some(pem) = &material.ca_cert {
+        for cert in certificates("ca_cert", pem)? {
+            roots
+                .add(cert)
+                .map_err(|e| AppError::Validation(format!("tls.ca_cert: 无法使用该 CA 证书: {}", e)))?;
+        }
+    }
+    let webpki = WebPkiServerVerifier::builder_with_provider(Arc::new(roots), provider.clone())
+        .build()
+        .map_err(|e| AppError::Internal(e.to_string()))?;
+    let verifier: Arc<dyn ServerCertVerifier> = match tls.mode {
+        TlsMode::VerifyFull => webpki,
+        TlsMode::VerifyCa => Arc::new(IgnoreHostname(webpki)),
+        _ => Arc::new(AcceptAnyCertificate(provider.clone())),
+    };
+
+    let builder = ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .map_err(|e| AppError::Internal(e.to_string()))?
+        .dangerous()
+        .with_custom_certificate_verifier(verifier);
+    let config = match (&material.client_cert, &material.client_key) {
+        (Some(cert), Some(key)) => builder
+            .with_client_auth_cert(certificates("client_cert", cert)?, private_key(key)?)
+            .map_err(|e| AppError::Validation(format!("tls.client_key: 与客户端证书不匹配: {}", e)))?,
+        _ => builder.with_no_client_auth(),
+    };
+    let server_name = ServerName::try_from(host.trim_start_matches('[').trim_end_matches(']').to_string())
+        .map_err(|e| AppError::Validation(format!("无效的主机名 {}: {}", host, e)))?;
+    Ok((TlsConnector::from(Arc::new(config)), server_name))
+}
+
+/// 把打开连接时的错误转换为 [`AppError::DatabaseConnection`]
+///
+/// 服务器证书校验失败时说明原因并给出处理建议。
+pub fn connect_error(error: impl std::fmt::Display) -> AppError {
+    let message = error.to_string();
+    let lower = message.to_ascii_lowercase().replace(' ', "");
+    let hint = if lower.contains("unknownissuer") || lower.contains("selfsigned") || lower.contains("localissuer") {
+        Some("服务器证书不受信任，请在 tls.ca_cert 中提供签发它的 CA 证书，或改用 mode=require")
+    } else if lower.contains("notvalidforname") || lower.contains("hostname") {
+        Some("服务器证书与主机名不匹配，请检查 host，或改用 mode=verify-ca")
+    } else if lower.contains("expired") || lower.contains("notvalidyet") {
+        Some("服务器证书不在有效期内")
+    } else if lower.contains("certificate") {
+        Some("服务器证书校验失败")
+    } else {
+        None
+    };
+    AppError::DatabaseConnection(match hint {
+        Some(hint) => format!("{}: {}", hint, message),
+        None => message,
+    })
+}
+
+/// verify-ca：校验证书链，忽略主机名不匹配
+#[derive(Debug)]
+struct IgnoreHostname(Arc<WebPkiServerVerifier>);
+
+impl ServerCertVerifier for IgnoreHostname {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        match self.0.verify_server_cert(end_entity, intermediates, server_name, ocsp_response, now) {
+            Err(rustls::Error::InvalidCertificate(
+                CertificateError::NotValidForName | CertificateError::NotValidForNameContext { .. },
+            )) => Ok(ServerCertVerified::assertion()),
+            other => other,
+        }
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.0.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.0.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.supported_verify_schemes()
+    }
+}
+
+/// prefer / require：接受任何服务器证书，但仍校验握手签名
+#[derive(Debug)]
+struct AcceptAnyCertificate(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for AcceptAnyCertificate {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        crypto::verify_tls12_signature(message, cert, dss, &self.0.signature_verification_algorithms)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        crypto::verify_tls13_signature(message, cert, dss, &self.0.signature_verification_algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CA: &str = include_str!("../testdata/tls/ca.pem");
+
+    #[tokio::test]
+    async fn test_pg_options_apply_tls_settings() {
+        let url = "postgres://postgres@db.internal:5432/app?sslmode=disable";
+        let options = pg_options(url, None).await.unwrap();
+        assert!(matches!(options.get_ssl_mode(), PgSslMode::Disable));
+
+        let tls = TlsOptions { mode: TlsMode::VerifyFull, ca_cert: Some(CA.into()), ..Default::default() };
+        let options = pg_options(url, Some(&tls)).await.unwrap();
+        assert!(matches!(options.get_ssl_mode(), PgSslMode::VerifyFull));
+
+        // 文件路径与 PEM 内容等价
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/testdata/tls/ca.pem");
+        let tls = TlsOptions { mode: TlsMode::VerifyCa, ca_cert: Some(path.into()), ..Default::default() };
+        assert!(pg_options(url, Some(&tls)).await.is_ok());
+
+        let missing = TlsOptions { ca_cert: Some("/nonexistent/ca.pem".into()), ..Default::default() };
+        let err = pg_options(url, Some(&missing)).await.unwrap_err();
+        assert!(matches!(err, AppError::Validation(ref m) if m.starts_with("tls.ca_cert")), "{}", err);
+
+        // 证书误填为私钥
+        let swapped = TlsOptions { client_cert: Some(CA.into()), client_key: Some(CA.into()), ..Default::default() };
+        let err = mysql_options("mysql://root@db.internal/app", Some(&swapped)).await.unwrap_err();
+        assert!(matches!(err, AppError::Validation(ref m) if m.starts_with("tls.client_key")), "{}", err);
+    }
+}
@@ -0,0 +1,214 @@
+//! 本地转发
+//!
+//! 在 127.0.0.1 的随机端口上监听，把每个接入的连接转发到远端地址，远端一侧可以包裹 TLS。
+//! 驱动自身无法建立的加密连接由它代为建立，驱动只需连接本地端口。
+//!
+//! 打开时先完成一次连接和握手，地址或证书问题在打开时就报告；
+//! [`Tunnel`] 被丢弃时停止监听，并断开所有正在转发的连接。
+
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use common::errors::{AppError, AppResult};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::{JoinHandle, JoinSet};
+use tokio_rustls::rustls::pki_types::ServerName;
+use tokio_rustls::TlsConnector;
+use tracing::{debug, warn};
+
+use crate::tls;
+
+/// 远端一侧的 TLS 设置
+#[derive(Clone)]
+pub struct Upstream {
+    pub connector: TlsConnector,
+    pub server_name: ServerName<'static>,
+}
+
+/// 正在运行的本地转发
+pub struct Tunnel {
+    local_addr: SocketAddr,
+    negotiated: Option<String>,
+    task: JoinHandle<()>,
+}
+
+trait Stream: AsyncRead + AsyncWrite + Unpin + Send {}
+
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> Stream for T {}
+
+impl Tunnel {
+    /// 连接远端验证可达后开始在本地端口上转发
+    pub async fn open(host: &str, port: u16, tls: Option<Upstream>, timeout: Duration) -> AppResult<Self> {
+        let remote = if host.contains(':') && !host.starts_with('[') {
+            format!("[{}]:{}", host, port)
+        } else {
+            format!("{}:{}", host, port)
+        };
+        let (_, negotiated) = connect(&remote, tls.as_ref(), timeout).await?;
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await?;
+        let local_addr = listener.local_addr()?;
+        let task = tokio::spawn(serve(listener, remote, tls, timeout));
+        Ok(Self { local_addr, negotiated, task })
+    }
+
+    /// 驱动应连接的本地地址
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// 打开时协商的 TLS 版本和加密套件；明文转发时为 `None`
+    pub fn negotiated(&self) -> Option<&str> {
+        self.negotiated.as_deref()
+    }
+}
+
+impl Drop for Tunnel {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// 接受本地连接并逐个转发；任务被取消时一并取消所有转发
+async fn serve(listener: TcpListener, remote: String, tls: Option<Upstream>, timeout: Duration) {
+    let mut relays = JoinSet::new();
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok((inbound, _)) => {
+                    relays.spawn(relay(inbound, remote.clone(), tls.clone(), timeout));
+                }
+                Err(e) => warn!(remote = %remote, error = %e, "本地转发接受连接失败"),
+            },
+            Some(_) = relays.join_next(), if !relays.is_empty() => {}
+        }
+    }
+}
+
+async fn relay(mut inbound: TcpStream, remote: String, tls: Option<Upstream>, timeout: Duration) {
+    let mut outbound = match connect(&remote, tls.as_ref(), timeout).await {
+        Ok((outbound, _)) => outbound,
+        Err(e) => {
+            warn!(remote = %remote, error = %e, "本地转发无法连接远端");
+            return;
+        }
+    };
+    if let Err(e) = tokio::io::copy_bidirectional(&mut inbound, &mut outbound).await {
+        debug!(remote = %remote, error = %e, "本地转发连接已断开");
+    }
+}
+
+/// 连接远端并在需要时完成 TLS 握手，返回连接和协商结果
+async fn connect(
+    remote: &str,
+    tls: Option<&Upstream>,
+    timeout: Duration,
+) -> AppResult<(Box<dyn Stream>, Option<String>)> {
+    let tcp = match tokio::time::timeout(timeout, TcpStream::connect(remote)).await {
+        Ok(Ok(tcp)) => tcp,
+        Ok(Err(e)) => return Err(AppError::DatabaseConnection(format!("无法连接 {}: {}", remote, e))),
+        Err(_) => return Err(AppError::DatabaseConnection(format!("连接 {} 超时", remote))),
+    };
+    let _ = tcp.set_nodelay(true);
+    let Some(upstream) = tls else { return Ok((Box::new(tcp), None)) };
+    let stream = match tokio::time::timeout(timeout, upstream.connector.connect(upstream.server_name.clone(), tcp)).await {
+        Ok(Ok(stream)) => stream,
+        Ok(Err(e)) => return Err(tls::connect_error(format!("与 {} 的 TLS 握手失败: {}", remote, e))),
+        Err(_) => return Err(AppError::DatabaseConnection(format!("与 {} 的 TLS 握手超时", remote))),
+    };
+    let (_, session) = stream.get_ref();
+    let negotiated = [
+        session.protocol_version().and_then(|v| v.as_str()).map(str::to_string),
+        session.negotiated_cipher_suite().and_then(|s| s.suite().as_str()).map(str::to_string),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join(" ");
+    Ok((Box::new(stream), Some(negotiated)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use common::models::connection::{TlsMode, TlsOptions};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio_rustls::rustls::crypto::ring;
+    use tokio_rustls::rustls::pki_types::pem::PemObject;
+    use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer};
+    use tokio_rustls::rustls::ServerConfig;
+    use tokio_rustls::TlsAcceptor;
+
+    use super::*;
+
+    const CA: &str = include_str!("../testdata/tls/ca.pem");
+    const OTHER_CA: &str = include_str!("../testdata/tls/other-ca.pem");
+
+    /// 启动使用测试证书（仅对 localhost 有效）的 TLS 回显服务器，返回端口
+    async fn echo_server() -> u16 {
+        let certs = CertificateDer::pem_slice_iter(include_bytes!("../testdata/tls/server.pem"))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let key = PrivateKeyDer::from_pem_slice(include_bytes!("../testdata/tls/server.key")).unwrap();
+        let config = ServerConfig::builder_with_provider(Arc::new(ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .unwrap()
+            .with_no_client_auth()
+            .with_single_cert(certs, key)
+            .unwrap();
+        let acceptor = TlsAcceptor::from(Arc::new(config));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            while let Ok((tcp, _)) = listener.accept().await {
+                let acceptor = acceptor.clone();
+                tokio::spawn(async move {
+                    let Ok(mut stream) = acceptor.accept(tcp).await else { return };
+                    let mut buf = [0u8; 64];
+                    while let Ok(n) = stream.read(&mut buf).await {
+                        if n == 0 || stream.write_all(&buf[..n]).await.is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+        port
+    }
+
+    async fn open(host: &str, port: u16, mode: TlsMode, ca_cert: &str) -> AppResult<Tunnel> {
+        let options = TlsOptions { mode, ca_cert: Some(ca_cert.into()), ..Default::default() };
+        let (connector, server_name) = tls::connector(host, &options).await?;
+        Tunnel::open(host, port, Some(Upstream { connector, server_name }), Duration::from_secs(5)).await
+    }
+
+    #[tokio::test]
+    async fn test_tls_tunnel_verifies_server_certificate() {
+        let port = echo_server().await;
+
+        let err = open("localhost", port, TlsMode::VerifyFull, OTHER_CA).await.err().unwrap();
+        assert!(matches!(err, AppError::DatabaseConnection(ref m) if m.contains("tls.ca_cert")), "{}", err);
+        let err = open("127.0.0.1", port, TlsMode::VerifyFull, CA).await.err().unwrap();
+        assert!(matches!(err, AppError::DatabaseConnection(ref m) if m.contains("主机名")), "{}", err);
+
+        // verify-ca 不校验主机名，require 不校验证书
+        assert!(open("127.0.0.1", port, TlsMode::VerifyCa, CA).await.is_ok());
+        assert!(open("localhost", port, TlsMode::Require, OTHER_CA).await.is_ok());
+
+        let tunnel = open("localhost", port, TlsMode::VerifyFull, CA).await.unwrap();
+        assert!(tunnel.negotiated().unwrap().starts_with("TLSv1_3"));
+        let mut conn = TcpStream::connect(tunnel.local_addr()).await.unwrap();
+        conn.write_all(b"PING").await.unwrap();
+        let mut reply = [0u8; 4];
+        conn.read_exact(&mut reply).await.unwrap();
+        assert_eq!(&reply, b"PING");
+
+        // 丢弃后停止监听并断开转发
+        let local_addr = tunnel.local_addr();
+        drop(tunnel);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(TcpStream::connect(local_addr).await.is_err());
+        assert_eq!(conn.read(&mut reply).await.unwrap_or(0), 0);
+    }
+}
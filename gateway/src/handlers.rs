@@ -6,6 +6,7 @@ use axum::{
 };
 use chrono::{DateTime, Utc};
 use serde::Serialize;
+use serde_json::Value;
 use utoipa::ToSchema;
 
 use crate::state::AppState;
@@ -29,6 +30,8 @@ pub async fn health_check() -> Json<HealthResponse> {
 }
 
 /// 聚合健康检查 - 检查所有微服务的健康状态
+///
+/// 同时汇总连接服务健康监控记录的数据库连接状态，有连接故障时整体状态为 degraded。
 #[utoipa::path(
     get,
     path = "/api/health/aggregated",
@@ -40,20 +43,59 @@ pub async fn health_check() -> Json<HealthResponse> {
 pub async fn aggregated_health(
     State(state): State<AppState>,
 ) -> Json<AggregatedHealth> {
+    let connection_service =
+        check_service_health(&state.http_client, "connection-service", &state.service_urls.connection_service).await;
+    let connections = if connection_service.healthy {
+        fetch_connection_health(&state.http_client, &state.service_urls.connection_service).await
+    } else {
+        None
+    };
     let services = vec![
-        check_service_health(&state.http_client, "connection-service", &state.service_urls.connection_service).await,
+        connection_service,
         check_service_health(&state.http_client, "query-service", &state.service_urls.query_service).await,
     ];
 
-    let all_healthy = services.iter().all(|s| s.healthy);
+    let all_healthy = services.iter().all(|s| s.healthy)
+        && connections.as_ref().is_none_or(|c| c.down == 0);
 
     Json(AggregatedHealth {
         status: if all_healthy { "healthy" } else { "degraded" }.to_string(),
         timestamp: Utc::now(),
         services,
+        connections,
     })
 }
 
+/// 读取连接服务健康监控的汇总，失败时返回 `None`
+async fn fetch_connection_health(client: &reqwest::Client, url: &str) -> Option<ConnectionsHealth> {
+    let response = client
+        .get(format!("{}/api/connections/health", url))
+        .send()
+        .await
+        .ok()?
+        .error_for_status()
+        .ok()?;
+    let body: Value = response.json().await.ok()?;
+    let data = body.get("data")?;
+    let count = |key: &str| data.get(key).and_then(Value::as_u64).unwrap_or(0) as usize;
+    let text = |item: &Value, key: &str| item.get(key).and_then(Value::as_str).map(str::to_string);
+    let failing = data
+        .get("connections")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter(|c| c.get("state").and_then(Value::as_str) == Some("down"))
+        .map(|c| DownConnection {
+            id: text(c, "id").unwrap_or_default(),
+            name: text(c, "name").unwrap_or_default(),
+            since: text(c, "since"),
+            // 最新的变化在前，即本次下线的原因
+            error: c.pointer("/transitions/0/error").and_then(Value::as_str).map(str::to_string),
+        })
+        .collect();
+    Some(ConnectionsHealth { up: count("up"), down: count("down"), unknown: count("unknown"), failing })
+}
+
 async fn check_service_health(
     client: &reqwest::Client,
     name: &str,
@@ -96,6 +138,27 @@ pub struct AggregatedHealth {
     pub status: String,
     pub timestamp: DateTime<Utc>,
     pub services: Vec<ServiceHealth>,
+    /// 数据库连接状态，连接服务不可用时缺省
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connections: Option<ConnectionsHealth>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ConnectionsHealth {
+    pub up: usize,
+    pub down: usize,
+    pub unknown: usize,
+    pub failing: Vec<DownConnection>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct DownConnection {
+    pub id: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
 
 #[derive(Serialize, ToSchema)]
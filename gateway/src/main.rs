@@ -40,6 +40,8 @@ const DEFAULT_PORT: u16 = 8080;
     components(schemas(
         handlers::HealthResponse,
         handlers::AggregatedHealth,
+        handlers::ConnectionsHealth,
+        handlers::DownConnection,
         handlers::ServiceHealth,
     )),
     tags(
@@ -68,7 +70,7 @@ async fn main() {
         .unwrap_or(DEFAULT_PORT);
 
     // 创建应用状态
-    let state = AppState::new(config.clone());
+    let state = AppState::new();
 
     // 创建路由
     let app = create_router(state);
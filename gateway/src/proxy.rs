@@ -18,9 +18,13 @@ pub fn router() -> Router<AppState> {
         // 连接服务路由
         .route("/api/connections", get(proxy_to_connection_service).post(proxy_to_connection_service))
         .route("/api/connections/{*path}", any(proxy_to_connection_service))
+        .route("/api/sql/complete", post(proxy_to_connection_service))
+        .route("/api/search", get(proxy_to_connection_service))
+        .route("/api/audit", get(proxy_to_connection_service))
+        .route("/api/secrets/rotate", post(proxy_to_connection_service))
+        .route("/api/search/{*path}", any(proxy_to_connection_service))
         // 查询服务路由
         .route("/api/query", post(proxy_to_query_service))
-        .route("/api/databases", post(proxy_to_query_service))
 }
 
 /// 转发请求到连接服务
@@ -23,6 +23,7 @@ use crate::state::AppState;
     responses(
         (status = 200, description = "查询执行成功", body = ApiResponse<QueryResult>),
         (status = 400, description = "SQL 无效或校验错误"),
+        (status = 403, description = "生产环境连接上的写语句未确认"),
         (status = 404, description = "连接未找到")
     )
 )]
@@ -68,7 +68,7 @@ async fn main() {
         .unwrap_or(DEFAULT_PORT);
 
     // 创建应用状态
-    let state = AppState::new(config.clone());
+    let state = AppState::new();
 
     // 创建路由
     let app = create_router(state);
@@ -25,7 +25,15 @@ impl QueryService {
         SqlValidator::validate(&req.sql)?;
 
         // 从连接服务获取连接信息
-        let _pool_info = self.get_pool_info(&req.connection_id).await?;
+        let pool_info = self.get_pool_info(&req.connection_id).await?;
+
+        // 生产环境连接上的写语句需要显式确认
+        if pool_info["data"]["environment"] == "prod" && SqlValidator::is_write(&req.sql) && !req.confirm_prod {
+            return Err(AppError::Forbidden(format!(
+                "连接 {} 为生产环境连接，执行写语句需设置 confirm_prod 确认",
+                req.connection_id
+            )));
+        }
 
         // TODO: 实现实际的查询执行逻辑
         // 目前返回占位结果
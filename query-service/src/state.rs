@@ -1,20 +1,18 @@
 //! Application state for query service.
 
-use common::config::{AppConfig, ServiceUrls};
+use common::config::ServiceUrls;
 
 /// Application state shared across handlers.
 #[derive(Clone)]
 pub struct AppState {
-    pub config: AppConfig,
     pub service_urls: ServiceUrls,
     pub http_client: reqwest::Client,
 }
 
 impl AppState {
     /// Creates a new application state.
-    pub fn new(config: AppConfig) -> Self {
+    pub fn new() -> Self {
         Self {
-            config,
             service_urls: ServiceUrls::load(),
             http_client: reqwest::Client::new(),
         }